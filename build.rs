@@ -0,0 +1,39 @@
+//! generates the table of assets embedded by `embedded-assets` feature
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// shared with the crate, which also looks fetched paths up by it
+#[allow(dead_code)]
+#[path = "src/brwsr/manifest.rs"]
+mod manifest;
+
+/// list of files under `static/` to embed, one path per line
+const MANIFEST: &str = "assets.manifest";
+const ASSET_DIR: &str = "static";
+
+fn main() {
+	println!("cargo:rerun-if-changed=build.rs");
+	if env::var_os("CARGO_FEATURE_EMBEDDED_ASSETS",).is_none() {
+		return;
+	}
+	println!("cargo:rerun-if-changed={MANIFEST}");
+
+	let root = env::var("CARGO_MANIFEST_DIR",)
+		.expect("CARGO_MANIFEST_DIR is not set",);
+	let root = Path::new(&root,);
+	let manifest = fs::read_to_string(root.join(MANIFEST,),)
+		.unwrap_or_else(|e| panic!("failed to read {MANIFEST}: {e}"),);
+
+	let dir = root.join(ASSET_DIR,);
+	for entry in manifest::entries(&manifest,) {
+		println!("cargo:rerun-if-changed={}", dir.join(entry,).display());
+	}
+	let table = manifest::table(&manifest, &dir,)
+		.unwrap_or_else(|e| panic!("{MANIFEST}: {e}"),);
+
+	let out = env::var("OUT_DIR",).expect("OUT_DIR is not set",);
+	fs::write(Path::new(&out,).join("assets.rs",), table,)
+		.expect("failed to write embedded asset table",);
+}
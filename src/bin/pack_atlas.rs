@@ -0,0 +1,75 @@
+//! packs a directory of png frames into an atlas png and its sheet json
+//!
+//! ```sh
+//! cargo run --bin pack_atlas -- <frame dir> <out.png> [--trim] [--padding N] [--max-size N]
+//! ```
+//!
+//! the sheet is written next to `out.png` with `.json` extension, in the form
+//! `engn::Sheet` deserializes
+
+use anyhow::Context;
+use anyhow::Result as Rslt;
+use anyhow::bail;
+use rust_webpack_template::engn::atlas;
+use rust_webpack_template::engn::atlas::PackOptions;
+use std::path::PathBuf;
+
+fn main() -> Rslt<(),> {
+	let mut opts = PackOptions::default();
+	let mut paths = vec![];
+	let mut args = std::env::args().skip(1,);
+	while let Some(arg,) = args.next() {
+		match arg.as_str() {
+			"--trim" => opts.trim = true,
+			"--padding" => {
+				opts.padding =
+					args.next().context("--padding needs a value",)?.parse()?
+			},
+			"--max-size" => {
+				opts.max_size =
+					args.next().context("--max-size needs a value",)?.parse()?
+			},
+			flag if flag.starts_with("--",) => bail!("unknown option {flag}"),
+			_ => paths.push(PathBuf::from(arg,),),
+		}
+	}
+	let [frame_dir, out,] = paths.as_slice() else {
+		bail!(
+			"usage: pack_atlas <frame dir> <out.png> [--trim] [--padding N] \
+			 [--max-size N]"
+		);
+	};
+
+	let mut frame_paths = std::fs::read_dir(frame_dir,)
+		.with_context(|| format!("failed to read {}", frame_dir.display()),)?
+		.map(|e| e.map(|e| e.path(),),)
+		.collect::<Result<Vec<_,>, _,>>()?;
+	frame_paths.retain(|p| {
+		p.extension().is_some_and(|e| e.eq_ignore_ascii_case("png",),)
+	},);
+	frame_paths.sort();
+	let frames = frame_paths
+		.iter()
+		.map(|p| atlas::read_png(p,),)
+		.collect::<Rslt<Vec<_,>,>>()?;
+
+	let image_name = out
+		.file_name()
+		.with_context(|| format!("{} has no file name", out.display()),)?
+		.to_string_lossy();
+	let packed = atlas::pack(frames, &opts, &image_name,)?;
+
+	atlas::write_png(out, packed.w, packed.h, &packed.rgba,)?;
+	let json = out.with_extension("json",);
+	std::fs::write(&json, serde_json::to_string_pretty(&packed.sheet,)?,)
+		.with_context(|| format!("failed to write {}", json.display()),)?;
+
+	println!(
+		"packed {} frames into {}x{} {}",
+		frame_paths.len(),
+		packed.w,
+		packed.h,
+		out.display()
+	);
+	Ok((),)
+}
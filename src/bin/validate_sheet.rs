@@ -0,0 +1,81 @@
+//! checks a sprite sheet json against its image and the clips the game plays
+//! from it
+//!
+//! ```sh
+//! cargo run --bin validate_sheet -- <sheet.json> [image.png]
+//! ```
+//!
+//! image defaults to the one named in the sheet's meta, next to the json.
+//! clips are only checked for images the game plays from. every problem
+//! found is printed, and exit code is nonzero if there is any
+
+use anyhow::Context;
+use anyhow::Result as Rslt;
+use anyhow::bail;
+use rust_webpack_template::SHEET_CLIPS;
+use rust_webpack_template::engn::Sheet;
+use rust_webpack_template::engn::Size;
+use rust_webpack_template::engn::atlas;
+use rust_webpack_template::engn::validate;
+use std::path::PathBuf;
+
+fn main() -> Rslt<(),> {
+	let args: Vec<PathBuf,> =
+		std::env::args().skip(1,).map(PathBuf::from,).collect();
+	let (sheet_path, image_path,) = match args.as_slice() {
+		[sheet,] => (sheet, None,),
+		[sheet, image,] => (sheet, Some(image.clone(),),),
+		_ => bail!("usage: validate_sheet <sheet.json> [image.png]"),
+	};
+
+	let json = std::fs::read_to_string(sheet_path,)
+		.with_context(|| format!("failed to read {}", sheet_path.display()),)?;
+	let sheet: Sheet = serde_json::from_str(&json,).with_context(|| {
+		format!("{} is not a sprite sheet", sheet_path.display())
+	},)?;
+
+	let image_path = match image_path {
+		Some(path,) => path,
+		None => {
+			let meta = sheet
+				.meta()
+				.context("sheet names no image, pass it explicitly",)?;
+			sheet_path.with_file_name(&meta.image,)
+		},
+	};
+	let image = atlas::read_png(&image_path,)?;
+	let (Ok(w,), Ok(h,),) = (u16::try_from(image.w,), u16::try_from(image.h,),)
+	else {
+		bail!(
+			"{} is {}x{}, larger than a sheet can describe",
+			image_path.display(),
+			image.w,
+			image.h
+		);
+	};
+	let size = Size { w, h, };
+
+	let name = image_path.file_name().and_then(|n| n.to_str(),);
+	let clips =
+		match SHEET_CLIPS.iter().find(|(image, _,)| Some(*image,) == name,) {
+			Some((_, clips,),) => *clips,
+			None => {
+				println!(
+					"{} has no clips in the game to check",
+					image_path.display()
+				);
+				&[]
+			},
+		};
+	let problems = validate::validate(&sheet, size, clips,);
+	for problem in problems.iter() {
+		eprintln!("{}: {problem}", sheet_path.display());
+	}
+	if !problems.is_empty() {
+		eprintln!("{} problems found", problems.len());
+		std::process::exit(1,);
+	}
+
+	println!("{} is valid", sheet_path.display());
+	Ok((),)
+}
@@ -1,6 +1,8 @@
 use crate::JRslt;
 pub use anyhow::Result as Rslt;
 use anyhow::anyhow;
+use std::cell::RefCell;
+use std::rc::Rc;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
 use wasm_bindgen::closure::IntoWasmClosure;
@@ -8,12 +10,19 @@ use wasm_bindgen::closure::WasmClosure;
 use wasm_bindgen::closure::WasmClosureFnOnce;
 use wasm_bindgen::prelude::Closure;
 use wasm_bindgen_futures::JsFuture;
+use web_sys::AddEventListenerOptions;
 use web_sys::Document;
 use web_sys::HtmlCanvasElement;
 use web_sys::HtmlImageElement;
+use web_sys::OffscreenCanvas;
 use web_sys::Response;
 use web_sys::Window;
 
+#[cfg(feature = "embedded-assets")]
+pub mod bundle;
+pub mod manifest;
+pub mod storage;
+
 macro_rules! log {
     ($($t:tt)*) => {
         web_sys::console::log_1(&format!($($t)*).into())
@@ -60,28 +69,59 @@ impl<T,> BrowserContext<T,> for Option<T,> {
 	}
 }
 
+#[derive(Clone,)]
 pub struct Renderer {
 	ctx: web_sys::CanvasRenderingContext2d,
 }
 
 impl Renderer {
-	const CANVAS_HEIGHT: f64 = 600.0;
-	const CANVAS_WIDTH: f64 = 600.0;
-
 	fn from_obj(val: JsValue,) -> Self {
 		Self {
 			ctx: web_sys::CanvasRenderingContext2d::unchecked_from_js(val,),
 		}
 	}
 
+	/// clear the whole backing store, whatever transform is set
 	pub fn clear(&self,) {
-		self.ctx.clear_rect(0.0, 0.0, Self::CANVAS_WIDTH, Self::CANVAS_HEIGHT,);
+		let Some(canvas,) = self.ctx.canvas() else {
+			return;
+		};
+		self.ctx.save();
+		let _ = self.ctx.reset_transform();
+		self.ctx.clear_rect(
+			0.0,
+			0.0,
+			canvas.width().into(),
+			canvas.height().into(),
+		);
+		self.ctx.restore();
+	}
+
+	/// turn off to keep upscaled pixel art sharp
+	pub fn set_image_smoothing(&self, enabled: bool,) {
+		self.ctx.set_image_smoothing_enabled(enabled,);
+	}
+
+	/// run `draw` with canvas transform replaced by `[a, b, c, d, e, f]`
+	pub fn with_transform<O,>(
+		&self,
+		[a, b, c, d, e, f,]: [f64; 6],
+		draw: impl FnOnce() -> Rslt<O,>,
+	) -> Rslt<O,> {
+		self.ctx.save();
+		let drawn = self
+			.ctx
+			.set_transform(a, b, c, d, e, f,)
+			.to_anhw()
+			.and_then(|_| draw(),);
+		self.ctx.restore();
+		drawn
 	}
 
 	#[allow(clippy::too_many_arguments)]
 	pub fn draw_image_opt(
 		&self,
-		image: &HtmlImageElement,
+		image: &(impl CanvasImage + ?Sized),
 		clip_x: f64,
 		clip_y: f64,
 		pos_x: f64,
@@ -89,7 +129,163 @@ impl Renderer {
 		w: f64,
 		h: f64,
 	) -> Rslt<(),> {
-		self.ctx.draw_image_with_html_image_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(image, clip_x, clip_y, w, h, pos_x, pos_y, w, h).to_anhw()
+		image
+			.draw_clipped(&self.ctx, clip_x, clip_y, w, h, pos_x, pos_y, w, h,)
+			.to_anhw()
+	}
+
+	/// draw `clip_w` x `clip_h` of `image` stretched over `w` x `h`
+	#[allow(clippy::too_many_arguments)]
+	pub fn draw_image_scaled(
+		&self,
+		image: &(impl CanvasImage + ?Sized),
+		clip_x: f64,
+		clip_y: f64,
+		clip_w: f64,
+		clip_h: f64,
+		pos_x: f64,
+		pos_y: f64,
+		w: f64,
+		h: f64,
+	) -> Rslt<(),> {
+		image
+			.draw_clipped(
+				&self.ctx, clip_x, clip_y, clip_w, clip_h, pos_x, pos_y, w, h,
+			)
+			.to_anhw()
+	}
+
+	/// `draw_image_opt` with `params` applied around `params.pivot`
+	#[allow(clippy::too_many_arguments)]
+	pub fn draw_image_with(
+		&self,
+		image: &(impl CanvasImage + ?Sized),
+		clip_x: f64,
+		clip_y: f64,
+		pos_x: f64,
+		pos_y: f64,
+		w: f64,
+		h: f64,
+		params: &DrawParams,
+	) -> Rslt<(),> {
+		if params.is_identity() {
+			return self
+				.draw_image_opt(image, clip_x, clip_y, pos_x, pos_y, w, h,);
+		}
+
+		let (pivot_x, pivot_y,) = params.pivot.unwrap_or((w / 2.0, h / 2.0,),);
+		let flip = |flipped| if flipped { -1.0 } else { 1.0 };
+
+		self.ctx.save();
+		let drawn = self
+			.ctx
+			.translate(pos_x + pivot_x, pos_y + pivot_y,)
+			.and_then(|_| self.ctx.rotate(params.rotation,),)
+			.and_then(|_| {
+				self.ctx.scale(
+					params.scale_x * flip(params.flip_x,),
+					params.scale_y * flip(params.flip_y,),
+				)
+			},)
+			.to_anhw()
+			.and_then(|_| {
+				self.ctx
+					.set_global_alpha(self.ctx.global_alpha() * params.alpha,);
+				self.draw_image_opt(
+					image, clip_x, clip_y, -pivot_x, -pivot_y, w, h,
+				)
+			},);
+		self.ctx.restore();
+		drawn
+	}
+
+	pub fn fill_rect(&self, x: f64, y: f64, w: f64, h: f64, color: &str,) {
+		self.ctx.set_fill_style_str(color,);
+		self.ctx.fill_rect(x, y, w, h,);
+	}
+
+	/// start a new path, discarding the previous one
+	pub fn begin_path(&self,) {
+		self.ctx.begin_path();
+	}
+
+	pub fn move_to(&self, x: f64, y: f64,) {
+		self.ctx.move_to(x, y,);
+	}
+
+	pub fn line_to(&self, x: f64, y: f64,) {
+		self.ctx.line_to(x, y,);
+	}
+
+	pub fn close_path(&self,) {
+		self.ctx.close_path();
+	}
+
+	pub fn rect(&self, x: f64, y: f64, w: f64, h: f64,) {
+		self.ctx.rect(x, y, w, h,);
+	}
+
+	/// clockwise arc from `start` to `end` radians
+	pub fn arc(
+		&self,
+		cx: f64,
+		cy: f64,
+		r: f64,
+		start: f64,
+		end: f64,
+	) -> Rslt<(),> {
+		self.ctx.arc(cx, cy, r, start, end,).to_anhw()
+	}
+
+	/// fill and/or stroke current path. state set here does not leak into
+	/// later draws
+	pub fn paint_path(
+		&self,
+		fill: Option<&str,>,
+		stroke: Option<&str,>,
+		line_width: f64,
+		dash: &[f64],
+		alpha: f64,
+	) -> Rslt<(),> {
+		self.ctx.save();
+		self.ctx.set_global_alpha(self.ctx.global_alpha() * alpha,);
+		let painted = (|| {
+			if let Some(color,) = fill {
+				self.ctx.set_fill_style_str(color,);
+				self.ctx.fill();
+			}
+			if let Some(color,) = stroke {
+				let dash = serde_wasm_bindgen::to_value(dash,).to_anhw()?;
+				self.ctx.set_line_dash(&dash,).to_anhw()?;
+				self.ctx.set_line_width(line_width,);
+				self.ctx.set_stroke_style_str(color,);
+				self.ctx.stroke();
+			}
+			Ok((),)
+		})();
+		self.ctx.restore();
+		painted
+	}
+
+	/// text in the browser's own `css_font` with its top at `y`, and its
+	/// left, center or right at `x` as css `align` says
+	pub fn fill_text(
+		&self,
+		text: &str,
+		x: f64,
+		y: f64,
+		color: &str,
+		css_font: &str,
+		align: &str,
+	) -> Rslt<(),> {
+		self.ctx.save();
+		self.ctx.set_font(css_font,);
+		self.ctx.set_text_baseline("top",);
+		self.ctx.set_text_align(align,);
+		self.ctx.set_fill_style_str(color,);
+		let filled = self.ctx.fill_text(text, x, y,).to_anhw();
+		self.ctx.restore();
+		filled
 	}
 
 	pub fn draw_image(&self, image: &HtmlImageElement,) -> Rslt<(),> {
@@ -97,6 +293,157 @@ impl Renderer {
 	}
 }
 
+/// how an image is transformed while it is drawn
+#[derive(Clone, Copy, Debug, PartialEq,)]
+pub struct DrawParams {
+	/// mirror left and right
+	pub flip_x:   bool,
+	/// mirror top and bottom
+	pub flip_y:   bool,
+	pub scale_x:  f64,
+	pub scale_y:  f64,
+	/// clockwise, in radians
+	pub rotation: f64,
+	/// point flips, scaling and rotation happen around, relative to the top
+	/// left corner of the image. center of the image when `None`
+	pub pivot:    Option<(f64, f64,),>,
+	/// multiplied with canvas' global alpha
+	pub alpha:    f64,
+}
+
+impl Default for DrawParams {
+	fn default() -> Self {
+		Self {
+			flip_x:   false,
+			flip_y:   false,
+			scale_x:  1.0,
+			scale_y:  1.0,
+			rotation: 0.0,
+			pivot:    None,
+			alpha:    1.0,
+		}
+	}
+}
+
+impl DrawParams {
+	pub fn scaled(scale: f64,) -> Self {
+		Self { scale_x: scale, scale_y: scale, ..Self::default() }
+	}
+
+	fn is_identity(&self,) -> bool {
+		Self { pivot: self.pivot, ..Self::default() } == *self
+	}
+}
+
+/// anything `drawImage` accepts as its source
+pub trait CanvasImage {
+	#[allow(clippy::too_many_arguments)]
+	fn draw_clipped(
+		&self,
+		ctx: &web_sys::CanvasRenderingContext2d,
+		clip_x: f64,
+		clip_y: f64,
+		clip_w: f64,
+		clip_h: f64,
+		pos_x: f64,
+		pos_y: f64,
+		w: f64,
+		h: f64,
+	) -> JRslt<(),>;
+}
+
+impl CanvasImage for HtmlImageElement {
+	fn draw_clipped(
+		&self,
+		ctx: &web_sys::CanvasRenderingContext2d,
+		clip_x: f64,
+		clip_y: f64,
+		clip_w: f64,
+		clip_h: f64,
+		pos_x: f64,
+		pos_y: f64,
+		w: f64,
+		h: f64,
+	) -> JRslt<(),> {
+		ctx.draw_image_with_html_image_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(self, clip_x, clip_y, clip_w, clip_h, pos_x, pos_y, w, h)
+	}
+}
+
+impl CanvasImage for HtmlCanvasElement {
+	fn draw_clipped(
+		&self,
+		ctx: &web_sys::CanvasRenderingContext2d,
+		clip_x: f64,
+		clip_y: f64,
+		clip_w: f64,
+		clip_h: f64,
+		pos_x: f64,
+		pos_y: f64,
+		w: f64,
+		h: f64,
+	) -> JRslt<(),> {
+		ctx.draw_image_with_html_canvas_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(self, clip_x, clip_y, clip_w, clip_h, pos_x, pos_y, w, h)
+	}
+}
+
+impl CanvasImage for OffscreenCanvas {
+	fn draw_clipped(
+		&self,
+		ctx: &web_sys::CanvasRenderingContext2d,
+		clip_x: f64,
+		clip_y: f64,
+		clip_w: f64,
+		clip_h: f64,
+		pos_x: f64,
+		pos_y: f64,
+		w: f64,
+		h: f64,
+	) -> JRslt<(),> {
+		ctx.draw_image_with_offscreen_canvas_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(self, clip_x, clip_y, clip_w, clip_h, pos_x, pos_y, w, h)
+	}
+}
+
+/// canvas never attached to the page
+pub enum Surface {
+	Offscreen(OffscreenCanvas,),
+	/// fallback for browsers without `OffscreenCanvas`
+	Element(HtmlCanvasElement,),
+}
+
+impl Surface {
+	/// `OffscreenCanvas` where the browser has one
+	pub fn new(w: u32, h: u32,) -> Rslt<Self,> {
+		match OffscreenCanvas::new(w, h,) {
+			Ok(canvas,) => Ok(Self::Offscreen(canvas,),),
+			Err(_,) => Ok(Self::Element(new_canvas(w, h,)?,),),
+		}
+	}
+}
+
+impl CanvasImage for Surface {
+	fn draw_clipped(
+		&self,
+		ctx: &web_sys::CanvasRenderingContext2d,
+		clip_x: f64,
+		clip_y: f64,
+		clip_w: f64,
+		clip_h: f64,
+		pos_x: f64,
+		pos_y: f64,
+		w: f64,
+		h: f64,
+	) -> JRslt<(),> {
+		match self {
+			Self::Offscreen(c,) => c.draw_clipped(
+				ctx, clip_x, clip_y, clip_w, clip_h, pos_x, pos_y, w, h,
+			),
+			Self::Element(c,) => c.draw_clipped(
+				ctx, clip_x, clip_y, clip_w, clip_h, pos_x, pos_y, w, h,
+			),
+		}
+	}
+}
+
 pub trait Canvas {
 	fn renderer(&self, context_id: &str,) -> Rslt<Renderer,>;
 }
@@ -113,6 +460,27 @@ impl Canvas for HtmlCanvasElement {
 	}
 }
 
+impl Canvas for OffscreenCanvas {
+	/// offscreen 2d context shares its whole api with a canvas element's one
+	fn renderer(&self, context_id: &str,) -> Rslt<Renderer,> {
+		let ctx = self
+			.get_context(context_id,)
+			.to_anhw()?
+			.ctx_ctx(&format!("context id {context_id} does not supported",),)
+			.to_anhw()?;
+		Ok(Renderer::from_obj(JsValue::from(ctx,),),)
+	}
+}
+
+impl Canvas for Surface {
+	fn renderer(&self, context_id: &str,) -> Rslt<Renderer,> {
+		match self {
+			Self::Offscreen(c,) => c.renderer(context_id,),
+			Self::Element(c,) => c.renderer(context_id,),
+		}
+	}
+}
+
 pub fn window_obj() -> Rslt<Window,> {
 	web_sys::window().dom_ctx("window object not found",).to_anhw()
 }
@@ -141,6 +509,32 @@ pub fn new_image() -> Rslt<HtmlImageElement,> {
 	HtmlImageElement::new().to_anhw()
 }
 
+/// detached canvas which is never inserted into the document
+pub fn new_canvas(w: u32, h: u32,) -> Rslt<HtmlCanvasElement,> {
+	let canvas: HtmlCanvasElement =
+		document_obj()?.create_element("canvas",).to_anhw()?.unchecked_into();
+	canvas.set_width(w,);
+	canvas.set_height(h,);
+	Ok(canvas,)
+}
+
+/// copy of `image` whose opaque pixels are painted with `color`
+///
+/// `color` is any css color string
+pub fn tinted(
+	image: &HtmlImageElement,
+	color: &str,
+) -> Rslt<HtmlCanvasElement,> {
+	let canvas = new_canvas(image.natural_width(), image.natural_height(),)?;
+	let Renderer { ctx, } = canvas.renderer("2d",)?;
+	ctx.draw_image_with_html_image_element(image, 0.0, 0.0,).to_anhw()?;
+	// keep the alpha of glyphs and replace their color
+	ctx.set_global_composite_operation("source-in",).to_anhw()?;
+	ctx.set_fill_style_str(color,);
+	ctx.fill_rect(0.0, 0.0, canvas.width().into(), canvas.height().into(),);
+	Ok(canvas,)
+}
+
 pub fn closure_once<F, A, R, T,>(fn_once: F,) -> Closure<T,>
 where
 	F: WasmClosureFnOnce<T, A, R,>, // + 'static,
@@ -170,11 +564,64 @@ pub fn request_animation_frame(cb: &Closure<dyn FnMut(f64,),>,) -> Rslt<i32,> {
 		.to_anhw()
 }
 
+pub fn device_pixel_ratio() -> Rslt<f64,> {
+	Ok(window_obj()?.device_pixel_ratio(),)
+}
+
+/// call `cb` every time the window is resized or moved to a screen with
+/// another pixel ratio
+pub fn on_resize(cb: impl FnMut() + 'static,) -> Rslt<(),> {
+	let cb: Rc<RefCell<dyn FnMut(),>,> = Rc::new(RefCell::new(cb,),);
+	let on_resize = cb.clone();
+	let listener = closure_new::<_, dyn FnMut(),>(move || {
+		(on_resize.borrow_mut())();
+	},);
+	window_obj()?
+		.add_event_listener_with_callback(
+			"resize",
+			listener.as_ref().unchecked_ref(),
+		)
+		.to_anhw()?;
+	listener.forget();
+	on_pixel_ratio_change(cb,)
+}
+
+/// call `cb` once the pixel ratio leaves its current value, then keep
+/// watching the new one. a resize event is not sent for that when the
+/// window keeps its css size
+fn on_pixel_ratio_change(cb: Rc<RefCell<dyn FnMut(),>,>,) -> Rslt<(),> {
+	let query = format!("(resolution: {}dppx)", device_pixel_ratio()?);
+	let Some(list,) = window_obj()?.match_media(&query,).to_anhw()? else {
+		return Ok((),);
+	};
+	let listener = closure_once::<_, _, _, dyn FnMut(),>(move || {
+		(cb.borrow_mut())();
+		if let Err(e,) = on_pixel_ratio_change(cb,) {
+			log!("failed to watch the pixel ratio: {e:#}");
+		}
+	},);
+	let options = AddEventListenerOptions::new();
+	options.set_once(true,);
+	list.add_event_listener_with_callback_and_add_event_listener_options(
+		"change",
+		listener.as_ref().unchecked_ref(),
+		&options,
+	)
+	.to_anhw()?;
+	listener.forget();
+	Ok((),)
+}
+
 pub fn spawn_local<F,>(future: F,)
 where F: Future<Output = (),> + 'static {
 	wasm_bindgen_futures::spawn_local(future,);
 }
 
+/// query part of page's url, including the leading `?`
+pub fn location_search() -> Rslt<String,> {
+	window_obj()?.location().search().to_anhw()
+}
+
 pub fn now() -> Rslt<f64,> {
 	Ok(window_obj()?
 		.performance()
@@ -202,6 +649,19 @@ pub trait Fetch {
 		.to_anhw()
 	}
 
+	async fn fetch_text(&self, src: &str,) -> Rslt<String,> {
+		let rsp = self.fetch_rsp(src,).await?;
+		JsFuture::from(
+			rsp.text().map_err(|e| {
+				anyhow!("could not get text from response: {e:?}")
+			},)?,
+		)
+		.await
+		.to_anhw()?
+		.as_string()
+		.ok_or_else(|| anyhow!("response body of {src} is not a string"),)
+	}
+
 	async fn fetch_json_de<D: serde::de::DeserializeOwned,>(
 		&self,
 		src: &str,
@@ -214,6 +674,10 @@ pub trait Fetch {
 
 impl Fetch for Window {
 	async fn raw_fetch(&self, src: &str,) -> Rslt<JsValue,> {
+		#[cfg(feature = "embedded-assets")]
+		if let Some(rsp,) = bundle::response(src,)? {
+			return Ok(rsp.into(),);
+		}
 		JsFuture::from(self.fetch_with_str(src,),).await.to_anhw()
 	}
 }
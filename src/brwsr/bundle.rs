@@ -0,0 +1,52 @@
+//! assets compiled into the binary by the `embedded-assets` feature
+//!
+//! embedded files are served in place of network requests, so the game runs
+//! without anything next to the wasm
+
+use crate::brwsr::Rslt;
+use crate::brwsr::ToAnyhow;
+use crate::brwsr::manifest;
+use anyhow::anyhow;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::Blob;
+use web_sys::Response;
+use web_sys::Url;
+
+include!(concat!(env!("OUT_DIR"), "/assets.rs"));
+
+/// bytes of `src` if it is listed in `assets.manifest`
+pub fn get(src: &str,) -> Option<&'static [u8],> {
+	let src = manifest::normalize(src,);
+	ASSETS.iter().find(|(path, _,)| *path == src,).map(|(_, bytes,)| *bytes,)
+}
+
+/// response carrying the embedded copy of `src`
+pub fn response(src: &str,) -> Rslt<Option<Response,>,> {
+	let Some(bytes,) = get(src,) else {
+		return Ok(None,);
+	};
+	let mut body = bytes.to_vec();
+	Response::new_with_opt_u8_array(Some(&mut body,),).to_anhw().map(Some,)
+}
+
+/// blob url pointing at the embedded copy of `src`
+///
+/// release it with [`revoke`] once the url is no longer read
+pub async fn object_url(src: &str,) -> Rslt<Option<String,>,> {
+	let Some(rsp,) = response(src,)? else {
+		return Ok(None,);
+	};
+	let blob = JsFuture::from(
+		rsp.blob()
+			.map_err(|e| anyhow!("could not get blob of {src}: {e:?}"),)?,
+	)
+	.await
+	.to_anhw()?
+	.unchecked_into::<Blob>();
+	Url::create_object_url_with_blob(&blob,).to_anhw().map(Some,)
+}
+
+pub fn revoke(url: &str,) -> Rslt<(),> {
+	Url::revoke_object_url(url,).to_anhw()
+}
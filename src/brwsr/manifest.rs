@@ -0,0 +1,46 @@
+//! `assets.manifest`, listing the files under `static/` embedded by the
+//! `embedded-assets` feature
+//!
+//! build.rs generates the table of embedded assets with this module too, so
+//! it only uses std
+
+use std::path::Path;
+
+/// paths `manifest` lists, one per line, skipping blank lines and `#`
+/// comments
+pub fn entries(manifest: &str,) -> impl Iterator<Item = &str,> {
+	manifest
+		.lines()
+		.map(str::trim,)
+		.filter(|entry| !entry.is_empty() && !entry.starts_with('#',),)
+}
+
+/// rust source of the asset table, embedding every entry of `manifest`
+/// found under `dir`
+///
+/// fails naming the first entry with no file under `dir`
+pub fn table(manifest: &str, dir: &Path,) -> Result<String, String,> {
+	let mut table =
+		String::from("pub static ASSETS: &[(&str, &[u8],)] = &[\n",);
+	for entry in entries(manifest,) {
+		let path = dir.join(entry,);
+		if !path.is_file() {
+			return Err(format!(
+				"manifest lists {entry}, but {} does not exist",
+				path.display()
+			),);
+		}
+		table += &format!(
+			"\t({entry:?}, include_bytes!({:?}),),\n",
+			path.display().to_string()
+		);
+	}
+	table += "];\n";
+	Ok(table,)
+}
+
+/// `src` as listed in the manifest, whether fetched relative to the page or
+/// from its root
+pub fn normalize(src: &str,) -> &str {
+	src.trim_start_matches("./",).trim_start_matches('/',)
+}
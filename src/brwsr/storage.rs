@@ -0,0 +1,83 @@
+//! strings kept across page loads by `localStorage`
+//!
+//! browsers may refuse storage, as private windows and sandboxed frames
+//! do. values are then kept in memory, and live only as long as the page
+
+use crate::brwsr::Rslt;
+use crate::brwsr::ToAnyhow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+pub struct Storage {
+	backing: Backing,
+}
+
+enum Backing {
+	Local(web_sys::Storage,),
+	Memory(RefCell<HashMap<String, String,>,>,),
+}
+
+impl Storage {
+	/// page's `localStorage`, or memory when it is unavailable
+	pub fn local() -> Self {
+		match local_storage() {
+			Ok(storage,) => Self { backing: Backing::Local(storage,), },
+			Err(_,) => Self::memory(),
+		}
+	}
+
+	/// storage forgetting everything with the page
+	pub fn memory() -> Self {
+		Self { backing: Backing::Memory(RefCell::new(HashMap::new(),),), }
+	}
+
+	/// whether values survive a page reload
+	pub fn is_persistent(&self,) -> bool {
+		matches!(self.backing, Backing::Local(_,))
+	}
+
+	pub fn get(&self, key: &str,) -> Rslt<Option<String,>,> {
+		match &self.backing {
+			Backing::Local(s,) => s.get_item(key,).to_anhw(),
+			Backing::Memory(m,) => Ok(m.borrow().get(key,).cloned(),),
+		}
+	}
+
+	/// store `value` under `key`. fails when the browser's quota is spent
+	pub fn set(&self, key: &str, value: &str,) -> Rslt<(),> {
+		match &self.backing {
+			Backing::Local(s,) => s.set_item(key, value,).to_anhw(),
+			Backing::Memory(m,) => {
+				m.borrow_mut().insert(key.to_string(), value.to_string(),);
+				Ok((),)
+			},
+		}
+	}
+
+	pub fn remove(&self, key: &str,) -> Rslt<(),> {
+		match &self.backing {
+			Backing::Local(s,) => s.remove_item(key,).to_anhw(),
+			Backing::Memory(m,) => {
+				m.borrow_mut().remove(key,);
+				Ok((),)
+			},
+		}
+	}
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Rslt<web_sys::Storage,> {
+	use crate::brwsr::BrowserContext;
+	// reading the property itself throws where storage is denied
+	crate::brwsr::window_obj()?
+		.local_storage()
+		.to_anhw()?
+		.brwsr_ctx("localStorage is not available",)
+		.to_anhw()
+}
+
+/// nothing to keep values in outside of a browser
+#[cfg(not(target_arch = "wasm32"))]
+fn local_storage() -> Rslt<web_sys::Storage,> {
+	anyhow::bail!("no browser to keep values in")
+}
@@ -7,37 +7,266 @@ use crate::brwsr::get_canvas_element;
 use crate::brwsr::new_image;
 use crate::brwsr::raf_closure;
 use crate::brwsr::request_animation_frame;
+use crate::engn::backend::Drawer;
+use crate::engn::backend::Src;
+use crate::engn::cache::CachedLayer;
+use crate::engn::camera::Bounds;
+use crate::engn::color::Color;
+use crate::engn::debug::DebugOverlay;
+use crate::engn::font::Align;
+use crate::engn::font::Font;
+use crate::engn::font::TextStyle;
+use crate::engn::nine_slice::Fill;
+use crate::engn::nine_slice::Insets;
+use crate::engn::queue::Command;
+use crate::engn::queue::FrameStats;
+use crate::engn::queue::Queued;
+use crate::engn::queue::RenderQueue;
+use crate::engn::shape::Shape;
+use crate::engn::shape::ShapeStyle;
+use crate::engn::software::Pixmap;
+use crate::engn::software::Raster;
+use crate::engn::tilemap::Tilemap;
+use crate::engn::viewport::Fit;
+use crate::engn::viewport::ScalePolicy;
+use crate::engn::viewport::Viewport;
 use anyhow::Context;
 use anyhow::anyhow;
 use futures::channel::mpsc::UnboundedReceiver;
 use futures::channel::mpsc::unbounded;
 use futures::channel::oneshot::Canceled;
 use serde::Deserialize;
+use serde::Serialize;
+use std::cell::Cell;
+use std::cell::Ref;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::collections::HashSet;
 use std::ops::Add;
 use std::ops::AddAssign;
 use std::rc::Rc;
 use std::sync::Mutex;
 use wasm_bindgen::JsCast;
 use web_sys::Event;
+use web_sys::HtmlCanvasElement;
 use web_sys::HtmlImageElement;
 use web_sys::KeyboardEvent;
 
+pub mod atlas;
+mod backend;
+pub mod cache;
+pub mod camera;
+pub mod color;
+pub mod debug;
+pub mod effects;
+pub mod entity;
+pub mod font;
+pub mod keyframes;
+pub mod nine_slice;
+pub mod particle;
+pub mod queue;
+pub mod rope;
+pub mod scene;
+pub mod shape;
+pub mod software;
+pub mod tilemap;
+pub mod transition;
+pub mod validate;
+pub mod viewport;
+
+pub use crate::brwsr::DrawParams;
+pub use crate::engn::camera::Camera;
+
 /// path to sprite sheet
 const SPRITE_SHEET: &str = "rhb.png";
 /// path to sprite sheet mapper
 const SPRITE_SHEET_MAPPER: &str = "rhb.json";
 const FRAME_SIZE: f32 = 1.0 / 60.0 * 1000.0;
 
+/// draws in world units seen through `camera`, or in logical pixels inside
+/// of [`Renderer::screen_space`]
+///
+/// logical pixels are fitted onto the canvas' css size and pixel ratio as
+/// `viewport` says, and refitted whenever the window is resized
 pub struct Renderer {
-	drawer: brwsr::Renderer,
+	/// context drawn into. swapped for a cache's one while it is redrawn
+	drawer:       RefCell<Drawer,>,
+	/// `None` when drawing in software
+	canvas:       Option<HtmlCanvasElement,>,
+	camera:       RefCell<Camera,>,
+	screen_space: Cell<bool,>,
+	viewport:     Viewport,
+	fit:          Cell<Fit,>,
+	resized:      Rc<Cell<bool,>,>,
+	stats:        RefCell<FrameStats,>,
+	/// whether the next flush records what it draws
+	capture:      Cell<bool,>,
+	captured:     RefCell<Vec<String,>,>,
+	/// top left of the world area held by the cache being redrawn, if any
+	offscreen:    Cell<Option<(f64, f64,),>,>,
 }
 
 impl Renderer {
 	pub async fn new(id: &str,) -> Rslt<Self,> {
-		let drawer = get_canvas_element(id,)?.renderer("2d",)?;
-		Ok(Self { drawer, },)
+		let canvas = get_canvas_element(id,)?;
+		let drawer = canvas.renderer("2d",)?;
+		let camera =
+			Camera::new(canvas.width().into(), canvas.height().into(),);
+		let fit = Viewport::native().fit(
+			canvas.width().into(),
+			canvas.height().into(),
+			1.0,
+		);
+
+		let resized = Rc::new(Cell::new(true,),);
+		let on_resize = resized.clone();
+		brwsr::on_resize(move || on_resize.set(true,),)?;
+
+		let rndrr = Self {
+			drawer: RefCell::new(Drawer::Canvas(drawer,),),
+			canvas: Some(canvas,),
+			camera: RefCell::new(camera,),
+			screen_space: Cell::new(false,),
+			viewport: Viewport::native(),
+			fit: Cell::new(fit,),
+			resized,
+			stats: RefCell::new(FrameStats::default(),),
+			capture: Cell::new(false,),
+			captured: RefCell::new(vec![],),
+			offscreen: Cell::new(None,),
+		};
+		rndrr.sync_size()?;
+		Ok(rndrr,)
+	}
+
+	/// draw into `w` x `h` pixels of memory instead of a canvas, as if to a
+	/// canvas that size on a display with a pixel ratio of 1
+	///
+	/// needs no browser, so frames can be checked in plain tests. see
+	/// [`software`]
+	pub fn software(w: u32, h: u32,) -> Rslt<Self,> {
+		let (w, h,) = (w.max(1,), h.max(1,),);
+		let fit = Viewport::native().fit(w.into(), h.into(), 1.0,);
+		let rndrr = Self {
+			drawer:       RefCell::new(Drawer::Software(Raster::new(w, h,),),),
+			canvas:       None,
+			camera:       RefCell::new(Camera::new(w.into(), h.into(),),),
+			screen_space: Cell::new(false,),
+			viewport:     Viewport::native(),
+			fit:          Cell::new(fit,),
+			resized:      Rc::new(Cell::new(true,),),
+			stats:        RefCell::new(FrameStats::default(),),
+			capture:      Cell::new(false,),
+			captured:     RefCell::new(vec![],),
+			offscreen:    Cell::new(None,),
+		};
+		rndrr.sync_size()?;
+		Ok(rndrr,)
+	}
+
+	pub fn is_software(&self,) -> bool {
+		self.canvas.is_none()
+	}
+
+	/// pixels drawn so far, when drawing in software
+	pub fn snapshot(&self,) -> Option<Pixmap,> {
+		match &*self.drawer.borrow() {
+			Drawer::Software(raster,) => Some(raster.pixmap().clone(),),
+			Drawer::Canvas(_,) => None,
+		}
+	}
+
+	pub fn camera(&self,) -> Ref<'_, Camera,> {
+		self.camera.borrow()
+	}
+
+	pub fn camera_mut(&mut self,) -> &mut Camera {
+		self.camera.get_mut()
+	}
+
+	pub fn viewport(&self,) -> Viewport {
+		self.viewport
+	}
+
+	/// resolution and scaling drawing happens at
+	pub fn set_viewport(&mut self, viewport: Viewport,) -> Rslt<(),> {
+		self.viewport = viewport;
+		self.resized.set(true,);
+		self.sync_size()
+	}
+
+	/// logical resolution currently drawn at
+	pub fn logical_size(&self,) -> (f64, f64,) {
+		self.fit.get().logical
+	}
+
+	/// refit onto the canvas if it may have changed size since last time
+	fn sync_size(&self,) -> Rslt<(),> {
+		if !self.resized.replace(false,) {
+			return Ok((),);
+		}
+
+		let fit = match (&self.canvas, &*self.drawer.borrow(),) {
+			(Some(canvas,), _,) => {
+				// css size is 0 until the canvas is laid out
+				let (mut css_w, mut css_h,) = (
+					canvas.client_width() as f64,
+					canvas.client_height() as f64,
+				);
+				if css_w <= 0.0 || css_h <= 0.0 {
+					(css_w, css_h,) =
+						(canvas.width().into(), canvas.height().into(),);
+				}
+				let fit = self.viewport.fit(
+					css_w,
+					css_h,
+					brwsr::device_pixel_ratio()?,
+				);
+
+				// resizing backing store resets context state such as
+				// smoothing
+				if (canvas.width(), canvas.height(),) != fit.backing {
+					canvas.set_width(fit.backing.0,);
+					canvas.set_height(fit.backing.1,);
+				}
+				fit
+			},
+			(None, Drawer::Software(raster,),) => {
+				let (w, h,) = raster.size();
+				self.viewport.fit(w.into(), h.into(), 1.0,)
+			},
+			(None, Drawer::Canvas(_,),) => {
+				unreachable!("canvas drawer without a canvas")
+			},
+		};
+		self.drawer.borrow().set_image_smoothing(
+			self.viewport.policy != ScalePolicy::PixelPerfect,
+		);
+		self.camera.borrow_mut().set_viewport(fit.logical.0, fit.logical.1,);
+		self.fit.set(fit,);
+		Ok((),)
+	}
+
+	/// run `draw` with positions taken as logical pixels, for hud elements
+	/// which stay put while the camera moves
+	pub fn screen_space<O,>(&self, draw: impl FnOnce(&Self,) -> O,) -> O {
+		let outer = self.screen_space.replace(true,);
+		let drawn = draw(self,);
+		self.screen_space.set(outer,);
+		drawn
+	}
+
+	/// run `draw` under the transform of the current space
+	fn placed<O,>(&self, draw: impl FnOnce() -> Rslt<O,>,) -> Rslt<O,> {
+		let fit = self.fit.get().transform;
+		let transform = if let Some((x, y,),) = self.offscreen.get() {
+			[1.0, 0.0, 0.0, 1.0, -x, -y,]
+		} else if self.screen_space.get() {
+			fit
+		} else {
+			viewport::compose(fit, self.camera.borrow().transform(),)
+		};
+		self.drawer.borrow().with_transform(transform, draw,)
 	}
 
 	pub fn draw_image(&self, img: &Image,) -> Rslt<(),> {
@@ -46,7 +275,29 @@ impl Renderer {
 		// 	.get(name,)
 		// 	.ok_or_else(|| anyhow!("{name} not found in sprite sheet"),)?;
 
-		self.drawer.draw_image(&img.image,)
+		let drawer = self.drawer.borrow();
+		self.placed(|| match &img.image {
+			Pixels::Element(element,) => drawer.draw_image_scaled(
+				img.src(),
+				0.0,
+				0.0,
+				element.natural_width().into(),
+				element.natural_height().into(),
+				0.0,
+				0.0,
+				element.natural_width().into(),
+				element.natural_height().into(),
+			),
+			Pixels::Raster(pixmap,) => drawer.draw_image_opt(
+				img.src(),
+				0.0,
+				0.0,
+				0.0,
+				0.0,
+				pixmap.w.into(),
+				pixmap.h.into(),
+			),
+		},)
 	}
 
 	pub fn draw_sprite_sheet(
@@ -55,32 +306,424 @@ impl Renderer {
 		name: &str,
 		pos: Point,
 	) -> Rslt<(),> {
-		let frame = &img
+		self.draw_sprite_sheet_with(img, name, pos, &DrawParams::default(),)
+	}
+
+	/// draw sprite `name` transformed by `params`
+	///
+	/// `params.pivot` is relative to the untrimmed sprite, so trimmed and
+	/// untrimmed sheets flip and rotate the same way
+	pub fn draw_sprite_sheet_with(
+		&self,
+		img: &Image,
+		name: &str,
+		pos: Point,
+		params: &DrawParams,
+	) -> Rslt<(),> {
+		let sprite = img
 			.sprite_sheet_mapper
 			.as_ref()
 			.context("Image struct is not sprite sheet",)?
 			.get(name,)
-			.ok_or_else(|| anyhow!("{name} not found in sprite sheet"),)?
-			.frame;
+			.ok_or_else(|| anyhow!("{name} not found in sprite sheet"),)?;
+		let frame = &sprite.frame;
+		let pos = pos + sprite.trim_offset();
+		let params = DrawParams {
+			pivot: Some(sprite.trimmed_pivot(params,),),
+			..*params
+		};
+
+		self.placed(|| {
+			self.drawer.borrow().draw_image_with(
+				img.src(),
+				frame.x_f64(),
+				frame.y_f64(),
+				pos.x_into(),
+				pos.y_into(),
+				frame.w_f64(),
+				frame.h_f64(),
+				&params,
+			)
+		},)
+	}
+
+	/// draw sprite `name` as a 9-patch over `dest`, cut by the insets its
+	/// frame carries
+	pub fn draw_nine_slice(
+		&self,
+		img: &Image,
+		name: &str,
+		dest: Bounds,
+		edges: Fill,
+		center: Fill,
+	) -> Rslt<(),> {
+		let sprite = img
+			.sprite_sheet_mapper
+			.as_ref()
+			.context("image has no sprite sheet",)?
+			.get(name,)
+			.ok_or_else(|| anyhow!("{name} not found in sprite sheet"),)?;
+		let insets = sprite
+			.nine_slice()
+			.ok_or_else(|| anyhow!("{name} has no nine slice insets"),)?;
+		let frame = sprite.frame();
+		let src = Bounds {
+			x: frame.x_f64(),
+			y: frame.y_f64(),
+			w: frame.w_f64(),
+			h: frame.h_f64(),
+		};
+
+		let drawer = self.drawer.borrow();
+		self.placed(|| {
+			for patch in nine_slice::slices(src, insets, dest, edges, center,) {
+				drawer.draw_image_scaled(
+					img.src(),
+					patch.src.x,
+					patch.src.y,
+					patch.src.w,
+					patch.src.h,
+					patch.dest.x,
+					patch.dest.y,
+					patch.dest.w,
+					patch.dest.h,
+				)?;
+			}
+			Ok((),)
+		},)
+	}
+
+	/// draw `text` with its first line's top at `pos`
+	///
+	/// `pos` is the left edge, center or right edge of each line depending
+	/// on `style.align`
+	pub fn draw_text(
+		&self,
+		font: &Font,
+		text: &str,
+		pos: Point,
+		style: &TextStyle,
+	) -> Rslt<(),> {
+		self.placed(|| {
+			for placed in font.face().layout(text, style,) {
+				let Some(glyph,) = font.face().glyph(placed.ch,) else {
+					continue;
+				};
+				let at = pos + placed.pos;
+				font.with_atlas(placed.page, style.tint, |atlas| {
+					self.drawer.borrow().draw_image_opt(
+						atlas,
+						glyph.clip.x_f64(),
+						glyph.clip.y_f64(),
+						at.x_into(),
+						at.y_into(),
+						glyph.clip.w_f64(),
+						glyph.clip.h_f64(),
+					)
+				},)?;
+			}
+			Ok((),)
+		},)
+	}
+
+	/// draw `shape` painted as `style` says
+	pub fn draw_shape(&self, shape: &Shape, style: &ShapeStyle,) -> Rslt<(),> {
+		let d = self.drawer.borrow();
+		self.placed(|| {
+			d.begin_path();
+			match shape {
+				Shape::Rect { x, y, w, h, } => d.rect(*x, *y, *w, *h,),
+				Shape::Circle { cx, cy, r, } => {
+					d.arc(*cx, *cy, *r, 0.0, std::f64::consts::TAU,)?
+				},
+				Shape::Line { from, to, } => {
+					d.move_to(from.0, from.1,);
+					d.line_to(to.0, to.1,);
+				},
+				Shape::Polyline(vs,) | Shape::Polygon(vs,) => {
+					let mut vs = vs.iter();
+					if let Some((x, y,),) = vs.next() {
+						d.move_to(*x, *y,);
+					}
+					for (x, y,) in vs {
+						d.line_to(*x, *y,);
+					}
+					if matches!(shape, Shape::Polygon(_,)) {
+						d.close_path();
+					}
+				},
+				Shape::Arc { cx, cy, r, start, end, } => {
+					d.arc(*cx, *cy, *r, *start, *end,)?
+				},
+			}
+			// a fill makes no sense for an open line, so only stroke it
+			let fill = match shape {
+				Shape::Line { .. } | Shape::Polyline(_,) => None,
+				_ => style.fill,
+			};
+			d.paint_path(
+				fill,
+				style.stroke,
+				style.line_width,
+				&style.dash,
+				style.alpha,
+			)
+		},)
+	}
+
+	/// `text` in the browser's monospace font with its top left at `pos`, for
+	/// tooling that has no bitmap font loaded
+	pub fn draw_label(
+		&self,
+		text: &str,
+		pos: (f64, f64,),
+		color: Color,
+	) -> Rslt<(),> {
+		self.draw_label_with(text, pos, color, 12.0, Align::Left,)
+	}
+
+	/// `draw_label` at `size` pixels tall, with `pos` at the top left, top
+	/// center or top right of `text` as `align` says
+	pub fn draw_label_with(
+		&self,
+		text: &str,
+		pos: (f64, f64,),
+		color: Color,
+		size: f64,
+		align: Align,
+	) -> Rslt<(),> {
+		let align = match align {
+			Align::Left => "left",
+			Align::Center => "center",
+			Align::Right => "right",
+		};
+		self.placed(|| {
+			self.drawer.borrow().fill_text(
+				text,
+				pos.0,
+				pos.1,
+				color,
+				&format!("{size}px monospace"),
+				align,
+			)
+		},)
+	}
+
+	/// redraw `layer` with `draw` when it is dirty
+	///
+	/// `draw` draws in world units as usual. anything outside of the layer's
+	/// bounds is lost
+	pub fn update_cache(
+		&self,
+		layer: &CachedLayer,
+		draw: impl FnOnce(&Self,) -> Rslt<(),>,
+	) -> Rslt<(),> {
+		if !layer.is_dirty() {
+			return Ok((),);
+		}
+		layer.drawer.clear();
+		let own = self.drawer.replace(layer.drawer.clone(),);
+		let bounds = layer.bounds();
+		let outer = self.offscreen.replace(Some((bounds.x, bounds.y,),),);
+		let drawn = draw(self,);
+		self.offscreen.set(outer,);
+		self.drawer.replace(own,);
+		if drawn.is_ok() {
+			layer.mark_clean();
+		}
+		drawn
+	}
+
+	/// draw the tiles of `map`'s layer `index` which are in view
+	pub fn draw_tiles(&self, map: &Tilemap, index: usize,) -> Rslt<(),> {
+		let Some(layer,) = map.layers().get(index,) else {
+			return Ok((),);
+		};
+		let view = if self.screen_space.get() {
+			let (w, h,) = self.logical_size();
+			Bounds { x: 0.0, y: 0.0, w, h, }
+		} else {
+			self.camera.borrow().visible()
+		};
+		let (cols, rows,) = map.visible_cells(index, &view,);
+		let grid = map.tileset.grid();
+		let (w, h,) = (grid.tile_w as f64, grid.tile_h as f64,);
+		let drawer = self.drawer.borrow();
+		self.placed(|| {
+			for row in rows {
+				for col in cols.clone() {
+					let Some(tile,) = layer.get(col, row,) else {
+						continue;
+					};
+					let (clip_x, clip_y,) = grid
+						.clip(map.tileset.resolve(tile.index, map.tick(),),);
+					drawer.draw_image_with(
+						map.tileset.image.src(),
+						clip_x,
+						clip_y,
+						map.origin.0 + col as f64 * w,
+						map.origin.1 + row as f64 * h,
+						w,
+						h,
+						&DrawParams {
+							flip_x: tile.flip_x,
+							flip_y: tile.flip_y,
+							..DrawParams::default()
+						},
+					)?;
+				}
+			}
+			Ok((),)
+		},)
+	}
+
+	/// composite the part of `layer` in view with a single `drawImage`
+	pub fn draw_cached(&self, layer: &CachedLayer,) -> Rslt<(),> {
+		let bounds = layer.bounds();
+		let area = if self.screen_space.get() {
+			Some(bounds,)
+		} else {
+			queue::intersection(&bounds, &self.camera.borrow().visible(),)
+		};
+		let Some(area,) = area else {
+			return Ok((),);
+		};
+		self.placed(|| {
+			layer.with_src(|src| {
+				self.drawer.borrow().draw_image_opt(
+					src,
+					area.x - bounds.x,
+					area.y - bounds.y,
+					area.x,
+					area.y,
+					area.w,
+					area.h,
+				)
+			},)
+		},)
+	}
+
+	/// sort and draw everything queued this frame
+	///
+	/// world layer commands entirely outside of the camera's view are skipped
+	pub fn flush(&self, queue: RenderQueue<'_,>,) -> Rslt<(),> {
+		let capture = self.capture.replace(false,);
+		let mut captured = vec![];
+		let visible = self.camera.borrow().visible();
+		let (queued, mut stats,) =
+			queue.cull(&visible, |cmd| self.bounds_of(cmd,),);
+		let mut last_source = None;
+
+		for Queued { layer, z, cmd, } in queued {
+			let screen_space = layer.is_screen_space();
+
+			let source = match &cmd {
+				Command::Sprite { image, .. } => {
+					Some(*image as *const Image as usize,)
+				},
+				Command::Text { font, .. } => {
+					Some(*font as *const Font as usize,)
+				},
+				Command::Cached(layer,) => {
+					Some(*layer as *const CachedLayer as usize,)
+				},
+				Command::Tiles { map, .. } => {
+					Some(&map.tileset as *const _ as usize,)
+				},
+				Command::Primitive { .. } | Command::Label { .. } => None,
+			};
+			if source.is_none() || source != last_source {
+				stats.batches += 1;
+			}
+			last_source = source;
+
+			if capture {
+				captured.push(format!("{layer:?} z={z} {cmd:?}"),);
+			}
+			if screen_space {
+				self.screen_space(|r| r.draw_command(&cmd,),)?;
+			} else {
+				self.draw_command(&cmd,)?;
+			}
+		}
 
-		self.drawer.draw_image_opt(
-			&img.image,
-			frame.x_f64(),
-			frame.y_f64(),
-			pos.x_into(),
-			pos.y_into(),
-			frame.w_f64(),
-			frame.h_f64(),
-		)
+		if capture {
+			*self.captured.borrow_mut() = captured;
+		}
+		*self.stats.borrow_mut() = stats;
+		Ok((),)
 	}
 
+	fn draw_command(&self, cmd: &Command<'_,>,) -> Rslt<(),> {
+		match cmd {
+			Command::Sprite { image, name, pos, params, } => {
+				self.draw_sprite_sheet_with(image, name, *pos, params,)
+			},
+			Command::Text { font, text, pos, style, } => {
+				self.draw_text(font, text, *pos, style,)
+			},
+			Command::Primitive { shape, style, } => {
+				self.draw_shape(shape, style,)
+			},
+			Command::Label { text, pos, color, size, align, } => {
+				self.draw_label_with(text, *pos, *color, *size, *align,)
+			},
+			Command::Cached(layer,) => self.draw_cached(layer,),
+			Command::Tiles { map, index, } => self.draw_tiles(map, *index,),
+		}
+	}
+
+	/// area `cmd` may touch in world units, if it is known without drawing
+	fn bounds_of(&self, cmd: &Command<'_,>,) -> Option<Bounds,> {
+		match cmd {
+			Command::Sprite { image, name, pos, params, } => {
+				image.sprite_footprint(name, *pos, params,)
+			},
+			Command::Text { .. } | Command::Label { .. } => None,
+			Command::Cached(layer,) => Some(layer.bounds(),),
+			Command::Tiles { map, index, } => map.bounds(*index,),
+			Command::Primitive { shape, style, } => {
+				let stroked = style.stroke.is_some();
+				Some(
+					shape.bounds(if stroked { style.line_width } else { 0.0 },),
+				)
+			},
+		}
+	}
+
+	/// what the last [`Renderer::flush`] did
+	pub fn frame_stats(&self,) -> FrameStats {
+		self.stats.borrow().clone()
+	}
+
+	/// make the next flush record every command it draws, in order
+	pub fn capture_next_frame(&self,) {
+		self.capture.set(true,);
+	}
+
+	/// commands recorded by the last captured flush
+	pub fn captured(&self,) -> Vec<String,> {
+		self.captured.borrow().clone()
+	}
+
+	/// clear the canvas, picking up any size change first
 	pub fn clear(&self,) {
-		self.drawer.clear();
+		if let Err(e,) = self.sync_size() {
+			log!("failed to fit canvas: {e:?}");
+		}
+		self.drawer.borrow().clear();
 	}
 }
 
+/// where an image's pixels live
+enum Pixels {
+	Element(HtmlImageElement,),
+	/// decoded in memory, for software drawing
+	Raster(Pixmap,),
+}
+
 pub struct Image {
-	image:               HtmlImageElement,
+	image:               Pixels,
 	sprite_sheet_mapper: Option<Sheet,>,
 }
 
@@ -88,46 +731,220 @@ impl Image {
 	pub async fn new() -> Rslt<Self,> {
 		let image = new_image()?;
 		// load(&image, src,).await??;
-		Ok(Self { image, sprite_sheet_mapper: None, },)
+		Ok(Self {
+			image:               Pixels::Element(image,),
+			sprite_sheet_mapper: None,
+		},)
 	}
 
 	pub async fn new_sprite_sheet() -> Rslt<Self,> {
+		Self::load_sprite_sheet(SPRITE_SHEET, SPRITE_SHEET_MAPPER,).await
+	}
+
+	/// sprite sheet drawn from image `src`, its frames mapped by json at
+	/// `mapper`
+	pub async fn load_sprite_sheet(src: &str, mapper: &str,) -> Rslt<Self,> {
 		let image = new_image()?;
-		load(&image, SPRITE_SHEET,).await??;
+		load(&image, src,).await??;
+
+		let sheet: Sheet = brwsr::window_obj()?.fetch_json_de(mapper,).await?;
+		Ok(Self {
+			image:               Pixels::Element(image,),
+			sprite_sheet_mapper: Some(sheet,),
+		},)
+	}
 
-		let sprite_sheet_mapper = Some(sprite_sheet_mapper().await?,);
-		Ok(Self { image, sprite_sheet_mapper, },)
+	/// image out of pixels already in memory, drawable by a software
+	/// [`Renderer`] only
+	pub fn from_pixmap(pixmap: Pixmap, sheet: Option<Sheet,>,) -> Self {
+		Self {
+			image:               Pixels::Raster(pixmap,),
+			sprite_sheet_mapper: sheet,
+		}
+	}
+
+	/// decode png `image` and, if given, sprite sheet json `sheet` from disk
+	/// for a software [`Renderer`]
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn read(
+		image: impl AsRef<std::path::Path,>,
+		sheet: Option<&std::path::Path,>,
+	) -> Rslt<Self,> {
+		let png = atlas::read_png(image.as_ref(),)?;
+		let pixmap = Pixmap::from_rgba(png.w, png.h, png.rgba,)?;
+		let sheet = sheet
+			.map(|path| {
+				let json =
+					std::fs::read_to_string(path,).with_context(|| {
+						format!("failed to read {}", path.display())
+					},)?;
+				serde_json::from_str(&json,).with_context(|| {
+					format!("bad sprite sheet {}", path.display())
+				},)
+			},)
+			.transpose()?;
+		Ok(Self::from_pixmap(pixmap, sheet,),)
 	}
 
 	pub async fn set_sprite_sheet(&mut self,) -> Rslt<&Self,> {
 		self.sprite_sheet_mapper = Some(sprite_sheet_mapper().await?,);
-		load(&self.image, SPRITE_SHEET,).await??;
+		set_sprite_sheet(self.element()?,).await??;
 		Ok(self,)
 	}
 
 	pub async fn load(&self, src: &str,) -> Rslt<&Self,> {
-		load(&self.image, src,).await??;
+		load(self.element()?, src,).await??;
 		Ok(self,)
 	}
+
+	fn element(&self,) -> Rslt<&HtmlImageElement,> {
+		match &self.image {
+			Pixels::Element(element,) => Ok(element,),
+			Pixels::Raster(_,) => Err(anyhow!(
+				"image is decoded in memory and can not be loaded into"
+			),),
+		}
+	}
+
+	fn src(&self,) -> Src<'_,> {
+		match &self.image {
+			Pixels::Element(element,) => Src::Canvas(element,),
+			Pixels::Raster(pixmap,) => Src::Raster(pixmap,),
+		}
+	}
+
+	/// area sprite `name` covers when drawn untransformed at `pos`, trimmed
+	/// transparent border excluded
+	pub fn sprite_bounds(&self, name: &str, pos: Point,) -> Option<Bounds,> {
+		let sprite = self.sprite_sheet_mapper.as_ref()?.get(name,)?;
+		let at = pos + sprite.trim_offset();
+		Some(Bounds {
+			x: at.x_into(),
+			y: at.y_into(),
+			w: sprite.frame.w_f64(),
+			h: sprite.frame.h_f64(),
+		},)
+	}
+
+	/// smallest box around sprite `name` drawn at `pos` transformed by
+	/// `params`, as [`Renderer::draw_sprite_sheet_with`] draws it
+	pub fn sprite_footprint(
+		&self,
+		name: &str,
+		pos: Point,
+		params: &DrawParams,
+	) -> Option<Bounds,> {
+		let sprite = self.sprite_sheet_mapper.as_ref()?.get(name,)?;
+		let Bounds { x, y, w, h, } = self.sprite_bounds(name, pos,)?;
+		let (px, py,) = sprite.trimmed_pivot(params,);
+		let flip = |flipped| if flipped { -1.0 } else { 1.0 };
+		let (sx, sy,) = (
+			params.scale_x * flip(params.flip_x,),
+			params.scale_y * flip(params.flip_y,),
+		);
+		let (sin, cos,) = params.rotation.sin_cos();
+		// corners around the pivot, scaled, then rotated, as drawers do
+		let corners =
+			[(-px, -py,), (w - px, -py,), (-px, h - py,), (w - px, h - py,),]
+				.map(|(u, v,)| {
+					let (u, v,) = (u * sx, v * sy,);
+					(x + px + cos * u - sin * v, y + py + sin * u + cos * v,)
+				},);
+		let (mut min, mut max,) = (corners[0], corners[0],);
+		for (cx, cy,) in corners {
+			min = (min.0.min(cx,), min.1.min(cy,),);
+			max = (max.0.max(cx,), max.1.max(cy,),);
+		}
+		Some(Bounds { x: min.0, y: min.1, w: max.0 - min.0, h: max.1 - min.1, },)
+	}
 }
 
-#[derive(Deserialize, Debug,)]
+#[derive(Serialize, Deserialize, Debug,)]
 pub struct Sheet {
-	frames: HashMap<String, Sprite,>,
+	frames: BTreeMap<String, Sprite,>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	meta:   Option<Meta,>,
 }
 
 impl Sheet {
 	pub fn get<'a,>(&'a self, key: &str,) -> Option<&'a Sprite,> {
 		self.frames.get(key,)
 	}
+
+	/// frames sorted by name
+	pub fn iter(&self,) -> impl Iterator<Item = (&String, &Sprite,),> {
+		self.frames.iter()
+	}
+
+	pub fn meta(&self,) -> Option<&Meta,> {
+		self.meta.as_ref()
+	}
 }
 
-#[derive(Deserialize, Debug,)]
+#[derive(Serialize, Deserialize, Debug,)]
+#[serde(rename_all = "camelCase")]
 pub struct Sprite {
-	frame: Rect,
+	frame:              Rect,
+	#[serde(default)]
+	rotated:            bool,
+	#[serde(default)]
+	trimmed:            bool,
+	/// where `frame` sits inside the untrimmed image
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	sprite_source_size: Option<Rect,>,
+	/// size of the untrimmed image
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	source_size:        Option<Size,>,
+	/// borders for drawing this frame as a 9-patch
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	nine_slice:         Option<Insets,>,
 }
 
-#[derive(Deserialize, Debug,)]
+impl Sprite {
+	pub fn frame(&self,) -> &Rect {
+		&self.frame
+	}
+
+	pub fn nine_slice(&self,) -> Option<Insets,> {
+		self.nine_slice
+	}
+
+	/// offset trimmed pixels have to be drawn at to stay where they were in
+	/// the untrimmed image
+	pub fn trim_offset(&self,) -> Point {
+		match self.sprite_source_size {
+			Some(Rect { x, y, .. },) => Point { x: x as i16, y: y as i16, },
+			None => Point { x: 0, y: 0, },
+		}
+	}
+
+	/// `params.pivot`, or the untrimmed center, relative to the trimmed frame
+	fn trimmed_pivot(&self, params: &DrawParams,) -> (f64, f64,) {
+		let frame = &self.frame;
+		let (src_w, src_h,) =
+			self.source_size.map_or((frame.w_f64(), frame.h_f64(),), |s| {
+				(s.w.into(), s.h.into(),)
+			},);
+		let (pivot_x, pivot_y,) =
+			params.pivot.unwrap_or((src_w / 2.0, src_h / 2.0,),);
+		let trim = self.trim_offset();
+		(pivot_x - trim.x_into(), pivot_y - trim.y_into(),)
+	}
+}
+
+#[derive(Serialize, Deserialize, Debug,)]
+pub struct Meta {
+	pub image: String,
+	pub size:  Size,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq,)]
+pub struct Size {
+	pub w: u16,
+	pub h: u16,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq,)]
 pub struct Rect {
 	x: u16,
 	y: u16,
@@ -192,30 +1009,48 @@ async fn load(
 	to.set_onload(Some(success_cb.as_ref().unchecked_ref(),),);
 	to.set_onerror(Some(error_cb.as_ref().unchecked_ref(),),);
 
+	// embedded assets are handed to the image element as blob urls
+	#[cfg(feature = "embedded-assets")]
+	let bundled = brwsr::bundle::object_url(src,).await?;
+	#[cfg(feature = "embedded-assets")]
+	let src = bundled.as_deref().unwrap_or(src,);
 	to.set_src(src,);
 
 	// flipping container here enables using `?` shorthand on functions
 	// web_sys provides
-	rx.await.flip_err()
+	let loaded = rx.await.flip_err();
+
+	#[cfg(feature = "embedded-assets")]
+	if let Some(url,) = bundled {
+		brwsr::bundle::revoke(&url,)?;
+	}
+	loaded
 }
 
 pub trait Game: std::marker::Sized {
 	async fn init(&mut self,) -> Rslt<(),>;
 	fn update(&mut self, kb_state: &KeyboardState,);
 	fn draw(&self,);
+	/// draw whatever helps debugging over the finished frame. only called
+	/// while `overlay` is enabled
+	fn draw_debug(&self, _overlay: &DebugOverlay,) {}
 }
 
 pub struct GameLoop {
 	last_frame:        f64,
 	accumulated_delta: f32,
+	debug:             DebugOverlay,
 }
 
 impl GameLoop {
 	pub async fn start(mut game: impl Game + 'static,) -> Rslt<(),> {
 		let mut kbe_rx = prepare_input()?;
 		game.init().await?;
-		let mut game_loop =
-			Self { last_frame: brwsr::now()?, accumulated_delta: 0.0, };
+		let mut game_loop = Self {
+			last_frame:        brwsr::now()?,
+			accumulated_delta: 0.0,
+			debug:             DebugOverlay::from_url(),
+		};
 
 		let f = Rc::new(RefCell::new(None,),);
 		let g = f.clone();
@@ -223,14 +1058,20 @@ impl GameLoop {
 		let mut kb_stat = KeyboardState::new();
 		*f.borrow_mut() = Some(raf_closure(move |perf| {
 			kb_stat.process_input(&mut kbe_rx,);
+			game_loop.debug.handle_input(&kb_stat,);
 			game_loop.accumulated_delta += (perf - game_loop.last_frame) as f32;
 
 			while game_loop.accumulated_delta > FRAME_SIZE {
 				game.update(&kb_stat,);
+				game_loop.debug.on_tick(perf,);
 				game_loop.accumulated_delta -= FRAME_SIZE;
 			}
 			game_loop.last_frame = perf;
 			game.draw();
+			game_loop.debug.on_frame(perf,);
+			if game_loop.debug.is_enabled() {
+				game.draw_debug(&game_loop.debug,);
+			}
 			request_animation_frame(g.borrow().as_ref().unwrap(),)
 				.unwrap_or_else(|e| {
 					panic!(
@@ -285,24 +1126,28 @@ fn prepare_input() -> Rslt<UnboundedReceiver<KeyState,>,> {
 	Ok(rx,)
 }
 
+#[derive(Clone, Debug, Default,)]
 pub struct KeyboardState {
-	pressed_keys: HashMap<String, KeyboardEvent,>,
+	pressed_keys: HashSet<String,>,
 }
 
 impl KeyboardState {
-	fn new() -> Self {
-		Self { pressed_keys: HashMap::new(), }
+	/// nothing pressed
+	pub fn new() -> Self {
+		Self::default()
 	}
 
 	pub fn is_pressed(&self, code: &str,) -> bool {
-		self.pressed_keys.contains_key(code,)
+		self.pressed_keys.contains(code,)
 	}
 
-	fn set_pressed(&mut self, code: &str, event: KeyboardEvent,) {
-		self.pressed_keys.insert(code.into(), event,);
+	/// hold key `code` down, as a simulated input would
+	pub fn press(&mut self, code: &str,) {
+		self.pressed_keys.insert(code.into(),);
 	}
 
-	fn set_released(&mut self, code: &str,) {
+	/// let go of key `code`
+	pub fn release(&mut self, code: &str,) {
 		self.pressed_keys.remove(code,);
 	}
 
@@ -310,10 +1155,10 @@ impl KeyboardState {
 		loop {
 			match kbe_rx.try_next() {
 				Ok(Some(KeyState::Down(event,),),) => {
-					self.set_pressed(&event.code(), event,)
+					self.press(&event.code(),)
 				},
 				Ok(Some(KeyState::Up(event,),),) => {
-					self.set_released(&event.code(),)
+					self.release(&event.code(),)
 				},
 				_ => {
 					// log!("prsd kys: {:?}", self.pressed_keys);
@@ -324,7 +1169,7 @@ impl KeyboardState {
 	}
 }
 
-#[derive(Clone, Copy,)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize,)]
 pub struct Point {
 	pub x: i16,
 	pub y: i16,
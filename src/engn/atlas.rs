@@ -0,0 +1,348 @@
+//! packs individual frames into one atlas image described by a [`Sheet`]
+
+use crate::brwsr::Rslt;
+use crate::engn::Meta;
+use crate::engn::Rect;
+use crate::engn::Sheet;
+use crate::engn::Size;
+use crate::engn::Sprite;
+use anyhow::bail;
+use std::collections::BTreeMap;
+
+/// bytes per pixel of rgba8 buffers
+const BPP: usize = 4;
+/// widest and tallest a sheet can describe, its sizes being `u16`
+const MAX_SIDE: u32 = u16::MAX as u32;
+
+/// `(x, y)` of a top left corner
+type Corner = (u32, u32,);
+/// `(x, y, w, h)` of a rectangle in the atlas
+type Area = (u32, u32, u32, u32,);
+
+/// one frame to pack, as straight rgba8 pixels
+pub struct Frame {
+	pub name: String,
+	pub w:    u32,
+	pub h:    u32,
+	pub rgba: Vec<u8,>,
+}
+
+#[derive(Clone, Debug,)]
+pub struct PackOptions {
+	/// cut fully transparent rows and columns off every frame's edges
+	pub trim:     bool,
+	/// transparent pixels kept between frames
+	pub padding:  u32,
+	/// atlas never grows past this in either direction
+	pub max_size: u32,
+}
+
+impl Default for PackOptions {
+	fn default() -> Self {
+		Self { trim: false, padding: 0, max_size: 4096, }
+	}
+}
+
+/// packed atlas image and the sheet mapping frame names into it
+pub struct Atlas {
+	pub sheet: Sheet,
+	pub w:     u32,
+	pub h:     u32,
+	pub rgba:  Vec<u8,>,
+}
+
+/// pack `frames` into a single atlas named `image` in the sheet's meta
+pub fn pack(
+	frames: Vec<Frame,>,
+	opts: &PackOptions,
+	image: &str,
+) -> Rslt<Atlas,> {
+	if opts.max_size > MAX_SIDE {
+		bail!(
+			"atlas cannot be larger than {MAX_SIDE}x{MAX_SIDE}, not {}",
+			opts.max_size
+		);
+	}
+	for f in frames.iter() {
+		if f.w > MAX_SIDE || f.h > MAX_SIDE {
+			bail!(
+				"{} is {}x{}, larger than the {MAX_SIDE}x{MAX_SIDE} a sheet \
+				 can describe",
+				f.name,
+				f.w,
+				f.h
+			);
+		}
+		if f.rgba.len() != f.w as usize * f.h as usize * BPP {
+			bail!(
+				"{} is {}x{} but has {} bytes",
+				f.name,
+				f.w,
+				f.h,
+				f.rgba.len()
+			);
+		}
+	}
+
+	let trims: Vec<Trim,> = frames
+		.iter()
+		.map(|f| if opts.trim { Trim::of(f,) } else { Trim::none(f,) },)
+		.collect();
+
+	// largest first keeps big frames from being squeezed into leftovers
+	let mut order: Vec<usize,> = (0..frames.len()).collect();
+	order.sort_by_key(|&i| {
+		let t = &trims[i];
+		(std::cmp::Reverse(t.w.max(t.h,),), std::cmp::Reverse(t.w * t.h,),)
+	},);
+
+	// padding too large to add up only makes frames too big to fit
+	let sizes: Vec<(u32, u32,),> = order
+		.iter()
+		.map(|&i| {
+			let t = &trims[i];
+			(
+				t.w.saturating_add(opts.padding,),
+				t.h.saturating_add(opts.padding,),
+			)
+		},)
+		.collect();
+	let (places, w, h,) = fit(&sizes, opts.max_size,)?;
+
+	let mut rgba = vec![0; w as usize * h as usize * BPP];
+	let mut sprites = BTreeMap::new();
+	for (&i, &(x, y,),) in order.iter().zip(places.iter(),) {
+		let (f, t,) = (&frames[i], &trims[i],);
+		for row in 0..t.h {
+			let src = ((t.y + row) * f.w + t.x) as usize * BPP;
+			let dst = ((y + row) * w + x) as usize * BPP;
+			let len = t.w as usize * BPP;
+			rgba[dst..dst + len].copy_from_slice(&f.rgba[src..src + len],);
+		}
+
+		let sprite = Sprite {
+			frame:              rect(x, y, t.w, t.h,),
+			rotated:            false,
+			trimmed:            t.w != f.w || t.h != f.h,
+			sprite_source_size: Some(rect(t.x, t.y, t.w, t.h,),),
+			source_size:        Some(Size { w: f.w as u16, h: f.h as u16, },),
+			nine_slice:         None,
+		};
+		sprites.insert(f.name.clone(), sprite,);
+	}
+
+	let meta = Meta {
+		image: image.to_string(),
+		size:  Size { w: w as u16, h: h as u16, },
+	};
+	Ok(Atlas {
+		sheet: Sheet { frames: sprites, meta: Some(meta,), },
+		w,
+		h,
+		rgba,
+	},)
+}
+
+fn rect(x: u32, y: u32, w: u32, h: u32,) -> Rect {
+	Rect { x: x as u16, y: y as u16, w: w as u16, h: h as u16, }
+}
+
+/// opaque part of a frame
+struct Trim {
+	x: u32,
+	y: u32,
+	w: u32,
+	h: u32,
+}
+
+impl Trim {
+	fn none(f: &Frame,) -> Self {
+		Self { x: 0, y: 0, w: f.w, h: f.h, }
+	}
+
+	fn of(f: &Frame,) -> Self {
+		let alpha = |x: u32, y: u32| f.rgba[(y * f.w + x) as usize * BPP + 3];
+		let opaque_row = |y| (0..f.w).any(|x| alpha(x, y,) != 0,);
+		let opaque_col = |x| (0..f.h).any(|y| alpha(x, y,) != 0,);
+
+		let Some(top,) = (0..f.h).find(|&y| opaque_row(y,),) else {
+			// fully transparent frames keep a single pixel so they still
+			// have a place in the atlas
+			return Self { x: 0, y: 0, w: 1.min(f.w,), h: 1.min(f.h,), };
+		};
+		let bottom = (0..f.h).rev().find(|&y| opaque_row(y,),).unwrap_or(top,);
+		let left = (0..f.w).find(|&x| opaque_col(x,),).unwrap_or(0,);
+		let right = (0..f.w).rev().find(|&x| opaque_col(x,),).unwrap_or(left,);
+
+		Self { x: left, y: top, w: right - left + 1, h: bottom - top + 1, }
+	}
+}
+
+/// place every size in the smallest atlas found by growing one side at a
+/// time, returning top left corners in the order of `sizes`
+fn fit(
+	sizes: &[(u32, u32,)],
+	max_size: u32,
+) -> Rslt<(Vec<Corner,>, u32, u32,),> {
+	let widest = sizes.iter().map(|s| s.0,).max().unwrap_or(1,);
+	let tallest = sizes.iter().map(|s| s.1,).max().unwrap_or(1,);
+	if widest > max_size || tallest > max_size {
+		bail!("frames do not fit in a {max_size}x{max_size} atlas");
+	}
+	// sides of at most 65535 keep the area of any sheet within u64
+	let area: u64 =
+		sizes.iter().map(|&(w, h,)| u64::from(w,) * u64::from(h,),).sum();
+
+	let mut w = widest.max((area as f64).sqrt() as u32,).max(1,);
+	let rows = area.div_ceil(w.into(),);
+	let mut h = tallest.max(u32::try_from(rows,).unwrap_or(u32::MAX,),).max(1,);
+	loop {
+		if w > max_size || h > max_size {
+			bail!("frames do not fit in a {max_size}x{max_size} atlas");
+		}
+
+		if let Some(places,) = MaxRects::new(w, h,).insert_all(sizes,) {
+			// shrink to what is actually used
+			let used_w = places.iter().zip(sizes,).map(|(p, s,)| p.0 + s.0,);
+			let used_h = places.iter().zip(sizes,).map(|(p, s,)| p.1 + s.1,);
+			let (w, h,) =
+				(used_w.max().unwrap_or(0,), used_h.max().unwrap_or(0,),);
+			return Ok((places, w.max(1,), h.max(1,),),);
+		}
+
+		if w <= h {
+			w += (w / 8).max(1,);
+		} else {
+			h += (h / 8).max(1,);
+		}
+	}
+}
+
+/// maximal rectangles bin packer placing by best short side fit
+struct MaxRects {
+	free: Vec<Area,>,
+}
+
+impl MaxRects {
+	fn new(w: u32, h: u32,) -> Self {
+		Self { free: vec![(0, 0, w, h,)], }
+	}
+
+	fn insert_all(mut self, sizes: &[(u32, u32,)],) -> Option<Vec<Corner,>,> {
+		sizes.iter().map(|&(w, h,)| self.insert(w, h,),).collect()
+	}
+
+	fn insert(&mut self, w: u32, h: u32,) -> Option<Corner,> {
+		let &(x, y, ..,) =
+			self.free.iter().filter(|f| f.2 >= w && f.3 >= h,).min_by_key(
+				|f| ((f.2 - w).min(f.3 - h,), (f.2 - w).max(f.3 - h,),),
+			)?;
+		let used = (x, y, w, h,);
+
+		let mut free = vec![];
+		for f in self.free.drain(..,) {
+			if !overlaps(f, used,) {
+				free.push(f,);
+				continue;
+			}
+			// keep the parts of `f` on each side of `used`
+			if used.0 > f.0 {
+				free.push((f.0, f.1, used.0 - f.0, f.3,),);
+			}
+			if used.0 + used.2 < f.0 + f.2 {
+				let x = used.0 + used.2;
+				free.push((x, f.1, f.0 + f.2 - x, f.3,),);
+			}
+			if used.1 > f.1 {
+				free.push((f.0, f.1, f.2, used.1 - f.1,),);
+			}
+			if used.1 + used.3 < f.1 + f.3 {
+				let y = used.1 + used.3;
+				free.push((f.0, y, f.2, f.1 + f.3 - y,),);
+			}
+		}
+
+		// drop free rects contained in another one
+		let contained = |a: &Area, b: &Area| {
+			a.0 >= b.0
+				&& a.1 >= b.1
+				&& a.0 + a.2 <= b.0 + b.2
+				&& a.1 + a.3 <= b.1 + b.3
+		};
+		self.free = free
+			.iter()
+			.enumerate()
+			.filter(|(i, a,)| {
+				!free.iter().enumerate().any(|(j, b,)| {
+					*i != j && contained(a, b,) && (!contained(b, a,) || j < *i)
+				},)
+			},)
+			.map(|(_, f,)| *f,)
+			.collect();
+
+		Some((x, y,),)
+	}
+}
+
+fn overlaps(a: Area, b: Area,) -> bool {
+	a.0 < b.0 + b.2 && b.0 < a.0 + a.2 && a.1 < b.1 + b.3 && b.1 < a.1 + a.3
+}
+
+/// decode png at `path` into rgba8 pixels named after its file name
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_png(path: &std::path::Path,) -> Rslt<Frame,> {
+	use anyhow::Context;
+	use png::ColorType;
+
+	let file = std::fs::File::open(path,)
+		.with_context(|| format!("failed to open {}", path.display()),)?;
+	let mut decoder = png::Decoder::new(std::io::BufReader::new(file,),);
+	decoder.set_transformations(png::Transformations::normalize_to_color8(),);
+	let mut reader = decoder.read_info()?;
+	let mut buf = vec![0; reader.output_buffer_size().unwrap_or_default()];
+	let info = reader.next_frame(&mut buf,)?;
+	buf.truncate(info.buffer_size(),);
+
+	let rgba = match info.color_type {
+		ColorType::Rgba => buf,
+		ColorType::Rgb => {
+			buf.chunks(3,).flat_map(|p| [p[0], p[1], p[2], 255,],).collect()
+		},
+		ColorType::GrayscaleAlpha => {
+			buf.chunks(2,).flat_map(|p| [p[0], p[0], p[0], p[1],],).collect()
+		},
+		ColorType::Grayscale => {
+			buf.iter().flat_map(|&g| [g, g, g, 255,],).collect()
+		},
+		ColorType::Indexed => {
+			bail!("{} was not expanded from palette", path.display())
+		},
+	};
+	let name = path
+		.file_name()
+		.with_context(|| format!("{} has no file name", path.display()),)?
+		.to_string_lossy()
+		.into_owned();
+	Ok(Frame { name, w: info.width, h: info.height, rgba, },)
+}
+
+/// encode rgba8 pixels as png at `path`
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_png(
+	path: &std::path::Path,
+	w: u32,
+	h: u32,
+	rgba: &[u8],
+) -> Rslt<(),> {
+	use anyhow::Context;
+
+	let file = std::fs::File::create(path,)
+		.with_context(|| format!("failed to create {}", path.display()),)?;
+	let mut encoder = png::Encoder::new(std::io::BufWriter::new(file,), w, h,);
+	encoder.set_color(png::ColorType::Rgba,);
+	encoder.set_depth(png::BitDepth::Eight,);
+	let mut writer = encoder.write_header()?;
+	writer.write_image_data(rgba,)?;
+	writer.finish()?;
+	Ok((),)
+}
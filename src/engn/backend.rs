@@ -0,0 +1,231 @@
+//! what [`Renderer`](crate::engn::Renderer) draws through: a browser canvas
+//! or the software [`Raster`]
+//!
+//! images only draw onto the backend they were loaded for. anything else is
+//! skipped, so a frame rendered in software simply lacks text drawn from a
+//! browser only font
+
+use crate::brwsr;
+use crate::brwsr::CanvasImage;
+use crate::brwsr::DrawParams;
+use crate::brwsr::Rslt;
+use crate::engn::color::Color;
+use crate::engn::software::Pixmap;
+use crate::engn::software::Raster;
+use crate::engn::viewport::Transform;
+
+/// pixels an image is drawn from
+#[derive(Clone, Copy,)]
+pub(super) enum Src<'a,> {
+	Canvas(&'a dyn CanvasImage,),
+	Raster(&'a Pixmap,),
+}
+
+#[derive(Clone,)]
+pub(super) enum Drawer {
+	Canvas(brwsr::Renderer,),
+	Software(Raster,),
+}
+
+impl Drawer {
+	pub(super) fn clear(&self,) {
+		match self {
+			Self::Canvas(d,) => d.clear(),
+			Self::Software(d,) => d.clear(),
+		}
+	}
+
+	pub(super) fn set_image_smoothing(&self, enabled: bool,) {
+		// software drawing always samples the nearest pixel
+		if let Self::Canvas(d,) = self {
+			d.set_image_smoothing(enabled,);
+		}
+	}
+
+	pub(super) fn with_transform<O,>(
+		&self,
+		transform: Transform,
+		draw: impl FnOnce() -> Rslt<O,>,
+	) -> Rslt<O,> {
+		match self {
+			Self::Canvas(d,) => d.with_transform(transform, draw,),
+			Self::Software(d,) => d.with_transform(transform, draw,),
+		}
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub(super) fn draw_image_opt(
+		&self,
+		image: Src<'_,>,
+		clip_x: f64,
+		clip_y: f64,
+		pos_x: f64,
+		pos_y: f64,
+		w: f64,
+		h: f64,
+	) -> Rslt<(),> {
+		self.draw_image_with(
+			image,
+			clip_x,
+			clip_y,
+			pos_x,
+			pos_y,
+			w,
+			h,
+			&DrawParams::default(),
+		)
+	}
+
+	/// draw `clip_w` x `clip_h` of `image` stretched over `w` x `h`
+	#[allow(clippy::too_many_arguments)]
+	pub(super) fn draw_image_scaled(
+		&self,
+		image: Src<'_,>,
+		clip_x: f64,
+		clip_y: f64,
+		clip_w: f64,
+		clip_h: f64,
+		pos_x: f64,
+		pos_y: f64,
+		w: f64,
+		h: f64,
+	) -> Rslt<(),> {
+		match (self, image,) {
+			(Self::Canvas(d,), Src::Canvas(image,),) => d.draw_image_scaled(
+				image, clip_x, clip_y, clip_w, clip_h, pos_x, pos_y, w, h,
+			),
+			(Self::Software(d,), Src::Raster(image,),) => {
+				d.draw_image(
+					image,
+					[clip_x, clip_y, clip_w, clip_h,],
+					[pos_x, pos_y, w, h,],
+					&DrawParams::default(),
+				);
+				Ok((),)
+			},
+			_ => Ok((),),
+		}
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub(super) fn draw_image_with(
+		&self,
+		image: Src<'_,>,
+		clip_x: f64,
+		clip_y: f64,
+		pos_x: f64,
+		pos_y: f64,
+		w: f64,
+		h: f64,
+		params: &DrawParams,
+	) -> Rslt<(),> {
+		match (self, image,) {
+			(Self::Canvas(d,), Src::Canvas(image,),) => d.draw_image_with(
+				image, clip_x, clip_y, pos_x, pos_y, w, h, params,
+			),
+			(Self::Software(d,), Src::Raster(image,),) => {
+				d.draw_image(
+					image,
+					[clip_x, clip_y, w, h,],
+					[pos_x, pos_y, w, h,],
+					params,
+				);
+				Ok((),)
+			},
+			_ => Ok((),),
+		}
+	}
+
+	pub(super) fn begin_path(&self,) {
+		match self {
+			Self::Canvas(d,) => d.begin_path(),
+			Self::Software(d,) => d.begin_path(),
+		}
+	}
+
+	pub(super) fn move_to(&self, x: f64, y: f64,) {
+		match self {
+			Self::Canvas(d,) => d.move_to(x, y,),
+			Self::Software(d,) => d.move_to(x, y,),
+		}
+	}
+
+	pub(super) fn line_to(&self, x: f64, y: f64,) {
+		match self {
+			Self::Canvas(d,) => d.line_to(x, y,),
+			Self::Software(d,) => d.line_to(x, y,),
+		}
+	}
+
+	pub(super) fn close_path(&self,) {
+		match self {
+			Self::Canvas(d,) => d.close_path(),
+			Self::Software(d,) => d.close_path(),
+		}
+	}
+
+	pub(super) fn rect(&self, x: f64, y: f64, w: f64, h: f64,) {
+		match self {
+			Self::Canvas(d,) => d.rect(x, y, w, h,),
+			Self::Software(d,) => d.rect(x, y, w, h,),
+		}
+	}
+
+	pub(super) fn arc(
+		&self,
+		cx: f64,
+		cy: f64,
+		r: f64,
+		start: f64,
+		end: f64,
+	) -> Rslt<(),> {
+		match self {
+			Self::Canvas(d,) => d.arc(cx, cy, r, start, end,),
+			Self::Software(d,) => {
+				d.arc(cx, cy, r, start, end,);
+				Ok((),)
+			},
+		}
+	}
+
+	pub(super) fn paint_path(
+		&self,
+		fill: Option<Color,>,
+		stroke: Option<Color,>,
+		line_width: f64,
+		dash: &[f64],
+		alpha: f64,
+	) -> Rslt<(),> {
+		match self {
+			Self::Canvas(d,) => d.paint_path(
+				fill.map(Color::to_css,).as_deref(),
+				stroke.map(Color::to_css,).as_deref(),
+				line_width,
+				dash,
+				alpha,
+			),
+			Self::Software(d,) => {
+				d.paint_path(fill, stroke, line_width, dash, alpha,);
+				Ok((),)
+			},
+		}
+	}
+
+	/// browser font text. software drawing has no fonts and skips it
+	pub(super) fn fill_text(
+		&self,
+		text: &str,
+		x: f64,
+		y: f64,
+		color: Color,
+		css_font: &str,
+		align: &str,
+	) -> Rslt<(),> {
+		match self {
+			Self::Canvas(d,) => {
+				d.fill_text(text, x, y, &color.to_css(), css_font, align,)
+			},
+			Self::Software(_,) => Ok((),),
+		}
+	}
+}
@@ -0,0 +1,70 @@
+//! scenery drawn once into an offscreen surface and composited every frame
+//! with a single `drawImage`
+
+use crate::brwsr::Canvas;
+use crate::brwsr::Rslt;
+use crate::brwsr::Surface;
+use crate::engn::Renderer;
+use crate::engn::backend::Drawer;
+use crate::engn::backend::Src;
+use crate::engn::camera::Bounds;
+use crate::engn::software::Raster;
+use std::cell::Cell;
+
+pub struct CachedLayer {
+	/// offscreen canvas drawn through `drawer`, `None` for software drawing
+	surface:           Option<Surface,>,
+	pub(super) drawer: Drawer,
+	/// area of the world held, at one pixel per world unit
+	bounds:            Bounds,
+	dirty:             Cell<bool,>,
+}
+
+impl CachedLayer {
+	/// empty cache of the world area `bounds` for `rndrr` to draw, dirty until
+	/// first drawn
+	pub fn new(rndrr: &Renderer, bounds: Bounds,) -> Rslt<Self,> {
+		let (w, h,) = (
+			bounds.w.ceil().max(1.0,) as u32,
+			bounds.h.ceil().max(1.0,) as u32,
+		);
+		let (surface, drawer,) = if rndrr.is_software() {
+			(None, Drawer::Software(Raster::new(w, h,),),)
+		} else {
+			let surface = Surface::new(w, h,)?;
+			let drawer = Drawer::Canvas(surface.renderer("2d",)?,);
+			(Some(surface,), drawer,)
+		};
+		Ok(Self { surface, drawer, bounds, dirty: Cell::new(true,), },)
+	}
+
+	pub fn bounds(&self,) -> Bounds {
+		self.bounds
+	}
+
+	/// have the content redrawn next time it is updated
+	pub fn mark_dirty(&self,) {
+		self.dirty.set(true,);
+	}
+
+	pub fn is_dirty(&self,) -> bool {
+		self.dirty.get()
+	}
+
+	pub(super) fn mark_clean(&self,) {
+		self.dirty.set(false,);
+	}
+
+	/// run `draw` with the cached pixels as an image source
+	pub(super) fn with_src<O,>(&self, draw: impl FnOnce(Src<'_,>,) -> O,) -> O {
+		match (&self.surface, &self.drawer,) {
+			(_, Drawer::Software(raster,),) => {
+				draw(Src::Raster(&raster.pixmap(),),)
+			},
+			(Some(surface,), _,) => draw(Src::Canvas(surface,),),
+			(None, Drawer::Canvas(_,),) => {
+				unreachable!("canvas cache without a surface")
+			},
+		}
+	}
+}
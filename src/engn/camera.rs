@@ -0,0 +1,154 @@
+//! view into the world, mapping world units onto canvas pixels
+
+use crate::engn::Point;
+
+/// axis aligned area in world units
+#[derive(Clone, Copy, Debug, PartialEq,)]
+pub struct Bounds {
+	pub x: f64,
+	pub y: f64,
+	pub w: f64,
+	pub h: f64,
+}
+
+#[derive(Clone, Debug,)]
+pub struct Camera {
+	/// world position shown at the center of the viewport
+	center:        (f64, f64,),
+	/// canvas pixels per world unit
+	zoom:          f64,
+	/// size of the area drawn into, in canvas pixels
+	viewport:      (f64, f64,),
+	/// `follow` target may move this far around the center, in world units,
+	/// before the camera moves
+	pub dead_zone: (f64, f64,),
+	/// fraction of the remaining distance covered every `follow`. `1.0`
+	/// snaps onto the target
+	pub smoothing: f64,
+	/// area the camera never shows anything outside of
+	pub bounds:    Option<Bounds,>,
+	/// added to the center while drawing, for effects such as shake. ignored
+	/// by `follow` and `bounds`
+	pub offset:    (f64, f64,),
+}
+
+impl Camera {
+	/// camera showing the world exactly as canvas pixels
+	pub fn new(viewport_w: f64, viewport_h: f64,) -> Self {
+		Self {
+			center:    (viewport_w / 2.0, viewport_h / 2.0,),
+			zoom:      1.0,
+			viewport:  (viewport_w, viewport_h,),
+			dead_zone: (0.0, 0.0,),
+			smoothing: 1.0,
+			bounds:    None,
+			offset:    (0.0, 0.0,),
+		}
+	}
+
+	pub fn center(&self,) -> (f64, f64,) {
+		self.center
+	}
+
+	pub fn zoom(&self,) -> f64 {
+		self.zoom
+	}
+
+	pub fn viewport(&self,) -> (f64, f64,) {
+		self.viewport
+	}
+
+	pub fn set_zoom(&mut self, zoom: f64,) {
+		self.zoom = zoom.max(f64::EPSILON,);
+		self.clamp();
+	}
+
+	pub fn set_viewport(&mut self, w: f64, h: f64,) {
+		self.viewport = (w, h,);
+		self.clamp();
+	}
+
+	/// jump onto `target` ignoring dead zone and smoothing
+	pub fn snap_to(&mut self, target: Point,) {
+		self.center = (target.x_into(), target.y_into(),);
+		self.clamp();
+	}
+
+	/// move toward `target`, called once per simulation tick
+	pub fn follow(&mut self, target: Point,) {
+		let ease = |center: f64, target: f64, dead_zone: f64| {
+			let half = dead_zone / 2.0;
+			let desired = if target > center + half {
+				target - half
+			} else if target < center - half {
+				target + half
+			} else {
+				center
+			};
+			center + (desired - center) * self.smoothing.clamp(0.0, 1.0,)
+		};
+
+		self.center = (
+			ease(self.center.0, target.x_into(), self.dead_zone.0,),
+			ease(self.center.1, target.y_into(), self.dead_zone.1,),
+		);
+		self.clamp();
+	}
+
+	/// area of the world currently visible
+	pub fn visible(&self,) -> Bounds {
+		let (w, h,) =
+			(self.viewport.0 / self.zoom, self.viewport.1 / self.zoom,);
+		let (cx, cy,) = self.shown_center();
+		Bounds { x: cx - w / 2.0, y: cy - h / 2.0, w, h, }
+	}
+
+	pub fn world_to_screen(&self, x: f64, y: f64,) -> (f64, f64,) {
+		let [a, _, _, d, e, f,] = self.transform();
+		(x * a + e, y * d + f,)
+	}
+
+	pub fn screen_to_world(&self, x: f64, y: f64,) -> (f64, f64,) {
+		let [a, _, _, d, e, f,] = self.transform();
+		((x - e) / a, (y - f) / d,)
+	}
+
+	/// canvas `setTransform` arguments mapping world onto screen
+	pub fn transform(&self,) -> [f64; 6] {
+		let z = self.zoom;
+		let (cx, cy,) = self.shown_center();
+		[
+			z,
+			0.0,
+			0.0,
+			z,
+			self.viewport.0 / 2.0 - cx * z,
+			self.viewport.1 / 2.0 - cy * z,
+		]
+	}
+
+	/// center with `offset` applied
+	fn shown_center(&self,) -> (f64, f64,) {
+		(self.center.0 + self.offset.0, self.center.1 + self.offset.1,)
+	}
+
+	fn clamp(&mut self,) {
+		let Some(bounds,) = self.bounds else {
+			return;
+		};
+		let visible = self.visible();
+		let clamp = |center: f64, start: f64, len: f64, view: f64| {
+			if len <= view {
+				// world is smaller than the view, so keep it centered
+				start + len / 2.0
+			} else {
+				center.clamp(start + view / 2.0, start + len - view / 2.0,)
+			}
+		};
+
+		self.center = (
+			clamp(self.center.0, bounds.x, bounds.w, visible.w,),
+			clamp(self.center.1, bounds.y, bounds.h, visible.h,),
+		);
+	}
+}
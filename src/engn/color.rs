@@ -0,0 +1,526 @@
+//! colors every fill, stroke and tint is painted with
+//!
+//! [`Color`] holds 8 bit channels as a canvas does. [`ColorF`] holds the
+//! same channels as `0.0..=1.0` for math that would lose precision in 8 bits.
+//! channels are never premultiplied by alpha
+
+use crate::brwsr::Rslt;
+use anyhow::Context;
+use anyhow::anyhow;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt;
+use std::ops::Add;
+use std::ops::Mul;
+use std::ops::Sub;
+use std::str::FromStr;
+
+/// rgba with 8 bit channels. (de)serializes as a css color string
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize,)]
+#[serde(try_from = "String", into = "String")]
+pub struct Color {
+	pub r: u8,
+	pub g: u8,
+	pub b: u8,
+	/// `0` is fully transparent, `255` fully opaque
+	pub a: u8,
+}
+
+/// rgba with channels in `0.0..=1.0`
+#[derive(Clone, Copy, Debug, PartialEq,)]
+pub struct ColorF {
+	pub r: f64,
+	pub g: f64,
+	pub b: f64,
+	pub a: f64,
+}
+
+/// hue, saturation and lightness, with hue in degrees `0.0..360.0` and the
+/// rest in `0.0..=1.0`
+#[derive(Clone, Copy, Debug, PartialEq,)]
+pub struct Hsl {
+	pub h: f64,
+	pub s: f64,
+	pub l: f64,
+	pub a: f64,
+}
+
+/// css named colors as `Color` constants plus a lookup by name
+macro_rules! named_colors {
+	($(($konst:ident, $name:literal, $hex:literal,),)*) => {
+		impl Color {
+			$(pub const $konst: Self = Self::hex($hex,);)*
+
+			/// css color called `name`, case insensitive
+			pub fn named(name: &str,) -> Option<Self,> {
+				match name.to_ascii_lowercase().as_str() {
+					$($name => Some(Self::$konst,),)*
+					"transparent" => Some(Self::TRANSPARENT,),
+					_ => None,
+				}
+			}
+		}
+	};
+}
+
+named_colors!(
+	(ALICEBLUE, "aliceblue", 0xf0f8ff,),
+	(ANTIQUEWHITE, "antiquewhite", 0xfaebd7,),
+	(AQUA, "aqua", 0x00ffff,),
+	(AQUAMARINE, "aquamarine", 0x7fffd4,),
+	(AZURE, "azure", 0xf0ffff,),
+	(BEIGE, "beige", 0xf5f5dc,),
+	(BISQUE, "bisque", 0xffe4c4,),
+	(BLACK, "black", 0x000000,),
+	(BLANCHEDALMOND, "blanchedalmond", 0xffebcd,),
+	(BLUE, "blue", 0x0000ff,),
+	(BLUEVIOLET, "blueviolet", 0x8a2be2,),
+	(BROWN, "brown", 0xa52a2a,),
+	(BURLYWOOD, "burlywood", 0xdeb887,),
+	(CADETBLUE, "cadetblue", 0x5f9ea0,),
+	(CHARTREUSE, "chartreuse", 0x7fff00,),
+	(CHOCOLATE, "chocolate", 0xd2691e,),
+	(CORAL, "coral", 0xff7f50,),
+	(CORNFLOWERBLUE, "cornflowerblue", 0x6495ed,),
+	(CORNSILK, "cornsilk", 0xfff8dc,),
+	(CRIMSON, "crimson", 0xdc143c,),
+	(CYAN, "cyan", 0x00ffff,),
+	(DARKBLUE, "darkblue", 0x00008b,),
+	(DARKCYAN, "darkcyan", 0x008b8b,),
+	(DARKGOLDENROD, "darkgoldenrod", 0xb8860b,),
+	(DARKGRAY, "darkgray", 0xa9a9a9,),
+	(DARKGREEN, "darkgreen", 0x006400,),
+	(DARKGREY, "darkgrey", 0xa9a9a9,),
+	(DARKKHAKI, "darkkhaki", 0xbdb76b,),
+	(DARKMAGENTA, "darkmagenta", 0x8b008b,),
+	(DARKOLIVEGREEN, "darkolivegreen", 0x556b2f,),
+	(DARKORANGE, "darkorange", 0xff8c00,),
+	(DARKORCHID, "darkorchid", 0x9932cc,),
+	(DARKRED, "darkred", 0x8b0000,),
+	(DARKSALMON, "darksalmon", 0xe9967a,),
+	(DARKSEAGREEN, "darkseagreen", 0x8fbc8f,),
+	(DARKSLATEBLUE, "darkslateblue", 0x483d8b,),
+	(DARKSLATEGRAY, "darkslategray", 0x2f4f4f,),
+	(DARKSLATEGREY, "darkslategrey", 0x2f4f4f,),
+	(DARKTURQUOISE, "darkturquoise", 0x00ced1,),
+	(DARKVIOLET, "darkviolet", 0x9400d3,),
+	(DEEPPINK, "deeppink", 0xff1493,),
+	(DEEPSKYBLUE, "deepskyblue", 0x00bfff,),
+	(DIMGRAY, "dimgray", 0x696969,),
+	(DIMGREY, "dimgrey", 0x696969,),
+	(DODGERBLUE, "dodgerblue", 0x1e90ff,),
+	(FIREBRICK, "firebrick", 0xb22222,),
+	(FLORALWHITE, "floralwhite", 0xfffaf0,),
+	(FORESTGREEN, "forestgreen", 0x228b22,),
+	(FUCHSIA, "fuchsia", 0xff00ff,),
+	(GAINSBORO, "gainsboro", 0xdcdcdc,),
+	(GHOSTWHITE, "ghostwhite", 0xf8f8ff,),
+	(GOLD, "gold", 0xffd700,),
+	(GOLDENROD, "goldenrod", 0xdaa520,),
+	(GRAY, "gray", 0x808080,),
+	(GREEN, "green", 0x008000,),
+	(GREENYELLOW, "greenyellow", 0xadff2f,),
+	(GREY, "grey", 0x808080,),
+	(HONEYDEW, "honeydew", 0xf0fff0,),
+	(HOTPINK, "hotpink", 0xff69b4,),
+	(INDIANRED, "indianred", 0xcd5c5c,),
+	(INDIGO, "indigo", 0x4b0082,),
+	(IVORY, "ivory", 0xfffff0,),
+	(KHAKI, "khaki", 0xf0e68c,),
+	(LAVENDER, "lavender", 0xe6e6fa,),
+	(LAVENDERBLUSH, "lavenderblush", 0xfff0f5,),
+	(LAWNGREEN, "lawngreen", 0x7cfc00,),
+	(LEMONCHIFFON, "lemonchiffon", 0xfffacd,),
+	(LIGHTBLUE, "lightblue", 0xadd8e6,),
+	(LIGHTCORAL, "lightcoral", 0xf08080,),
+	(LIGHTCYAN, "lightcyan", 0xe0ffff,),
+	(LIGHTGOLDENRODYELLOW, "lightgoldenrodyellow", 0xfafad2,),
+	(LIGHTGRAY, "lightgray", 0xd3d3d3,),
+	(LIGHTGREEN, "lightgreen", 0x90ee90,),
+	(LIGHTGREY, "lightgrey", 0xd3d3d3,),
+	(LIGHTPINK, "lightpink", 0xffb6c1,),
+	(LIGHTSALMON, "lightsalmon", 0xffa07a,),
+	(LIGHTSEAGREEN, "lightseagreen", 0x20b2aa,),
+	(LIGHTSKYBLUE, "lightskyblue", 0x87cefa,),
+	(LIGHTSLATEGRAY, "lightslategray", 0x778899,),
+	(LIGHTSLATEGREY, "lightslategrey", 0x778899,),
+	(LIGHTSTEELBLUE, "lightsteelblue", 0xb0c4de,),
+	(LIGHTYELLOW, "lightyellow", 0xffffe0,),
+	(LIME, "lime", 0x00ff00,),
+	(LIMEGREEN, "limegreen", 0x32cd32,),
+	(LINEN, "linen", 0xfaf0e6,),
+	(MAGENTA, "magenta", 0xff00ff,),
+	(MAROON, "maroon", 0x800000,),
+	(MEDIUMAQUAMARINE, "mediumaquamarine", 0x66cdaa,),
+	(MEDIUMBLUE, "mediumblue", 0x0000cd,),
+	(MEDIUMORCHID, "mediumorchid", 0xba55d3,),
+	(MEDIUMPURPLE, "mediumpurple", 0x9370db,),
+	(MEDIUMSEAGREEN, "mediumseagreen", 0x3cb371,),
+	(MEDIUMSLATEBLUE, "mediumslateblue", 0x7b68ee,),
+	(MEDIUMSPRINGGREEN, "mediumspringgreen", 0x00fa9a,),
+	(MEDIUMTURQUOISE, "mediumturquoise", 0x48d1cc,),
+	(MEDIUMVIOLETRED, "mediumvioletred", 0xc71585,),
+	(MIDNIGHTBLUE, "midnightblue", 0x191970,),
+	(MINTCREAM, "mintcream", 0xf5fffa,),
+	(MISTYROSE, "mistyrose", 0xffe4e1,),
+	(MOCCASIN, "moccasin", 0xffe4b5,),
+	(NAVAJOWHITE, "navajowhite", 0xffdead,),
+	(NAVY, "navy", 0x000080,),
+	(OLDLACE, "oldlace", 0xfdf5e6,),
+	(OLIVE, "olive", 0x808000,),
+	(OLIVEDRAB, "olivedrab", 0x6b8e23,),
+	(ORANGE, "orange", 0xffa500,),
+	(ORANGERED, "orangered", 0xff4500,),
+	(ORCHID, "orchid", 0xda70d6,),
+	(PALEGOLDENROD, "palegoldenrod", 0xeee8aa,),
+	(PALEGREEN, "palegreen", 0x98fb98,),
+	(PALETURQUOISE, "paleturquoise", 0xafeeee,),
+	(PALEVIOLETRED, "palevioletred", 0xdb7093,),
+	(PAPAYAWHIP, "papayawhip", 0xffefd5,),
+	(PEACHPUFF, "peachpuff", 0xffdab9,),
+	(PERU, "peru", 0xcd853f,),
+	(PINK, "pink", 0xffc0cb,),
+	(PLUM, "plum", 0xdda0dd,),
+	(POWDERBLUE, "powderblue", 0xb0e0e6,),
+	(PURPLE, "purple", 0x800080,),
+	(REBECCAPURPLE, "rebeccapurple", 0x663399,),
+	(RED, "red", 0xff0000,),
+	(ROSYBROWN, "rosybrown", 0xbc8f8f,),
+	(ROYALBLUE, "royalblue", 0x4169e1,),
+	(SADDLEBROWN, "saddlebrown", 0x8b4513,),
+	(SALMON, "salmon", 0xfa8072,),
+	(SANDYBROWN, "sandybrown", 0xf4a460,),
+	(SEAGREEN, "seagreen", 0x2e8b57,),
+	(SEASHELL, "seashell", 0xfff5ee,),
+	(SIENNA, "sienna", 0xa0522d,),
+	(SILVER, "silver", 0xc0c0c0,),
+	(SKYBLUE, "skyblue", 0x87ceeb,),
+	(SLATEBLUE, "slateblue", 0x6a5acd,),
+	(SLATEGRAY, "slategray", 0x708090,),
+	(SLATEGREY, "slategrey", 0x708090,),
+	(SNOW, "snow", 0xfffafa,),
+	(SPRINGGREEN, "springgreen", 0x00ff7f,),
+	(STEELBLUE, "steelblue", 0x4682b4,),
+	(TAN, "tan", 0xd2b48c,),
+	(TEAL, "teal", 0x008080,),
+	(THISTLE, "thistle", 0xd8bfd8,),
+	(TOMATO, "tomato", 0xff6347,),
+	(TURQUOISE, "turquoise", 0x40e0d0,),
+	(VIOLET, "violet", 0xee82ee,),
+	(WHEAT, "wheat", 0xf5deb3,),
+	(WHITE, "white", 0xffffff,),
+	(WHITESMOKE, "whitesmoke", 0xf5f5f5,),
+	(YELLOW, "yellow", 0xffff00,),
+	(YELLOWGREEN, "yellowgreen", 0x9acd32,),
+);
+
+impl Color {
+	pub const TRANSPARENT: Self = Self::rgba(0, 0, 0, 0,);
+
+	pub const fn rgb(r: u8, g: u8, b: u8,) -> Self {
+		Self { r, g, b, a: 255, }
+	}
+
+	pub const fn rgba(r: u8, g: u8, b: u8, a: u8,) -> Self {
+		Self { r, g, b, a, }
+	}
+
+	/// opaque color out of `0xrrggbb`
+	pub const fn hex(rgb: u32,) -> Self {
+		Self::rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8,)
+	}
+
+	/// same color with opacity `alpha`, `0.0..=1.0`
+	pub fn with_alpha(self, alpha: f64,) -> Self {
+		Self { a: unit_to_u8(alpha,), ..self }
+	}
+
+	pub fn to_f(self,) -> ColorF {
+		ColorF {
+			r: self.r as f64 / 255.0,
+			g: self.g as f64 / 255.0,
+			b: self.b as f64 / 255.0,
+			a: self.a as f64 / 255.0,
+		}
+	}
+
+	/// `self` painted over `below` with its opacity scaled by `alpha`, as a
+	/// canvas composites
+	pub fn over(self, below: Self, alpha: f64,) -> Self {
+		let src = ColorF { a: self.to_f().a * alpha, ..self.to_f() };
+		src.over(below.to_f(),).to_u8()
+	}
+
+	/// color `t` of the way from `self` to `to`, `0.0..=1.0`
+	pub fn lerp(self, to: Self, t: f64,) -> Self {
+		self.to_f().lerp(to.to_f(), t,).to_u8()
+	}
+
+	pub fn to_hsl(self,) -> Hsl {
+		let ColorF { r, g, b, a, } = self.to_f();
+		let max = r.max(g,).max(b,);
+		let min = r.min(g,).min(b,);
+		let l = (max + min) / 2.0;
+		let d = max - min;
+		if d == 0.0 {
+			return Hsl { h: 0.0, s: 0.0, l, a, };
+		}
+		let s = d / (1.0 - (2.0 * l - 1.0).abs());
+		let h = if max == r {
+			((g - b) / d).rem_euclid(6.0,)
+		} else if max == g {
+			(b - r) / d + 2.0
+		} else {
+			(r - g) / d + 4.0
+		};
+		Hsl { h: h * 60.0, s, l, a, }
+	}
+
+	pub fn from_hsl(Hsl { h, s, l, a, }: Hsl,) -> Self {
+		let h = h.rem_euclid(360.0,) / 60.0;
+		let (s, l,) = (s.clamp(0.0, 1.0,), l.clamp(0.0, 1.0,),);
+		let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+		let x = c * (1.0 - (h.rem_euclid(2.0,) - 1.0).abs());
+		let (r, g, b,) = match h as u8 {
+			0 => (c, x, 0.0,),
+			1 => (x, c, 0.0,),
+			2 => (0.0, c, x,),
+			3 => (0.0, x, c,),
+			4 => (x, 0.0, c,),
+			_ => (c, 0.0, x,),
+		};
+		let m = l - c / 2.0;
+		ColorF { r: r + m, g: g + m, b: b + m, a, }.to_u8()
+	}
+
+	/// same hue with lightness raised by `amount`, `0.0..=1.0`
+	pub fn lighten(self, amount: f64,) -> Self {
+		let hsl = self.to_hsl();
+		Self::from_hsl(Hsl { l: hsl.l + amount, ..hsl },)
+	}
+
+	/// same hue with lightness lowered by `amount`, `0.0..=1.0`
+	pub fn darken(self, amount: f64,) -> Self {
+		self.lighten(-amount,)
+	}
+
+	/// `#rrggbb` when opaque, `rgba(r, g, b, a)` otherwise
+	pub fn to_css(self,) -> String {
+		self.to_string()
+	}
+}
+
+impl ColorF {
+	/// nearest 8 bit color, channels clamped into range
+	pub fn to_u8(self,) -> Color {
+		Color {
+			r: unit_to_u8(self.r,),
+			g: unit_to_u8(self.g,),
+			b: unit_to_u8(self.b,),
+			a: unit_to_u8(self.a,),
+		}
+	}
+
+	/// source-over compositing of `self` onto `below`
+	pub fn over(self, below: Self,) -> Self {
+		let a = self.a + below.a * (1.0 - self.a);
+		if a <= 0.0 {
+			return Self { r: 0.0, g: 0.0, b: 0.0, a: 0.0, };
+		}
+		let mix =
+			|s: f64, d: f64| (s * self.a + d * below.a * (1.0 - self.a)) / a;
+		Self {
+			r: mix(self.r, below.r,),
+			g: mix(self.g, below.g,),
+			b: mix(self.b, below.b,),
+			a,
+		}
+	}
+
+	pub fn lerp(self, to: Self, t: f64,) -> Self {
+		let t = t.clamp(0.0, 1.0,);
+		let mix = |from: f64, to: f64| from + (to - from) * t;
+		Self {
+			r: mix(self.r, to.r,),
+			g: mix(self.g, to.g,),
+			b: mix(self.b, to.b,),
+			a: mix(self.a, to.a,),
+		}
+	}
+}
+
+fn unit_to_u8(v: f64,) -> u8 {
+	(v.clamp(0.0, 1.0,) * 255.0).round() as u8
+}
+
+impl From<Color,> for ColorF {
+	fn from(color: Color,) -> Self {
+		color.to_f()
+	}
+}
+
+impl From<ColorF,> for Color {
+	fn from(color: ColorF,) -> Self {
+		color.to_u8()
+	}
+}
+
+impl From<(u8, u8, u8,),> for Color {
+	fn from((r, g, b,): (u8, u8, u8,),) -> Self {
+		Self::rgb(r, g, b,)
+	}
+}
+
+impl From<(u8, u8, u8, u8,),> for Color {
+	fn from((r, g, b, a,): (u8, u8, u8, u8,),) -> Self {
+		Self::rgba(r, g, b, a,)
+	}
+}
+
+/// channel-wise saturating sum of the color channels. alpha is the left
+/// side's
+impl Add for Color {
+	type Output = Self;
+
+	fn add(self, rhs: Self,) -> Self::Output {
+		Self {
+			r: self.r.saturating_add(rhs.r,),
+			g: self.g.saturating_add(rhs.g,),
+			b: self.b.saturating_add(rhs.b,),
+			a: self.a,
+		}
+	}
+}
+
+/// channel-wise saturating difference of the color channels. alpha is the
+/// left side's
+impl Sub for Color {
+	type Output = Self;
+
+	fn sub(self, rhs: Self,) -> Self::Output {
+		Self {
+			r: self.r.saturating_sub(rhs.r,),
+			g: self.g.saturating_sub(rhs.g,),
+			b: self.b.saturating_sub(rhs.b,),
+			a: self.a,
+		}
+	}
+}
+
+/// color channels scaled by `rhs`, clamped into range. alpha is kept
+impl Mul<f64,> for Color {
+	type Output = Self;
+
+	fn mul(self, rhs: f64,) -> Self::Output {
+		let scale = |c: u8| (c as f64 * rhs).clamp(0.0, 255.0,).round() as u8;
+		Self {
+			r: scale(self.r,),
+			g: scale(self.g,),
+			b: scale(self.b,),
+			a: self.a,
+		}
+	}
+}
+
+impl fmt::Display for Color {
+	fn fmt(&self, f: &mut fmt::Formatter<'_,>,) -> fmt::Result {
+		let Self { r, g, b, a, } = *self;
+		if a == 255 {
+			write!(f, "#{r:02x}{g:02x}{b:02x}")
+		} else {
+			// 3 decimals tell every 8 bit alpha apart
+			let alpha = (a as f64 / 255.0 * 1000.0).round() / 1000.0;
+			write!(f, "rgba({r}, {g}, {b}, {alpha})")
+		}
+	}
+}
+
+/// `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`, `rgb()`, `rgba()` or a css
+/// color name
+impl FromStr for Color {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str,) -> Result<Self, Self::Err,> {
+		let css = s.trim();
+		if let Some(hex,) = css.strip_prefix('#',) {
+			return parse_hex(hex,)
+				.with_context(|| format!("bad hex color `{s}`"),);
+		}
+		let lower = css.to_ascii_lowercase();
+		if let Some(args,) = lower
+			.strip_prefix("rgba(",)
+			.or_else(|| lower.strip_prefix("rgb(",),)
+			.and_then(|rest| rest.strip_suffix(')',),)
+		{
+			return parse_rgb(args,)
+				.with_context(|| format!("bad rgb color `{s}`"),);
+		}
+		Self::named(css,).ok_or_else(|| anyhow!("unknown color `{s}`"),)
+	}
+}
+
+fn parse_hex(hex: &str,) -> Rslt<Color,> {
+	let digits = hex
+		.chars()
+		.map(|c| c.to_digit(16,).map(|d| d as u8,),)
+		.collect::<Option<Vec<_,>,>>()
+		.context("not a hex digit",)?;
+	let pair = |hi: u8, lo: u8| hi * 16 + lo;
+	Ok(match digits[..] {
+		[r, g, b,] => Color::rgb(r * 17, g * 17, b * 17,),
+		[r, g, b, a,] => Color::rgba(r * 17, g * 17, b * 17, a * 17,),
+		[r1, r0, g1, g0, b1, b0,] => {
+			Color::rgb(pair(r1, r0,), pair(g1, g0,), pair(b1, b0,),)
+		},
+		[r1, r0, g1, g0, b1, b0, a1, a0,] => Color::rgba(
+			pair(r1, r0,),
+			pair(g1, g0,),
+			pair(b1, b0,),
+			pair(a1, a0,),
+		),
+		_ => bail!("expected 3, 4, 6 or 8 digits, found {}", digits.len()),
+	},)
+}
+
+/// arguments of `rgb()`, comma or space separated, with alpha optionally
+/// after a `/`. channels may be percentages
+fn parse_rgb(args: &str,) -> Rslt<Color,> {
+	let parts = args
+		.split([',', ' ', '/',],)
+		.filter(|p| !p.is_empty(),)
+		.collect::<Vec<_,>>();
+	let channel = |p: &str, max: f64| -> Rslt<f64,> {
+		let v = match p.strip_suffix('%',) {
+			Some(pct,) => pct.parse::<f64>()? / 100.0,
+			None => p.parse::<f64>()? / max,
+		};
+		Ok(v,)
+	};
+	let (r, g, b, a,) = match parts[..] {
+		[r, g, b,] => (r, g, b, "1",),
+		[r, g, b, a,] => (r, g, b, a,),
+		_ => bail!("expected 3 or 4 channels, found {}", parts.len()),
+	};
+	Ok(ColorF {
+		r: channel(r, 255.0,)?,
+		g: channel(g, 255.0,)?,
+		b: channel(b, 255.0,)?,
+		a: channel(a, 1.0,)?,
+	}
+	.to_u8(),)
+}
+
+impl TryFrom<String,> for Color {
+	type Error = anyhow::Error;
+
+	fn try_from(css: String,) -> Result<Self, Self::Error,> {
+		css.parse()
+	}
+}
+
+impl From<Color,> for String {
+	fn from(color: Color,) -> Self {
+		color.to_css()
+	}
+}
@@ -0,0 +1,212 @@
+//! overlay showing what the simulation is doing, for tuning movement
+
+use crate::brwsr;
+use crate::brwsr::Rslt;
+use crate::engn::KeyboardState;
+use crate::engn::Point;
+use crate::engn::Renderer;
+use crate::engn::camera::Bounds;
+use crate::engn::color::Color;
+use crate::engn::shape::Shape;
+use crate::engn::shape::ShapeStyle;
+
+/// key flipping the overlay on and off
+pub const TOGGLE_KEY: &str = "Backquote";
+/// query parameter turning the overlay on at start, as in `?debug`
+pub const URL_FLAG: &str = "debug";
+/// velocity vectors are drawn as long as this many ticks of travel
+const VEL_SCALE: f64 = 10.0;
+const LINE_HEIGHT: f64 = 14.0;
+
+/// what the overlay shows about one entity
+#[derive(Clone, Debug, PartialEq,)]
+pub struct EntityInfo {
+	pub name:   String,
+	/// state machine's current state
+	pub state:  String,
+	/// animation frame drawn this frame
+	pub frame:  String,
+	pub bounds: Option<Bounds,>,
+	pub pos:    Point,
+	pub vel:    Point,
+}
+
+/// events per second, averaged over the last second or so
+#[derive(Clone, Debug, Default,)]
+pub struct RateCounter {
+	window_start: Option<f64,>,
+	count:        u32,
+	rate:         f64,
+}
+
+impl RateCounter {
+	/// count one event happening at `now` milliseconds
+	pub fn tick(&mut self, now: f64,) {
+		let Some(start,) = self.window_start else {
+			self.window_start = Some(now,);
+			return;
+		};
+		self.count += 1;
+		let elapsed = now - start;
+		if elapsed >= 1000.0 {
+			self.rate = self.count as f64 * 1000.0 / elapsed;
+			self.count = 0;
+			self.window_start = Some(now,);
+		}
+	}
+
+	pub fn rate(&self,) -> f64 {
+		self.rate
+	}
+}
+
+#[derive(Clone, Debug, Default,)]
+pub struct DebugOverlay {
+	enabled:     bool,
+	/// toggle key was down last time input was checked
+	toggle_held: bool,
+	frames:      RateCounter,
+	ticks:       RateCounter,
+}
+
+impl DebugOverlay {
+	pub fn new(enabled: bool,) -> Self {
+		Self { enabled, ..Self::default() }
+	}
+
+	/// enabled when page's url carries [`URL_FLAG`]
+	pub fn from_url() -> Self {
+		let search = brwsr::location_search().unwrap_or_default();
+		Self::new(query_flag(&search, URL_FLAG,),)
+	}
+
+	pub fn is_enabled(&self,) -> bool {
+		self.enabled
+	}
+
+	pub fn set_enabled(&mut self, enabled: bool,) {
+		self.enabled = enabled;
+	}
+
+	/// flip the overlay when [`TOGGLE_KEY`] goes down
+	pub fn handle_input(&mut self, kb_state: &KeyboardState,) {
+		let held = kb_state.is_pressed(TOGGLE_KEY,);
+		if held && !self.toggle_held {
+			self.enabled = !self.enabled;
+		}
+		self.toggle_held = held;
+	}
+
+	/// count a simulation tick
+	pub fn on_tick(&mut self, now: f64,) {
+		self.ticks.tick(now,);
+	}
+
+	/// count a drawn frame
+	pub fn on_frame(&mut self, now: f64,) {
+		self.frames.tick(now,);
+	}
+
+	pub fn fps(&self,) -> f64 {
+		self.frames.rate()
+	}
+
+	pub fn tps(&self,) -> f64 {
+		self.ticks.rate()
+	}
+
+	/// draw `entities` in the world, then the counters and `tuning` as
+	/// `(name, value)` over the screen
+	pub fn draw(
+		&self,
+		rndrr: &Renderer,
+		entities: &[EntityInfo],
+		tuning: &[(String, String,)],
+	) -> Rslt<(),> {
+		for entity in entities {
+			self.draw_entity(rndrr, entity,)?;
+		}
+
+		let stats = rndrr.frame_stats();
+		let mut lines = vec![
+			format!("fps {:.0}  tps {:.0}", self.fps(), self.tps()),
+			format!(
+				"drawn {}  culled {}  batches {}",
+				stats.drawn, stats.culled, stats.batches
+			),
+		];
+		lines.extend(
+			tuning.iter().map(|(name, value,)| format!("{name} {value}"),),
+		);
+		rndrr.screen_space(|r| {
+			r.draw_shape(
+				&Shape::Rect {
+					x: 0.0,
+					y: 0.0,
+					w: 220.0,
+					h: LINE_HEIGHT * lines.len() as f64 + 8.0,
+				},
+				&ShapeStyle::fill(Color::BLACK.with_alpha(0.6,),),
+			)?;
+			draw_lines(r, &lines, 4.0, 4.0, Color::WHITE,)
+		},)
+	}
+
+	fn draw_entity(&self, rndrr: &Renderer, entity: &EntityInfo,) -> Rslt<(),> {
+		let (x, y,) = (entity.pos.x_into(), entity.pos.y_into(),);
+		if let Some(Bounds { x, y, w, h, },) = entity.bounds {
+			rndrr.draw_shape(
+				&Shape::Rect { x, y, w, h, },
+				&ShapeStyle::stroke(Color::LIME, 1.0,),
+			)?;
+		}
+		rndrr.draw_shape(
+			&Shape::Circle { cx: x, cy: y, r: 3.0, },
+			&ShapeStyle::fill(Color::RED,),
+		)?;
+		rndrr.draw_shape(
+			&Shape::Line {
+				from: (x, y,),
+				to:   (
+					x + entity.vel.x_into() * VEL_SCALE,
+					y + entity.vel.y_into() * VEL_SCALE,
+				),
+			},
+			&ShapeStyle::stroke(Color::YELLOW, 2.0,),
+		)?;
+
+		let lines = [
+			format!("{} [{}]", entity.name, entity.state),
+			entity.frame.clone(),
+			format!(
+				"pos ({}, {})  vel ({}, {})",
+				entity.pos.x, entity.pos.y, entity.vel.x, entity.vel.y
+			),
+		];
+		let top = entity.bounds.map_or(y, |b| b.y,) - LINE_HEIGHT * 3.0;
+		let left = entity.bounds.map_or(x, |b| b.x,);
+		draw_lines(rndrr, &lines, left, top, Color::LIME,)
+	}
+}
+
+fn draw_lines(
+	rndrr: &Renderer,
+	lines: &[String],
+	x: f64,
+	y: f64,
+	color: Color,
+) -> Rslt<(),> {
+	for (i, line,) in lines.iter().enumerate() {
+		rndrr.draw_label(line, (x, y + LINE_HEIGHT * i as f64,), color,)?;
+	}
+	Ok((),)
+}
+
+/// whether url query `search` turns `name` on. `?name`, `?name=1` and
+/// `?name=true` do, `?name=0` and `?name=false` do not
+pub fn query_flag(search: &str, name: &str,) -> bool {
+	search.trim_start_matches('?',).split('&',).any(|pair| {
+		let (key, value,) = pair.split_once('=',).unwrap_or((pair, "",),);
+		key == name && !matches!(value, "0" | "false")
+	},)
+}
@@ -0,0 +1,135 @@
+//! camera shake, flashes and hit-stop
+//!
+//! everything here moves on simulation ticks only, so a replay fed the same
+//! inputs shakes and freezes exactly the same way
+
+use crate::brwsr::Rslt;
+use crate::engn::Renderer;
+use crate::engn::color::Color;
+use crate::engn::shape::Shape;
+use crate::engn::shape::ShapeStyle;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// shake amplitude below this many world units stops the shake
+const SHAKE_EPSILON: f64 = 0.1;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize,)]
+struct Flash {
+	color: Color,
+	left:  u32,
+	total: u32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize,)]
+pub struct CameraEffects {
+	/// ticks since creation, seeding the shake's direction
+	tick:     u64,
+	/// current shake amplitude in world units
+	shake:    f64,
+	/// multiplied with the amplitude every tick
+	decay:    f64,
+	flash:    Option<Flash,>,
+	/// ticks the simulation stays frozen for
+	hit_stop: u32,
+}
+
+impl CameraEffects {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// shake `amplitude` world units in every direction, shrinking by a
+	/// factor of `decay` every tick. stronger of this and any running shake
+	/// wins
+	pub fn shake(&mut self, amplitude: f64, decay: f64,) {
+		if amplitude >= self.shake {
+			self.shake = amplitude;
+			self.decay = decay.clamp(0.0, 1.0,);
+		}
+	}
+
+	/// cover the screen in `color`, fading out over `ticks`
+	pub fn flash(&mut self, color: Color, ticks: u32,) {
+		self.flash = Some(Flash { color, left: ticks, total: ticks, },);
+	}
+
+	/// freeze the simulation for `ticks`, to let a hit land
+	pub fn hit_stop(&mut self, ticks: u32,) {
+		self.hit_stop = self.hit_stop.max(ticks,);
+	}
+
+	/// advance every effect one tick. returns whether the simulation should
+	/// step this tick, which it should not during hit-stop
+	pub fn update(&mut self,) -> bool {
+		self.tick += 1;
+		self.shake *= self.decay;
+		if self.shake < SHAKE_EPSILON {
+			self.shake = 0.0;
+		}
+		if let Some(flash,) = self.flash.as_mut() {
+			flash.left = flash.left.saturating_sub(1,);
+			if flash.left == 0 {
+				self.flash = None;
+			}
+		}
+		if self.hit_stop > 0 {
+			self.hit_stop -= 1;
+			return false;
+		}
+		true
+	}
+
+	/// displacement to set as the camera's offset this tick
+	pub fn shake_offset(&self,) -> (f64, f64,) {
+		if self.shake == 0.0 {
+			return (0.0, 0.0,);
+		}
+		(
+			noise(self.tick * 2,) * self.shake,
+			noise(self.tick * 2 + 1,) * self.shake,
+		)
+	}
+
+	pub fn is_shaking(&self,) -> bool {
+		self.shake > 0.0
+	}
+
+	pub fn is_frozen(&self,) -> bool {
+		self.hit_stop > 0
+	}
+
+	/// opacity of the flash, `0.0` when there is none
+	pub fn flash_alpha(&self,) -> f64 {
+		self.flash
+			.as_ref()
+			.map_or(0.0, |f| f.left as f64 / f.total.max(1,) as f64,)
+	}
+
+	/// draw the flash over the whole screen
+	pub fn draw(&self, rndrr: &Renderer,) -> Rslt<(),> {
+		let Some(flash,) = self.flash.as_ref() else {
+			return Ok((),);
+		};
+		let (w, h,) = rndrr.logical_size();
+		rndrr.screen_space(|r| {
+			r.draw_shape(
+				&Shape::Rect { x: 0.0, y: 0.0, w, h, },
+				&ShapeStyle {
+					alpha: self.flash_alpha(),
+					..ShapeStyle::fill(flash.color,)
+				},
+			)
+		},)
+	}
+}
+
+/// value in `[-1, 1]` looking random but always the same for the same `n`
+fn noise(n: u64,) -> f64 {
+	// splitmix64 finalizer
+	let mut z = n.wrapping_add(0x9e37_79b9_7f4a_7c15,);
+	z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9,);
+	z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb,);
+	z ^= z >> 31;
+	(z >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+}
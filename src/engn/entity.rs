@@ -0,0 +1,274 @@
+//! entities as plain handles with their data kept in per kind component
+//! storages, and systems run over them in a declared order
+//!
+//! a handle stays valid until its entity is despawned. the slot is reused
+//! by later spawns under a new generation, so a stale handle never reaches
+//! the data of whatever took its place
+
+use crate::brwsr::Rslt;
+use crate::engn::queue::RenderQueue;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// handle to a spawned entity
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize,)]
+pub struct Entity {
+	index:      u32,
+	generation: u32,
+}
+
+impl Entity {
+	/// slot the entity lives in. shared with entities spawned before it
+	/// was despawned, or after
+	pub fn index(&self,) -> usize {
+		self.index as usize
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize,)]
+struct Slot {
+	generation: u32,
+	alive:      bool,
+}
+
+/// every entity spawned, alive or not
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize,)]
+pub struct Entities {
+	slots: Vec<Slot,>,
+	/// indices of despawned slots, reused last freed first
+	free:  Vec<u32,>,
+}
+
+impl Entities {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn spawn(&mut self,) -> Entity {
+		if let Some(index,) = self.free.pop() {
+			let slot = &mut self.slots[index as usize];
+			slot.alive = true;
+			return Entity { index, generation: slot.generation, };
+		}
+
+		let index = self.slots.len() as u32;
+		self.slots.push(Slot { generation: 0, alive: true, },);
+		Entity { index, generation: 0, }
+	}
+
+	/// whether `entity` was alive. its components are left to the storages
+	/// holding them, see [`Components::prune`]
+	pub fn despawn(&mut self, entity: Entity,) -> bool {
+		if !self.is_alive(entity,) {
+			return false;
+		}
+		let slot = &mut self.slots[entity.index()];
+		slot.alive = false;
+		slot.generation = slot.generation.wrapping_add(1,);
+		self.free.push(entity.index,);
+		true
+	}
+
+	/// fail unless every freed index is a dead slot, freed once. storage
+	/// read back from a file may have been tampered with
+	pub fn check(&self,) -> Rslt<(),> {
+		let mut freed = vec![false; self.slots.len()];
+		for &index in self.free.iter() {
+			match self.slots.get(index as usize,) {
+				None => bail!("freed entity {index} has no slot"),
+				Some(slot,) if slot.alive => {
+					bail!("freed entity {index} is alive")
+				},
+				Some(_,) if freed[index as usize] => {
+					bail!("entity {index} is freed twice")
+				},
+				Some(_,) => freed[index as usize] = true,
+			}
+		}
+		Ok((),)
+	}
+
+	pub fn is_alive(&self, entity: Entity,) -> bool {
+		self.slots
+			.get(entity.index(),)
+			.is_some_and(|s| s.alive && s.generation == entity.generation,)
+	}
+
+	pub fn len(&self,) -> usize {
+		self.slots.len() - self.free.len()
+	}
+
+	pub fn is_empty(&self,) -> bool {
+		self.len() == 0
+	}
+
+	/// alive entities in slot order
+	pub fn iter(&self,) -> impl Iterator<Item = Entity,> + '_ {
+		self.slots.iter().enumerate().filter(|(_, s,)| s.alive,).map(
+			|(index, s,)| Entity {
+				index:      index as u32,
+				generation: s.generation,
+			},
+		)
+	}
+}
+
+/// one kind of data, at most one per entity
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize,)]
+pub struct Components<T,> {
+	slots: Vec<Option<(u32, T,),>,>,
+}
+
+impl<T,> Components<T,> {
+	pub fn new() -> Self {
+		Self { slots: vec![], }
+	}
+
+	/// attach `value` to `entity`, returning what it had before
+	pub fn insert(&mut self, entity: Entity, value: T,) -> Option<T,> {
+		if self.slots.len() <= entity.index() {
+			self.slots.resize_with(entity.index() + 1, || None,);
+		}
+		let prev =
+			self.slots[entity.index()].replace((entity.generation, value,),);
+		prev.and_then(|(g, v,)| (g == entity.generation).then_some(v,),)
+	}
+
+	pub fn remove(&mut self, entity: Entity,) -> Option<T,> {
+		let slot = self.slots.get_mut(entity.index(),)?;
+		match slot {
+			Some((g, _,),) if *g == entity.generation => {
+				slot.take().map(|(_, v,)| v,)
+			},
+			_ => None,
+		}
+	}
+
+	pub fn get(&self, entity: Entity,) -> Option<&T,> {
+		match self.slots.get(entity.index(),)? {
+			Some((g, v,),) if *g == entity.generation => Some(v,),
+			_ => None,
+		}
+	}
+
+	pub fn get_mut(&mut self, entity: Entity,) -> Option<&mut T,> {
+		match self.slots.get_mut(entity.index(),)? {
+			Some((g, v,),) if *g == entity.generation => Some(v,),
+			_ => None,
+		}
+	}
+
+	pub fn contains(&self, entity: Entity,) -> bool {
+		self.get(entity,).is_some()
+	}
+
+	pub fn len(&self,) -> usize {
+		self.slots.iter().flatten().count()
+	}
+
+	pub fn is_empty(&self,) -> bool {
+		self.slots.iter().all(Option::is_none,)
+	}
+
+	/// components with their entities in slot order
+	pub fn iter(&self,) -> impl Iterator<Item = (Entity, &T,),> {
+		self.slots.iter().enumerate().filter_map(|(index, slot,)| {
+			let (generation, v,) = slot.as_ref()?;
+			Some((Entity { index: index as u32, generation: *generation, }, v,),)
+		},)
+	}
+
+	pub fn iter_mut(&mut self,) -> impl Iterator<Item = (Entity, &mut T,),> {
+		self.slots.iter_mut().enumerate().filter_map(|(index, slot,)| {
+			let (generation, v,) = slot.as_mut()?;
+			Some((Entity { index: index as u32, generation: *generation, }, v,),)
+		},)
+	}
+
+	/// drop components of entities which are no longer alive
+	pub fn prune(&mut self, entities: &Entities,) {
+		for (index, slot,) in self.slots.iter_mut().enumerate() {
+			let stale = slot.as_ref().is_some_and(|(generation, _,)| {
+				!entities.is_alive(Entity {
+					index:      index as u32,
+					generation: *generation,
+				},)
+			},);
+			if stale {
+				*slot = None;
+			}
+		}
+	}
+}
+
+impl<T,> Default for Components<T,> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// advances world `W` one tick given input `I`
+pub type UpdateSystem<W, I,> = fn(&mut W, &I,);
+/// queues what world `W` looks like this frame
+pub type DrawSystem<W,> = for<'a> fn(&'a W, &mut RenderQueue<'a,>,);
+
+/// systems of a world, run in the order they were added
+pub struct Schedule<W, I,> {
+	update: Vec<(&'static str, UpdateSystem<W, I,>,),>,
+	draw:   Vec<(&'static str, DrawSystem<W,>,),>,
+}
+
+impl<W, I,> Schedule<W, I,> {
+	pub fn new() -> Self {
+		Self { update: vec![], draw: vec![], }
+	}
+
+	/// run `system` on every update, after those added before it
+	pub fn on_update(
+		mut self,
+		name: &'static str,
+		system: UpdateSystem<W, I,>,
+	) -> Self {
+		self.update.push((name, system,),);
+		self
+	}
+
+	/// run `system` on every draw, after those added before it
+	pub fn on_draw(
+		mut self,
+		name: &'static str,
+		system: DrawSystem<W,>,
+	) -> Self {
+		self.draw.push((name, system,),);
+		self
+	}
+
+	pub fn update(&self, world: &mut W, input: &I,) {
+		for (_, system,) in &self.update {
+			system(world, input,);
+		}
+	}
+
+	pub fn draw<'a,>(&self, world: &'a W, queue: &mut RenderQueue<'a,>,) {
+		for (_, system,) in &self.draw {
+			system(world, queue,);
+		}
+	}
+
+	/// names of update systems in the order they run
+	pub fn update_names(&self,) -> Vec<&'static str,> {
+		self.update.iter().map(|(name, _,)| *name,).collect()
+	}
+
+	/// names of draw systems in the order they run
+	pub fn draw_names(&self,) -> Vec<&'static str,> {
+		self.draw.iter().map(|(name, _,)| *name,).collect()
+	}
+}
+
+impl<W, I,> Default for Schedule<W, I,> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,465 @@
+//! bitmap fonts described by [BMFont](https://www.angelcode.com/products/bmfont/doc/file_format.html)
+//! descriptors in either text or json form
+
+use crate::brwsr;
+use crate::brwsr::Fetch;
+use crate::brwsr::Rslt;
+use crate::engn::Image;
+use crate::engn::Point;
+use crate::engn::Rect;
+use crate::engn::backend::Src;
+use crate::engn::color::Color;
+use crate::engn::software::Pixmap;
+use anyhow::Context;
+use anyhow::anyhow;
+use anyhow::bail;
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::str::FromStr;
+use web_sys::HtmlCanvasElement;
+
+/// glyph drawn in place of characters the font does not contain
+const FALLBACK_GLYPH: char = '?';
+
+/// font face together with its glyph atlases
+pub struct Font {
+	face:    FontFace,
+	atlases: Vec<Image,>,
+	/// tinted copies of atlases keyed by page index and color
+	tinted:  RefCell<HashMap<(usize, Color,), Tinted,>,>,
+}
+
+/// atlas painted over with a tint, wherever the atlas itself lives
+enum Tinted {
+	Canvas(HtmlCanvasElement,),
+	Raster(Pixmap,),
+}
+
+impl Font {
+	/// load descriptor at `src` and every atlas page it refers to
+	///
+	/// descriptor is read as json when `src` ends with `.json`, otherwise as
+	/// BMFont text format. page files are resolved relative to `src`
+	pub async fn load(src: &str,) -> Rslt<Self,> {
+		let win = brwsr::window_obj()?;
+		let face: FontFace = if src.ends_with(".json",) {
+			win.fetch_json_de(src,).await?
+		} else {
+			win.fetch_text(src,).await?.parse()?
+		};
+
+		let mut atlases = Vec::with_capacity(face.pages.len(),);
+		for page in face.pages.iter() {
+			let atlas = Image::new().await?;
+			atlas.load(&relative_to(src, page,),).await?;
+			atlases.push(atlas,);
+		}
+
+		Ok(Self::new(face, atlases,),)
+	}
+
+	/// font drawing `face` from `atlases`, one per page in order
+	pub fn new(face: FontFace, atlases: Vec<Image,>,) -> Self {
+		Self { face, atlases, tinted: RefCell::new(HashMap::new(),), }
+	}
+
+	pub fn face(&self,) -> &FontFace {
+		&self.face
+	}
+
+	/// run `draw` with atlas of `page`, painted with `tint` if any
+	pub(super) fn with_atlas<O,>(
+		&self,
+		page: usize,
+		tint: Option<Color,>,
+		draw: impl FnOnce(Src<'_,>,) -> Rslt<O,>,
+	) -> Rslt<O,> {
+		let atlas = self
+			.atlases
+			.get(page,)
+			.with_context(|| format!("font has no page {page}"),)?;
+
+		let Some(color,) = tint else {
+			return draw(atlas.src(),);
+		};
+
+		let key = (page, color,);
+		if !self.tinted.borrow().contains_key(&key,) {
+			let tinted = match atlas.src() {
+				Src::Raster(pixmap,) => Tinted::Raster(pixmap.tinted(color,),),
+				Src::Canvas(_,) => Tinted::Canvas(brwsr::tinted(
+					atlas.element()?,
+					&color.to_css(),
+				)?,),
+			};
+			self.tinted.borrow_mut().insert(key, tinted,);
+		}
+		match &self.tinted.borrow()[&key] {
+			Tinted::Canvas(canvas,) => draw(Src::Canvas(canvas,),),
+			Tinted::Raster(pixmap,) => draw(Src::Raster(pixmap,),),
+		}
+	}
+}
+
+fn relative_to(descriptor: &str, file: &str,) -> String {
+	match descriptor.rsplit_once('/',) {
+		Some((dir, _,),) => format!("{dir}/{file}"),
+		None => file.to_string(),
+	}
+}
+
+/// horizontal alignment of each line against the position text is drawn at
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq,)]
+pub enum Align {
+	#[default]
+	Left,
+	Center,
+	Right,
+}
+
+#[derive(Clone, Debug, Default,)]
+pub struct TextStyle {
+	pub align:     Align,
+	/// lines longer than this wrap at the last space that fits
+	pub max_width: Option<i16,>,
+	/// color every glyph is painted with. glyphs keep atlas colors when this
+	/// is `None`
+	pub tint:      Option<Color,>,
+	/// extra space between lines on top of the font's own line height
+	pub line_gap:  i16,
+}
+
+/// glyph positioned by [`FontFace::layout`]
+#[derive(Debug, PartialEq, Eq,)]
+pub struct PlacedGlyph {
+	pub ch:   char,
+	pub page: usize,
+	/// top left corner relative to the position text is drawn at
+	pub pos:  Point,
+}
+
+/// glyph metrics without any atlas image, which is enough to lay text out
+#[derive(Deserialize, Debug,)]
+#[serde(from = "Descriptor")]
+pub struct FontFace {
+	glyphs:      HashMap<char, Glyph,>,
+	kernings:    HashMap<(char, char,), i16,>,
+	line_height: i16,
+	base:        i16,
+	pages:       Vec<String,>,
+}
+
+#[derive(Debug,)]
+pub struct Glyph {
+	pub(super) clip: Rect,
+	page:            usize,
+	x_offset:        i16,
+	y_offset:        i16,
+	x_advance:       i16,
+}
+
+impl FontFace {
+	pub fn line_height(&self,) -> i16 {
+		self.line_height
+	}
+
+	/// distance from the top of a line to the baseline
+	pub fn base(&self,) -> i16 {
+		self.base
+	}
+
+	pub fn glyph(&self, ch: char,) -> Option<&Glyph,> {
+		self.glyphs.get(&ch,).or_else(|| self.glyphs.get(&FALLBACK_GLYPH,),)
+	}
+
+	pub fn kerning(&self, first: char, second: char,) -> i16 {
+		self.kernings.get(&(first, second,),).copied().unwrap_or_default()
+	}
+
+	/// width of `text` laid out on a single line, held at the limits of
+	/// `i16` when wider
+	pub fn measure(&self, text: &str,) -> i16 {
+		let mut prev = None;
+		text.chars().fold(0i16, |w, ch| {
+			let kern = prev.map_or(0, |p| self.kerning(p, ch,),);
+			prev = Some(ch,);
+			w.saturating_add(kern,)
+				.saturating_add(self.glyph(ch,).map_or(0, |g| g.x_advance,),)
+		},)
+	}
+
+	/// place every visible glyph of `text`
+	///
+	/// `\n` always starts a new line, and spaces are where lines wrap when
+	/// `style.max_width` is set. glyphs further away than `i16` reaches are
+	/// held at its limits, piling up far out of view
+	pub fn layout(&self, text: &str, style: &TextStyle,) -> Vec<PlacedGlyph,> {
+		let mut placed = vec![];
+		let lines =
+			text.split('\n',).flat_map(|l| self.wrap(l, style.max_width,),);
+		for (row, line,) in lines.enumerate() {
+			let width = self.measure(&line,);
+			let mut pen = match style.align {
+				Align::Left => 0,
+				Align::Center => -(width / 2),
+				Align::Right => width.saturating_neg(),
+			};
+			let row = i16::try_from(row,).unwrap_or(i16::MAX,);
+			let top = row.saturating_mul(
+				self.line_height.saturating_add(style.line_gap,),
+			);
+
+			let mut prev = None;
+			for ch in line.chars() {
+				let Some(glyph,) = self.glyph(ch,) else {
+					continue;
+				};
+				pen = pen
+					.saturating_add(prev.map_or(0, |p| self.kerning(p, ch,),),);
+				prev = Some(ch,);
+
+				if glyph.clip.w != 0 && glyph.clip.h != 0 {
+					placed.push(PlacedGlyph {
+						ch,
+						page: glyph.page,
+						pos: Point {
+							x: pen.saturating_add(glyph.x_offset,),
+							y: top.saturating_add(glyph.y_offset,),
+						},
+					},);
+				}
+				pen = pen.saturating_add(glyph.x_advance,);
+			}
+		}
+
+		placed
+	}
+
+	fn wrap(&self, line: &str, max_width: Option<i16,>,) -> Vec<String,> {
+		let Some(max_width,) = max_width else {
+			return vec![line.to_string()];
+		};
+
+		let mut lines = vec![];
+		let mut cur = String::new();
+		for word in line.split(' ',) {
+			let candidate = if cur.is_empty() {
+				word.to_string()
+			} else {
+				format!("{cur} {word}")
+			};
+
+			// a single word wider than `max_width` overflows on its own line
+			if self.measure(&candidate,) > max_width && !cur.is_empty() {
+				lines.push(std::mem::replace(&mut cur, word.to_string(),),);
+			} else {
+				cur = candidate;
+			}
+		}
+		lines.push(cur,);
+		lines
+	}
+}
+
+impl FromStr for FontFace {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str,) -> Result<Self, Self::Err,> {
+		Ok(s.parse::<Descriptor>()?.into(),)
+	}
+}
+
+/// BMFont descriptor as written to disk
+#[derive(Deserialize, Debug, Default,)]
+struct Descriptor {
+	common:   Common,
+	pages:    Vec<String,>,
+	chars:    Vec<CharDesc,>,
+	#[serde(default)]
+	kernings: Vec<KerningDesc,>,
+}
+
+#[derive(Deserialize, Debug, Default,)]
+#[serde(rename_all = "camelCase")]
+struct Common {
+	line_height: i16,
+	base:        i16,
+	/// count of pages the descriptor lists
+	#[serde(default)]
+	pages:       usize,
+}
+
+#[derive(Deserialize, Debug,)]
+struct CharDesc {
+	id:       u32,
+	x:        u16,
+	y:        u16,
+	width:    u16,
+	height:   u16,
+	xoffset:  i16,
+	yoffset:  i16,
+	xadvance: i16,
+	#[serde(default)]
+	page:     usize,
+}
+
+#[derive(Deserialize, Debug,)]
+struct KerningDesc {
+	first:  u32,
+	second: u32,
+	amount: i16,
+}
+
+impl From<Descriptor,> for FontFace {
+	fn from(desc: Descriptor,) -> Self {
+		let glyphs = desc
+			.chars
+			.into_iter()
+			.filter_map(|c| {
+				let glyph = Glyph {
+					clip:      Rect {
+						x: c.x, y: c.y, w: c.width, h: c.height,
+					},
+					page:      c.page,
+					x_offset:  c.xoffset,
+					y_offset:  c.yoffset,
+					x_advance: c.xadvance,
+				};
+				Some((char::from_u32(c.id,)?, glyph,),)
+			},)
+			.collect();
+		let kernings = desc
+			.kernings
+			.into_iter()
+			.filter_map(|k| {
+				let pair =
+					(char::from_u32(k.first,)?, char::from_u32(k.second,)?,);
+				Some((pair, k.amount,),)
+			},)
+			.collect();
+
+		Self {
+			glyphs,
+			kernings,
+			line_height: desc.common.line_height,
+			base: desc.common.base,
+			pages: desc.pages,
+		}
+	}
+}
+
+impl FromStr for Descriptor {
+	type Err = anyhow::Error;
+
+	/// parse text format, one tag followed by `key=value` pairs per line
+	fn from_str(s: &str,) -> Result<Self, Self::Err,> {
+		let mut desc = Self::default();
+		for (i, line,) in s.lines().enumerate() {
+			let mut tokens = tokenize(line,).into_iter();
+			let Some(tag,) = tokens.next() else {
+				continue;
+			};
+			let attrs = tokens
+				.map(|t| {
+					t.split_once('=',)
+						.map(|(k, v,)| (k.to_string(), v.to_string(),),)
+						.ok_or_else(|| {
+							anyhow!("line {}: malformed `{t}`", i + 1)
+						},)
+				},)
+				.collect::<Rslt<HashMap<_, _,>,>>()?;
+			let num = |key: &str| {
+				attrs
+					.get(key,)
+					.with_context(|| {
+						format!("line {}: missing `{key}`", i + 1)
+					},)?
+					.parse::<i64>()
+					.with_context(|| {
+						format!("line {}: `{key}` is not a number", i + 1)
+					},)
+			};
+
+			match tag.as_str() {
+				"common" => {
+					desc.common = Common {
+						line_height: num("lineHeight",)? as i16,
+						base:        num("base",)? as i16,
+						pages:       num("pages",)?
+							.try_into()
+							.unwrap_or_default(),
+					}
+				},
+				"page" => {
+					let id = num("id",)?;
+					// ids are bounded by `common` so a bad one cannot make
+					// the page list huge
+					let Some(id,) = usize::try_from(id,)
+						.ok()
+						.filter(|id| *id < desc.common.pages,)
+					else {
+						bail!(
+							"line {}: page {id} is not one of the {} pages in \
+							 `common`",
+							i + 1,
+							desc.common.pages
+						);
+					};
+					let file = attrs.get("file",).with_context(|| {
+						format!("line {}: missing `file`", i + 1)
+					},)?;
+					if desc.pages.len() <= id {
+						desc.pages.resize(id + 1, String::new(),);
+					}
+					desc.pages[id] = file.clone();
+				},
+				"char" => desc.chars.push(CharDesc {
+					id:       num("id",)? as u32,
+					x:        num("x",)? as u16,
+					y:        num("y",)? as u16,
+					width:    num("width",)? as u16,
+					height:   num("height",)? as u16,
+					xoffset:  num("xoffset",)? as i16,
+					yoffset:  num("yoffset",)? as i16,
+					xadvance: num("xadvance",)? as i16,
+					page:     num("page",).unwrap_or_default() as usize,
+				},),
+				"kerning" => desc.kernings.push(KerningDesc {
+					first:  num("first",)? as u32,
+					second: num("second",)? as u32,
+					amount: num("amount",)? as i16,
+				},),
+				// `info`, `chars` and `kernings` carry nothing layout needs
+				_ => {},
+			}
+		}
+
+		if desc.pages.is_empty() {
+			bail!("font descriptor has no page");
+		}
+		Ok(desc,)
+	}
+}
+
+/// split on whitespace outside of double quotes, dropping the quotes
+fn tokenize(line: &str,) -> Vec<String,> {
+	let mut tokens = vec![];
+	let mut cur = String::new();
+	let mut quoted = false;
+	for ch in line.chars() {
+		match ch {
+			'"' => quoted = !quoted,
+			c if c.is_whitespace() && !quoted => {
+				if !cur.is_empty() {
+					tokens.push(std::mem::take(&mut cur,),);
+				}
+			},
+			c => cur.push(c,),
+		}
+	}
+	if !cur.is_empty() {
+		tokens.push(cur,);
+	}
+	tokens
+}
@@ -0,0 +1,68 @@
+//! values changing along a line through a few points, as designers write
+//! them in data files
+//!
+//! written in json as `[[x, y], ...]` with `x` increasing, and held flat
+//! before the first point and after the last
+
+use crate::brwsr::Rslt;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize,)]
+#[serde(try_from = "Vec<(f64, f64)>", into = "Vec<(f64, f64)>")]
+pub struct Keyframes {
+	points: Vec<(f64, f64,),>,
+}
+
+impl Keyframes {
+	/// line through `points`, which needs at least one and `x` increasing
+	pub fn new(points: Vec<(f64, f64,),>,) -> Rslt<Self,> {
+		if points.is_empty() {
+			bail!("keyframes need at least one point");
+		}
+		if let Some(w,) = points.windows(2,).find(|w| w[0].0 >= w[1].0,) {
+			bail!("keyframe at {} does not come after {}", w[1].0, w[0].0);
+		}
+		if points.iter().any(|(x, y,)| !x.is_finite() || !y.is_finite(),) {
+			bail!("keyframes need finite numbers");
+		}
+		Ok(Self { points, },)
+	}
+
+	/// same value everywhere
+	pub fn constant(value: f64,) -> Self {
+		Self { points: vec![(0.0, value,)], }
+	}
+
+	pub fn points(&self,) -> &[(f64, f64,)] {
+		&self.points
+	}
+
+	/// value at `x`
+	pub fn at(&self, x: f64,) -> f64 {
+		let after = self.points.partition_point(|(px, _,)| *px <= x,);
+		match (after.checked_sub(1,), self.points.get(after,),) {
+			(None, _,) => self.points[0].1,
+			(Some(i,), None,) => self.points[i].1,
+			(Some(i,), Some(&(x1, y1,),),) => {
+				let (x0, y0,) = self.points[i];
+				y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+			},
+		}
+	}
+}
+
+impl TryFrom<Vec<(f64, f64,),>,> for Keyframes {
+	type Error = anyhow::Error;
+
+	fn try_from(points: Vec<(f64, f64,),>,) -> Rslt<Self,> {
+		Self::new(points,)
+	}
+}
+
+impl From<Keyframes,> for Vec<(f64, f64,),> {
+	fn from(value: Keyframes,) -> Self {
+		value.points
+	}
+}
@@ -0,0 +1,136 @@
+//! 9-patch images, whose corners keep their size while edges and center
+//! grow to fill any area
+//!
+//! insets cut a sheet frame into a 3x3 grid. corners are drawn as they are,
+//! top and bottom edges grow horizontally, left and right edges vertically,
+//! and the center both ways
+
+use crate::engn::camera::Bounds;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// width of the border on each side of a frame, in pixels
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq,)]
+pub struct Insets {
+	pub left:   u16,
+	pub top:    u16,
+	pub right:  u16,
+	pub bottom: u16,
+}
+
+/// how a slice fills an area larger than itself
+#[derive(Clone, Copy, Debug, PartialEq, Eq,)]
+pub enum Fill {
+	Stretch,
+	/// repeat at its own size, cutting the last repeat short
+	Tile,
+}
+
+/// part of the source frame and where it is drawn
+#[derive(Clone, Copy, Debug, PartialEq,)]
+pub struct Patch {
+	pub src:  Bounds,
+	pub dest: Bounds,
+}
+
+/// one run along an axis: `(src start, src len, dest start, dest len)`
+type Span = (f64, f64, f64, f64,);
+
+/// patches drawing frame `src` cut by `insets` over `dest`
+///
+/// when `dest` is smaller than the borders, they shrink to fit and the
+/// middle slices vanish
+pub fn slices(
+	src: Bounds,
+	insets: Insets,
+	dest: Bounds,
+	edges: Fill,
+	center: Fill,
+) -> Vec<Patch,> {
+	let cols = axis(
+		src.x,
+		src.w,
+		insets.left.into(),
+		insets.right.into(),
+		dest.x,
+		dest.w,
+	);
+	let rows = axis(
+		src.y,
+		src.h,
+		insets.top.into(),
+		insets.bottom.into(),
+		dest.y,
+		dest.h,
+	);
+
+	let mut patches = vec![];
+	for (j, row,) in rows.iter().enumerate() {
+		for (i, col,) in cols.iter().enumerate() {
+			let fill = match (i == 1, j == 1,) {
+				(true, true,) => center,
+				(false, false,) => Fill::Stretch,
+				_ => edges,
+			};
+			let tile = fill == Fill::Tile;
+			// corners never tile, edges only tile along their length
+			let xs = spans(*col, tile && i == 1,);
+			let ys = spans(*row, tile && j == 1,);
+			for &(sx, sw, dx, dw,) in &xs {
+				for &(sy, sh, dy, dh,) in &ys {
+					patches.push(Patch {
+						src:  Bounds { x: sx, y: sy, w: sw, h: sh, },
+						dest: Bounds { x: dx, y: dy, w: dw, h: dh, },
+					},);
+				}
+			}
+		}
+	}
+	patches
+}
+
+/// the three slices along one axis, empty ones dropped later by `spans`
+fn axis(
+	src: f64,
+	len: f64,
+	start_inset: f64,
+	end_inset: f64,
+	dest: f64,
+	dest_len: f64,
+) -> [Span; 3] {
+	let borders = start_inset + end_inset;
+	let k = if borders > dest_len && borders > 0.0 {
+		dest_len / borders
+	} else {
+		1.0
+	};
+	let (ds, de,) = (start_inset * k, end_inset * k,);
+	[
+		(src, start_inset, dest, ds,),
+		(
+			src + start_inset,
+			(len - borders).max(0.0,),
+			dest + ds,
+			(dest_len - ds - de).max(0.0,),
+		),
+		(src + len - end_inset, end_inset, dest + dest_len - de, de,),
+	]
+}
+
+/// `span` as drawn, repeated at its own size when `tile`d
+fn spans((src, len, dest, dest_len,): Span, tile: bool,) -> Vec<Span,> {
+	if len <= 0.0 || dest_len <= 0.0 {
+		return vec![];
+	}
+	if !tile {
+		return vec![(src, len, dest, dest_len,)];
+	}
+	let mut out = vec![];
+	let mut done = 0.0;
+	while done < dest_len {
+		let step = len.min(dest_len - done,);
+		out.push((src, step, dest + done, step,),);
+		done += step;
+	}
+	out
+}
@@ -0,0 +1,304 @@
+//! short lived sprites and shapes for dust, sparks and impacts
+//!
+//! particles live in a pool allocated up front and are stepped once per
+//! simulation tick, so the same inputs always give the same effects
+
+use crate::brwsr::Rslt;
+use crate::engn::DrawParams;
+use crate::engn::Image;
+use crate::engn::Point;
+use crate::engn::color::Color;
+use crate::engn::queue::Layer;
+use crate::engn::queue::RenderQueue;
+use crate::engn::shape::Shape;
+use crate::engn::shape::ShapeStyle;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// value changing linearly over a particle's life
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize,)]
+pub struct Curve {
+	pub start: f64,
+	pub end:   f64,
+}
+
+impl Curve {
+	pub fn constant(value: f64,) -> Self {
+		Self { start: value, end: value, }
+	}
+
+	pub fn linear(start: f64, end: f64,) -> Self {
+		Self { start, end, }
+	}
+
+	/// value at `t`, from `0.0` at birth to `1.0` at death
+	pub fn at(&self, t: f64,) -> f64 {
+		self.start + (self.end - self.start) * t.clamp(0.0, 1.0,)
+	}
+}
+
+/// what a particle is drawn as
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize,)]
+pub enum Look {
+	/// frame of the sprite sheet given to [`ParticleSystem::draw`], centered
+	/// on the particle
+	Sprite(String,),
+	Circle {
+		radius: f64,
+		color:  Color,
+	},
+	Square {
+		size:  f64,
+		color: Color,
+	},
+}
+
+/// how particles of one kind are born, move and look
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize,)]
+pub struct ParticleKind {
+	/// lifetime in ticks, picked between both ends
+	pub lifetime: (u32, u32,),
+	/// distance per tick, picked between both ends
+	pub speed:    (f64, f64,),
+	/// direction in radians, clockwise from the right, picked between both
+	/// ends
+	pub angle:    (f64, f64,),
+	/// added to vertical velocity every tick
+	pub gravity:  f64,
+	pub alpha:    Curve,
+	pub scale:    Curve,
+	pub look:     Look,
+}
+
+/// handle to a kind registered with [`ParticleSystem::add_kind`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize,)]
+pub struct KindId(usize,);
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize,)]
+pub struct Particle {
+	pub kind:     KindId,
+	pub pos:      (f64, f64,),
+	pub vel:      (f64, f64,),
+	pub age:      u32,
+	pub lifetime: u32,
+}
+
+impl Particle {
+	/// fraction of its life already spent
+	pub fn progress(&self,) -> f64 {
+		self.age as f64 / self.lifetime.max(1,) as f64
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize,)]
+pub struct ParticleSystem {
+	kinds:     Vec<ParticleKind,>,
+	/// live particles. never grows past `capacity`
+	particles: Vec<Particle,>,
+	capacity:  usize,
+	rng:       XorShift,
+}
+
+impl ParticleSystem {
+	/// room for at most `capacity` live particles. particles emitted while
+	/// full are dropped
+	pub fn new(capacity: usize, seed: u32,) -> Self {
+		Self {
+			kinds: vec![],
+			particles: Vec::with_capacity(capacity,),
+			capacity,
+			rng: XorShift::new(seed,),
+		}
+	}
+
+	pub fn add_kind(&mut self, kind: ParticleKind,) -> KindId {
+		self.kinds.push(kind,);
+		KindId(self.kinds.len() - 1,)
+	}
+
+	pub fn kind(&self, id: KindId,) -> &ParticleKind {
+		&self.kinds[id.0]
+	}
+
+	/// whether `id` was registered with this system
+	pub fn has_kind(&self, id: KindId,) -> bool {
+		id.0 < self.kinds.len()
+	}
+
+	/// fail unless every live particle is of a registered kind. systems
+	/// read back from a file may have been tampered with
+	pub fn check(&self,) -> Rslt<(),> {
+		if let Some(p,) =
+			self.particles.iter().find(|p| !self.has_kind(p.kind,),)
+		{
+			bail!("particle of unknown kind {}", p.kind.0);
+		}
+		Ok((),)
+	}
+
+	/// emit `count` particles of `kind` at once from `at`
+	pub fn burst(&mut self, kind: KindId, at: (f64, f64,), count: u32,) {
+		for _ in 0..count {
+			if self.particles.len() >= self.capacity {
+				return;
+			}
+			let k = &self.kinds[kind.0];
+			let lifetime = self.rng.range_u32(k.lifetime,);
+			let speed = self.rng.range(k.speed,);
+			let angle = self.rng.range(k.angle,);
+			self.particles.push(Particle {
+				kind,
+				pos: at,
+				vel: (angle.cos() * speed, angle.sin() * speed,),
+				age: 0,
+				lifetime,
+			},);
+		}
+	}
+
+	/// step every particle one tick, dropping those which reached their end
+	pub fn update(&mut self,) {
+		let kinds = &self.kinds;
+		self.particles.retain_mut(|p| {
+			p.age += 1;
+			p.vel.1 += kinds[p.kind.0].gravity;
+			p.pos = (p.pos.0 + p.vel.0, p.pos.1 + p.vel.1,);
+			p.age < p.lifetime
+		},);
+	}
+
+	pub fn particles(&self,) -> &[Particle] {
+		&self.particles
+	}
+
+	pub fn len(&self,) -> usize {
+		self.particles.len()
+	}
+
+	pub fn is_empty(&self,) -> bool {
+		self.particles.is_empty()
+	}
+
+	/// queue every live particle. sprite looks are taken from `sheet`, and
+	/// skipped without one
+	pub fn draw<'a,>(
+		&self,
+		queue: &mut RenderQueue<'a,>,
+		layer: Layer,
+		z: i32,
+		sheet: Option<&'a Image,>,
+	) {
+		for p in &self.particles {
+			let kind = &self.kinds[p.kind.0];
+			let (alpha, scale,) =
+				(kind.alpha.at(p.progress(),), kind.scale.at(p.progress(),),);
+			let (x, y,) = p.pos;
+			match &kind.look {
+				Look::Sprite(name,) => {
+					let Some(sheet,) = sheet else {
+						continue;
+					};
+					let Some(b,) =
+						sheet.sprite_bounds(name, Point { x: 0, y: 0, },)
+					else {
+						continue;
+					};
+					let pos = Point {
+						x: (x - b.w / 2.0).round() as i16,
+						y: (y - b.h / 2.0).round() as i16,
+					};
+					queue.sprite(
+						layer,
+						z,
+						sheet,
+						name.as_str(),
+						pos,
+						DrawParams { alpha, ..DrawParams::scaled(scale,) },
+					);
+				},
+				Look::Circle { radius, color, } => queue.primitive(
+					layer,
+					z,
+					Shape::Circle { cx: x, cy: y, r: radius * scale, },
+					ShapeStyle { alpha, ..ShapeStyle::fill(*color,) },
+				),
+				Look::Square { size, color, } => {
+					let size = size * scale;
+					queue.primitive(
+						layer,
+						z,
+						Shape::Rect {
+							x: x - size / 2.0,
+							y: y - size / 2.0,
+							w: size,
+							h: size,
+						},
+						ShapeStyle { alpha, ..ShapeStyle::fill(*color,) },
+					)
+				},
+			}
+		}
+	}
+}
+
+/// emits a steady stream of particles while active
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize,)]
+pub struct Emitter {
+	pub kind:     KindId,
+	/// particles per tick. fractions carry over to the next ticks
+	pub per_tick: f64,
+	pub active:   bool,
+	carry:        f64,
+}
+
+impl Emitter {
+	pub fn new(kind: KindId, per_tick: f64,) -> Self {
+		Self { kind, per_tick, active: false, carry: 0.0, }
+	}
+
+	/// emit this tick's share of particles from `at`
+	pub fn update(&mut self, system: &mut ParticleSystem, at: (f64, f64,),) {
+		if !self.active {
+			self.carry = 0.0;
+			return;
+		}
+		self.carry += self.per_tick;
+		let count = self.carry.floor();
+		self.carry -= count;
+		system.burst(self.kind, at, count as u32,);
+	}
+}
+
+/// small deterministic generator, good enough for where a spark flies
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize,)]
+struct XorShift(u32,);
+
+impl XorShift {
+	fn new(seed: u32,) -> Self {
+		// zero would only ever produce zero
+		Self(seed.max(1,),)
+	}
+
+	fn next(&mut self,) -> u32 {
+		let mut x = self.0;
+		x ^= x << 13;
+		x ^= x >> 17;
+		x ^= x << 5;
+		self.0 = x;
+		x
+	}
+
+	/// uniform in `[lo, hi)`
+	fn range(&mut self, (lo, hi,): (f64, f64,),) -> f64 {
+		lo + (hi - lo) * (self.next() as f64 / (u32::MAX as f64 + 1.0))
+	}
+
+	/// uniform in `[lo, hi]`
+	fn range_u32(&mut self, (lo, hi,): (u32, u32,),) -> u32 {
+		if hi <= lo {
+			return lo;
+		}
+		lo + self.next() % (hi - lo).saturating_add(1,)
+	}
+}
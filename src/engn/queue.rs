@@ -0,0 +1,281 @@
+//! draw commands collected over a frame, then sorted and drawn at once by
+//! [`Renderer::flush`]
+//!
+//! [`Renderer::flush`]: crate::engn::Renderer::flush
+
+use crate::engn::DrawParams;
+use crate::engn::Image;
+use crate::engn::Point;
+use crate::engn::cache::CachedLayer;
+use crate::engn::camera::Bounds;
+use crate::engn::color::Color;
+use crate::engn::font::Align;
+use crate::engn::font::Font;
+use crate::engn::font::TextStyle;
+use crate::engn::shape::Shape;
+use crate::engn::shape::ShapeStyle;
+use crate::engn::tilemap::Tilemap;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+/// coarse draw order. every command of a layer is drawn over every command
+/// of the layers before it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash,)]
+pub enum Layer {
+	Background,
+	World,
+	Effects,
+	/// drawn in screen space, unaffected by the camera
+	Hud,
+	/// drawn in screen space over everything else
+	Debug,
+}
+
+impl Layer {
+	pub fn is_screen_space(&self,) -> bool {
+		matches!(self, Self::Hud | Self::Debug)
+	}
+}
+
+pub enum Command<'a,> {
+	Sprite {
+		image:  &'a Image,
+		name:   String,
+		pos:    Point,
+		params: DrawParams,
+	},
+	Text {
+		font:  &'a Font,
+		text:  String,
+		pos:   Point,
+		style: TextStyle,
+	},
+	Primitive {
+		shape: Shape,
+		style: ShapeStyle,
+	},
+	/// text in the browser's font, see [`Renderer::draw_label_with`]
+	///
+	/// [`Renderer::draw_label_with`]: crate::engn::Renderer::draw_label_with
+	Label {
+		text:  String,
+		pos:   (f64, f64,),
+		color: Color,
+		size:  f64,
+		align: Align,
+	},
+	/// composite of a cache already drawn by [`Renderer::update_cache`]
+	///
+	/// [`Renderer::update_cache`]: crate::engn::Renderer::update_cache
+	Cached(&'a CachedLayer,),
+	/// tiles of `map`'s layer `index` in view
+	Tiles {
+		map:   &'a Tilemap,
+		index: usize,
+	},
+}
+
+impl Debug for Command<'_,> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_,>,) -> std::fmt::Result {
+		match self {
+			Self::Sprite { name, pos, params, .. } => f
+				.debug_struct("Sprite",)
+				.field("name", name,)
+				.field("pos", pos,)
+				.field("params", params,)
+				.finish(),
+			Self::Text { text, pos, style, .. } => f
+				.debug_struct("Text",)
+				.field("text", text,)
+				.field("pos", pos,)
+				.field("style", style,)
+				.finish(),
+			Self::Primitive { shape, style, } => f
+				.debug_struct("Primitive",)
+				.field("shape", shape,)
+				.field("style", style,)
+				.finish(),
+			Self::Label { text, pos, color, size, align, } => f
+				.debug_struct("Label",)
+				.field("text", text,)
+				.field("pos", pos,)
+				.field("color", color,)
+				.field("size", size,)
+				.field("align", align,)
+				.finish(),
+			Self::Cached(layer,) => {
+				f.debug_tuple("Cached",).field(&layer.bounds(),).finish()
+			},
+			Self::Tiles { map, index, } => f
+				.debug_struct("Tiles",)
+				.field("layer", &map.layers().get(*index,).map(|l| &l.name,),)
+				.field("bounds", &map.bounds(*index,),)
+				.finish(),
+		}
+	}
+}
+
+/// command together with where it is drawn in the frame
+pub struct Queued<'a,> {
+	pub layer: Layer,
+	pub z:     i32,
+	pub cmd:   Command<'a,>,
+}
+
+#[derive(Default,)]
+pub struct RenderQueue<'a,> {
+	queued: Vec<Queued<'a,>,>,
+}
+
+impl<'a,> RenderQueue<'a,> {
+	pub fn new() -> Self {
+		Self { queued: vec![], }
+	}
+
+	/// queue `cmd`. within a layer, lower `z` is drawn first and equal `z`
+	/// keeps submission order
+	pub fn push(&mut self, layer: Layer, z: i32, cmd: Command<'a,>,) {
+		self.queued.push(Queued { layer, z, cmd, },);
+	}
+
+	pub fn sprite(
+		&mut self,
+		layer: Layer,
+		z: i32,
+		image: &'a Image,
+		name: impl Into<String,>,
+		pos: Point,
+		params: DrawParams,
+	) {
+		self.push(
+			layer,
+			z,
+			Command::Sprite { image, name: name.into(), pos, params, },
+		);
+	}
+
+	pub fn text(
+		&mut self,
+		layer: Layer,
+		z: i32,
+		font: &'a Font,
+		text: impl Into<String,>,
+		pos: Point,
+		style: TextStyle,
+	) {
+		self.push(
+			layer,
+			z,
+			Command::Text { font, text: text.into(), pos, style, },
+		);
+	}
+
+	pub fn primitive(
+		&mut self,
+		layer: Layer,
+		z: i32,
+		shape: Shape,
+		style: ShapeStyle,
+	) {
+		self.push(layer, z, Command::Primitive { shape, style, },);
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn label(
+		&mut self,
+		layer: Layer,
+		z: i32,
+		text: impl Into<String,>,
+		pos: (f64, f64,),
+		color: Color,
+		size: f64,
+		align: Align,
+	) {
+		self.push(
+			layer,
+			z,
+			Command::Label { text: text.into(), pos, color, size, align, },
+		);
+	}
+
+	pub fn cached(&mut self, layer: Layer, z: i32, cache: &'a CachedLayer,) {
+		self.push(layer, z, Command::Cached(cache,),);
+	}
+
+	pub fn tiles(
+		&mut self,
+		layer: Layer,
+		z: i32,
+		map: &'a Tilemap,
+		index: usize,
+	) {
+		self.push(layer, z, Command::Tiles { map, index, },);
+	}
+
+	pub fn len(&self,) -> usize {
+		self.queued.len()
+	}
+
+	pub fn is_empty(&self,) -> bool {
+		self.queued.is_empty()
+	}
+
+	/// commands in the order they are drawn, leaving out world ones
+	/// `bounds_of` places entirely outside of `view`
+	///
+	/// stats are those of the frame the commands make, but for batches which
+	/// only drawing tells
+	pub fn cull(
+		mut self,
+		view: &Bounds,
+		bounds_of: impl Fn(&Command<'a,>,) -> Option<Bounds,>,
+	) -> (Vec<Queued<'a,>,>, FrameStats,) {
+		let mut stats =
+			FrameStats { submitted: self.len(), ..Default::default() };
+		// stable, so equal keys keep submission order
+		self.queued.sort_by_key(|q| (q.layer, q.z,),);
+		self.queued.retain(|q| {
+			let out = !q.layer.is_screen_space()
+				&& bounds_of(&q.cmd,).is_some_and(|b| !intersects(&b, view,),);
+			stats.culled += usize::from(out,);
+			!out
+		},);
+		stats.drawn = self.queued.len();
+		for q in self.queued.iter() {
+			*stats.per_layer.entry(q.layer,).or_default() += 1;
+		}
+		(self.queued, stats,)
+	}
+}
+
+/// what happened to the commands of the last flushed frame
+#[derive(Clone, Debug, Default, PartialEq, Eq,)]
+pub struct FrameStats {
+	pub submitted: usize,
+	pub drawn:     usize,
+	/// commands skipped for lying outside of the camera's view
+	pub culled:    usize,
+	/// runs of consecutive draws reading the same image, which is what a
+	/// batching backend could merge into one call
+	pub batches:   usize,
+	pub per_layer: BTreeMap<Layer, usize,>,
+}
+
+/// axis aligned overlap test
+pub fn intersects(a: &Bounds, b: &Bounds,) -> bool {
+	a.x < b.x + b.w && b.x < a.x + a.w && a.y < b.y + b.h && b.y < a.y + a.h
+}
+
+/// area `a` and `b` share, if they overlap
+pub fn intersection(a: &Bounds, b: &Bounds,) -> Option<Bounds,> {
+	if !intersects(a, b,) {
+		return None;
+	}
+	let (x, y,) = (a.x.max(b.x,), a.y.max(b.y,),);
+	Some(Bounds {
+		x,
+		y,
+		w: (a.x + a.w).min(b.x + b.w,) - x,
+		h: (a.y + a.h).min(b.y + b.h,) - y,
+	},)
+}
@@ -0,0 +1,88 @@
+//! rope tied between two bodies, keeping them within its length of each
+//! other and hanging under its own slack
+//!
+//! the rope is a distance constraint: it does nothing while the bodies are
+//! closer than its length, and once they are further apart both are moved
+//! back in, the lighter one more
+
+use crate::engn::shape::Vertex;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize,)]
+pub struct Rope {
+	pub length: f64,
+}
+
+impl Rope {
+	pub fn new(length: f64,) -> Self {
+		Self { length, }
+	}
+
+	/// whether ends at `a` and `b` pull the rope straight
+	pub fn is_taut(&self, a: Vertex, b: Vertex,) -> bool {
+		distance(a, b,) >= self.length
+	}
+
+	/// moves bringing ends at `a` and `b` back within the rope's length,
+	/// shared between them in inverse proportion to their masses. `None`
+	/// while the rope is slack
+	pub fn solve(
+		&self,
+		a: Vertex,
+		b: Vertex,
+		mass_a: f64,
+		mass_b: f64,
+	) -> Option<(Vertex, Vertex,),> {
+		let d = distance(a, b,);
+		if d <= self.length || d == 0.0 {
+			return None;
+		}
+		let excess = d - self.length;
+		// unit vector from a to b
+		let (ux, uy,) = ((b.0 - a.0) / d, (b.1 - a.1) / d,);
+		let share_a = mass_b / (mass_a + mass_b);
+		let share_b = 1.0 - share_a;
+		Some((
+			(ux * excess * share_a, uy * excess * share_a,),
+			(-ux * excess * share_b, -uy * excess * share_b,),
+		),)
+	}
+
+	/// how far below the straight line between `a` and `b` the rope's
+	/// middle hangs
+	///
+	/// taken from the parabola as long as the rope, which is close for
+	/// shallow sags. a rope with its ends close together hangs half its
+	/// slack down instead
+	pub fn sag(&self, a: Vertex, b: Vertex,) -> f64 {
+		let d = distance(a, b,);
+		let slack = (self.length - d).max(0.0,);
+		(3.0 * d * slack / 8.0).sqrt().max(slack / 2.0,)
+	}
+
+	/// `segments + 1` points along the rope from `a` to `b`, hanging
+	/// downwards
+	pub fn curve(
+		&self,
+		a: Vertex,
+		b: Vertex,
+		segments: usize,
+	) -> Vec<Vertex,> {
+		let sag = self.sag(a, b,);
+		let segments = segments.max(1,);
+		(0..=segments)
+			.map(|i| {
+				let t = i as f64 / segments as f64;
+				(
+					a.0 + (b.0 - a.0) * t,
+					a.1 + (b.1 - a.1) * t + 4.0 * sag * t * (1.0 - t),
+				)
+			},)
+			.collect()
+	}
+}
+
+fn distance(a: Vertex, b: Vertex,) -> f64 {
+	(b.0 - a.0).hypot(b.1 - a.1,)
+}
@@ -0,0 +1,293 @@
+//! stack of scenes such as a title screen, gameplay and menus over it
+//!
+//! only the top scene updates. it is drawn over the scenes below it as long
+//! as it is an overlay, down to the first scene which is not, so a pause
+//! menu shows the paused game underneath
+
+use crate::brwsr::Rslt;
+use crate::engn::KeyboardState;
+use crate::engn::Renderer;
+use crate::engn::debug::EntityInfo;
+use crate::engn::transition::Transition;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+/// keys held this tick, and the ones held the tick before
+pub struct Input<'a,> {
+	now:  &'a KeyboardState,
+	prev: &'a KeyboardState,
+}
+
+impl<'a,> Input<'a,> {
+	pub fn new(now: &'a KeyboardState, prev: &'a KeyboardState,) -> Self {
+		Self { now, prev, }
+	}
+
+	pub fn is_pressed(&self, code: &str,) -> bool {
+		self.now.is_pressed(code,)
+	}
+
+	/// whether `code` went down this tick
+	pub fn just_pressed(&self, code: &str,) -> bool {
+		self.now.is_pressed(code,) && !self.prev.is_pressed(code,)
+	}
+
+	pub fn keyboard(&self,) -> &KeyboardState {
+		self.now
+	}
+}
+
+/// what the stack does once the top scene has updated
+pub enum Change<C,> {
+	Stay,
+	/// put a scene on top. it is initialized first
+	Push(Box<dyn Scene<C,>,>,),
+	/// drop the top scene
+	Pop,
+	/// drop the top scene and put a scene in its place
+	Replace(Box<dyn Scene<C,>,>,),
+	/// drop every scene and start over from a single one
+	Reset(Box<dyn Scene<C,>,>,),
+	/// play a transition and make the change once it covers the screen
+	Through(Transition, Box<Change<C,>,>,),
+}
+
+impl<C,> Change<C,> {
+	pub fn push(scene: impl Scene<C,> + 'static,) -> Self {
+		Self::Push(Box::new(scene,),)
+	}
+
+	pub fn replace(scene: impl Scene<C,> + 'static,) -> Self {
+		Self::Replace(Box::new(scene,),)
+	}
+
+	pub fn reset(scene: impl Scene<C,> + 'static,) -> Self {
+		Self::Reset(Box::new(scene,),)
+	}
+
+	/// make this change behind `transition`
+	pub fn through(self, transition: Transition,) -> Self {
+		Self::Through(transition, Box::new(self,),)
+	}
+}
+
+/// one screen of the game. `C` is whatever every scene shares, such as the
+/// renderer and loaded assets
+pub trait Scene<C,> {
+	/// shown in the debug overlay
+	fn name(&self,) -> &str;
+
+	/// called once, right before the scene is put on the stack
+	fn init(&mut self, _ctx: &mut C,) -> Rslt<(),> {
+		Ok((),)
+	}
+
+	/// advance one tick. only called while the scene is on top
+	fn update(&mut self, ctx: &mut C, input: &Input<'_,>,) -> Change<C,>;
+
+	fn draw(&self, ctx: &C,) -> Rslt<(),>;
+
+	/// whether scenes below stay visible, paused, under this one
+	fn is_overlay(&self,) -> bool {
+		false
+	}
+
+	/// entities to show in the debug overlay
+	fn entities(&self,) -> Vec<EntityInfo,> {
+		vec![]
+	}
+
+	/// `(name, value)` of whatever designers tune, shown in the debug
+	/// overlay as it changes
+	fn tuning(&self,) -> Vec<(String, String,),> {
+		vec![]
+	}
+
+	/// state of the scene in the middle of play, for scenes keeping any
+	fn snapshot(&self,) -> Rslt<Option<Value,>,> {
+		Ok(None,)
+	}
+
+	/// go back to `state` taken by [`Scene::snapshot`] of a scene like this
+	fn restore(&mut self, _ctx: &mut C, _state: Value,) -> Rslt<(),> {
+		bail!("{} scene keeps no state to restore", self.name())
+	}
+}
+
+/// state of one scene on a stack, see [`SceneStack::snapshot`]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize,)]
+pub struct SceneSnapshot {
+	/// name of the scene the state was taken from
+	pub scene: String,
+	pub state: Value,
+}
+
+pub struct SceneStack<C,> {
+	scenes:     Vec<Box<dyn Scene<C,>,>,>,
+	/// transition playing, with the change waiting for it to cover the screen
+	transition: Option<(Transition, Option<Change<C,>,>,),>,
+	/// keys held last tick, to tell presses apart from holds
+	prev_keys:  KeyboardState,
+}
+
+impl<C: AsRef<Renderer,>,> SceneStack<C,> {
+	pub fn new() -> Self {
+		Self {
+			scenes:     vec![],
+			transition: None,
+			prev_keys:  KeyboardState::new(),
+		}
+	}
+
+	pub fn len(&self,) -> usize {
+		self.scenes.len()
+	}
+
+	pub fn is_empty(&self,) -> bool {
+		self.scenes.is_empty()
+	}
+
+	/// names of the scenes from bottom to top
+	pub fn names(&self,) -> Vec<&str,> {
+		self.scenes.iter().map(|s| s.name(),).collect()
+	}
+
+	/// play `transition` without changing scenes, as when the game boots
+	pub fn play(&mut self, transition: Transition,) {
+		self.transition = Some((transition, None,),);
+	}
+
+	/// whether a transition is playing
+	pub fn is_transitioning(&self,) -> bool {
+		self.transition.is_some()
+	}
+
+	/// stop any transition playing, dropping the change waiting for it
+	pub fn cancel_transition(&mut self,) {
+		self.transition = None;
+	}
+
+	/// make `change` right away
+	pub fn apply(&mut self, ctx: &mut C, change: Change<C,>,) -> Rslt<(),> {
+		match change {
+			Change::Stay => {},
+			Change::Push(mut scene,) => {
+				scene.init(ctx,)?;
+				self.scenes.push(scene,);
+			},
+			Change::Pop => {
+				self.scenes.pop();
+			},
+			Change::Replace(mut scene,) => {
+				scene.init(ctx,)?;
+				self.scenes.pop();
+				self.scenes.push(scene,);
+			},
+			Change::Reset(mut scene,) => {
+				scene.init(ctx,)?;
+				self.scenes.clear();
+				self.scenes.push(scene,);
+			},
+			// a transition asked for mid transition replaces the running one
+			Change::Through(transition, change,) => {
+				self.transition = Some((transition, Some(*change,),),);
+			},
+		}
+		Ok((),)
+	}
+
+	/// advance the transition and the top scene one tick
+	///
+	/// the top scene stands still while a transition covers the screen, so
+	/// it can not ask for another change before the first one is made
+	pub fn update(&mut self, ctx: &mut C, keys: &KeyboardState,) -> Rslt<(),> {
+		let mut frozen = false;
+		if let Some((transition, pending,),) = self.transition.as_mut() {
+			let covered = transition.update();
+			frozen = transition.is_covering();
+			let done = transition.is_done();
+			let change = if covered { pending.take() } else { None };
+			if done {
+				self.transition = None;
+			}
+			if let Some(change,) = change {
+				self.apply(ctx, change,)?;
+			}
+		}
+
+		let input = Input::new(keys, &self.prev_keys,);
+		let change = match self.scenes.last_mut() {
+			Some(top,) if !frozen => top.update(ctx, &input,),
+			_ => Change::Stay,
+		};
+		self.prev_keys = keys.clone();
+		self.apply(ctx, change,)
+	}
+
+	/// draw the top scene over the overlays and scene below it, then any
+	/// transition over everything
+	pub fn draw(&self, ctx: &C,) -> Rslt<(),> {
+		for scene in &self.scenes[self.visible_from()..] {
+			scene.draw(ctx,)?;
+		}
+		match &self.transition {
+			Some((transition, _,),) => transition.draw(ctx.as_ref(),),
+			None => Ok((),),
+		}
+	}
+
+	/// entities of every visible scene
+	pub fn entities(&self,) -> Vec<EntityInfo,> {
+		self.scenes[self.visible_from()..]
+			.iter()
+			.flat_map(|s| s.entities(),)
+			.collect()
+	}
+
+	/// tuned values of every visible scene
+	pub fn tuning(&self,) -> Vec<(String, String,),> {
+		self.scenes[self.visible_from()..]
+			.iter()
+			.flat_map(|s| s.tuning(),)
+			.collect()
+	}
+
+	/// state of the topmost scene keeping any
+	pub fn snapshot(&self,) -> Rslt<Option<SceneSnapshot,>,> {
+		for scene in self.scenes.iter().rev() {
+			if let Some(state,) = scene.snapshot()? {
+				let scene = scene.name().to_string();
+				return Ok(Some(SceneSnapshot { scene, state, },),);
+			}
+		}
+		Ok(None,)
+	}
+
+	/// put the topmost scene named like the one `snapshot` was taken from
+	/// back into its state
+	pub fn restore(
+		&mut self,
+		ctx: &mut C,
+		snapshot: SceneSnapshot,
+	) -> Rslt<(),> {
+		let Some(scene,) =
+			self.scenes.iter_mut().rev().find(|s| s.name() == snapshot.scene,)
+		else {
+			bail!("no {} scene to restore", snapshot.scene);
+		};
+		scene.restore(ctx, snapshot.state,)
+	}
+
+	/// index of the lowest scene drawn
+	fn visible_from(&self,) -> usize {
+		self.scenes.iter().rposition(|s| !s.is_overlay(),).unwrap_or(0,)
+	}
+}
+
+impl<C: AsRef<Renderer,>,> Default for SceneStack<C,> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
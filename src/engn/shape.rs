@@ -0,0 +1,117 @@
+//! vector shapes drawn without any image, for health bars, debug drawing
+//! and ui panels
+
+use crate::engn::camera::Bounds;
+use crate::engn::color::Color;
+
+/// `(x, y)` of a vertex
+pub type Vertex = (f64, f64,);
+
+#[derive(Clone, Debug, PartialEq,)]
+pub enum Shape {
+	Rect {
+		x: f64,
+		y: f64,
+		w: f64,
+		h: f64,
+	},
+	Circle {
+		cx: f64,
+		cy: f64,
+		r:  f64,
+	},
+	Line {
+		from: Vertex,
+		to:   Vertex,
+	},
+	/// open path through every vertex
+	Polyline(Vec<Vertex,>,),
+	/// path through every vertex, closed back to the first one
+	Polygon(Vec<Vertex,>,),
+	/// part of a circle from `start` to `end` radians, clockwise. fill closes
+	/// it with a straight chord
+	Arc {
+		cx:    f64,
+		cy:    f64,
+		r:     f64,
+		start: f64,
+		end:   f64,
+	},
+}
+
+impl Shape {
+	/// area covered once stroked `line_width` wide
+	pub fn bounds(&self, line_width: f64,) -> Bounds {
+		let bounds = match self {
+			Self::Rect { x, y, w, h, } => {
+				Bounds { x: *x, y: *y, w: *w, h: *h, }
+			},
+			Self::Circle { cx, cy, r, } | Self::Arc { cx, cy, r, .. } => {
+				Bounds { x: cx - r, y: cy - r, w: r * 2.0, h: r * 2.0, }
+			},
+			Self::Line { from, to, } => vertex_bounds(&[*from, *to,],),
+			Self::Polyline(vs,) | Self::Polygon(vs,) => vertex_bounds(vs,),
+		};
+		let half = line_width.max(0.0,) / 2.0;
+		Bounds {
+			x: bounds.x - half,
+			y: bounds.y - half,
+			w: bounds.w + line_width.max(0.0,),
+			h: bounds.h + line_width.max(0.0,),
+		}
+	}
+}
+
+fn vertex_bounds(vs: &[Vertex],) -> Bounds {
+	if vs.is_empty() {
+		return Bounds { x: 0.0, y: 0.0, w: 0.0, h: 0.0, };
+	}
+	let (mut min, mut max,) = (
+		(f64::INFINITY, f64::INFINITY,),
+		(f64::NEG_INFINITY, f64::NEG_INFINITY,),
+	);
+	for &(x, y,) in vs {
+		min = (min.0.min(x,), min.1.min(y,),);
+		max = (max.0.max(x,), max.1.max(y,),);
+	}
+	Bounds { x: min.0, y: min.1, w: max.0 - min.0, h: max.1 - min.1, }
+}
+
+/// how a [`Shape`] is painted
+#[derive(Clone, Debug, PartialEq,)]
+pub struct ShapeStyle {
+	pub fill:       Option<Color,>,
+	pub stroke:     Option<Color,>,
+	pub line_width: f64,
+	/// lengths of alternating dashes and gaps. solid line when empty
+	pub dash:       Vec<f64,>,
+	/// multiplied with canvas' global alpha
+	pub alpha:      f64,
+}
+
+impl Default for ShapeStyle {
+	fn default() -> Self {
+		Self {
+			fill:       None,
+			stroke:     None,
+			line_width: 1.0,
+			dash:       vec![],
+			alpha:      1.0,
+		}
+	}
+}
+
+impl ShapeStyle {
+	pub fn fill(color: Color,) -> Self {
+		Self { fill: Some(color,), ..Self::default() }
+	}
+
+	pub fn stroke(color: Color, line_width: f64,) -> Self {
+		Self { stroke: Some(color,), line_width, ..Self::default() }
+	}
+
+	pub fn dashed(mut self, dash: impl Into<Vec<f64,>,>,) -> Self {
+		self.dash = dash.into();
+		self
+	}
+}
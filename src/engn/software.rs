@@ -0,0 +1,533 @@
+//! drawing into plain memory instead of a canvas, so frames can be rendered
+//! and compared against reference images without a browser
+//!
+//! images are sampled nearest neighbour and shapes are not antialiased,
+//! which keeps output identical across machines. text in browser fonts is
+//! not drawn, while bitmap fonts are
+
+use crate::brwsr::DrawParams;
+use crate::brwsr::Rslt;
+use crate::engn::color::Color;
+use crate::engn::viewport;
+use crate::engn::viewport::Transform;
+use anyhow::bail;
+use std::cell::Ref;
+use std::cell::RefCell;
+use std::f64::consts::TAU;
+use std::rc::Rc;
+
+const IDENTITY: Transform = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0,];
+
+/// rgba8 pixels, row major, not premultiplied
+#[derive(Clone, Debug, PartialEq, Eq,)]
+pub struct Pixmap {
+	pub w:    u32,
+	pub h:    u32,
+	pub rgba: Vec<u8,>,
+}
+
+impl Pixmap {
+	/// fully transparent
+	pub fn new(w: u32, h: u32,) -> Self {
+		Self { w, h, rgba: vec![0; (w * h * 4) as usize], }
+	}
+
+	pub fn from_rgba(w: u32, h: u32, rgba: Vec<u8,>,) -> Rslt<Self,> {
+		if rgba.len() != (w * h * 4) as usize {
+			bail!("{} bytes can not be {w}x{h} rgba pixels", rgba.len());
+		}
+		Ok(Self { w, h, rgba, },)
+	}
+
+	pub fn pixel(&self, x: u32, y: u32,) -> [u8; 4] {
+		let i = ((y * self.w + x) * 4) as usize;
+		[self.rgba[i], self.rgba[i + 1], self.rgba[i + 2], self.rgba[i + 3],]
+	}
+
+	/// copy painted `color` wherever this is, keeping its alpha, as a canvas
+	/// filling over it with `source-in` would
+	pub fn tinted(&self, color: Color,) -> Self {
+		let mut rgba = self.rgba.clone();
+		for px in rgba.chunks_exact_mut(4,) {
+			let a = u16::from(px[3],) * u16::from(color.a,) / 255;
+			px.copy_from_slice(&[color.r, color.g, color.b, a as u8,],);
+		}
+		Self { rgba, ..*self }
+	}
+
+	fn put(&mut self, x: u32, y: u32, px: [u8; 4],) {
+		let i = ((y * self.w + x) * 4) as usize;
+		self.rgba[i..i + 4].copy_from_slice(&px,);
+	}
+
+	/// paint `src` over pixel `x`, `y` with its alpha scaled by `alpha`
+	fn blend(&mut self, x: u32, y: u32, src: Color, alpha: f64,) {
+		if src.a == 0 || alpha <= 0.0 {
+			return;
+		}
+		let [r, g, b, a,] = self.pixel(x, y,);
+		let Color { r, g, b, a, } = src.over(Color::rgba(r, g, b, a,), alpha,);
+		self.put(x, y, [r, g, b, a,],);
+	}
+}
+
+#[derive(Clone, Debug, Default,)]
+struct SubPath {
+	points: Vec<(f64, f64,),>,
+	closed: bool,
+}
+
+#[derive(Debug,)]
+struct State {
+	pixmap:    Pixmap,
+	transform: Transform,
+	alpha:     f64,
+	saved:     Vec<(Transform, f64,),>,
+	/// current path, in pixels
+	path:      Vec<SubPath,>,
+}
+
+/// software stand-in for a canvas' 2d context. clones draw into the same
+/// pixels
+#[derive(Clone, Debug,)]
+pub struct Raster {
+	state: Rc<RefCell<State,>,>,
+}
+
+impl Raster {
+	pub fn new(w: u32, h: u32,) -> Self {
+		Self {
+			state: Rc::new(RefCell::new(State {
+				pixmap:    Pixmap::new(w, h,),
+				transform: IDENTITY,
+				alpha:     1.0,
+				saved:     vec![],
+				path:      vec![],
+			},),),
+		}
+	}
+
+	pub fn size(&self,) -> (u32, u32,) {
+		let s = self.state.borrow();
+		(s.pixmap.w, s.pixmap.h,)
+	}
+
+	/// start over with transparent pixels `w` x `h`
+	pub fn resize(&self, w: u32, h: u32,) {
+		self.state.borrow_mut().pixmap = Pixmap::new(w, h,);
+	}
+
+	/// pixels drawn so far
+	pub fn pixmap(&self,) -> Ref<'_, Pixmap,> {
+		Ref::map(self.state.borrow(), |s| &s.pixmap,)
+	}
+
+	pub fn clear(&self,) {
+		self.state.borrow_mut().pixmap.rgba.fill(0,);
+	}
+
+	/// run `draw` with transform replaced by `transform`
+	pub fn with_transform<O,>(
+		&self,
+		transform: Transform,
+		draw: impl FnOnce() -> Rslt<O,>,
+	) -> Rslt<O,> {
+		{
+			let mut s = self.state.borrow_mut();
+			let saved = (s.transform, s.alpha,);
+			s.saved.push(saved,);
+			s.transform = transform;
+		}
+		let drawn = draw();
+		let mut s = self.state.borrow_mut();
+		if let Some((transform, alpha,),) = s.saved.pop() {
+			s.transform = transform;
+			s.alpha = alpha;
+		}
+		drawn
+	}
+
+	/// draw `clip` of `image` stretched over `dest`, transformed by `params`
+	/// as a canvas would
+	pub fn draw_image(
+		&self,
+		image: &Pixmap,
+		clip: [f64; 4],
+		dest: [f64; 4],
+		params: &DrawParams,
+	) {
+		let [x, y, w, h,] = dest;
+		let (px, py,) = params.pivot.unwrap_or((w / 2.0, h / 2.0,),);
+		let flip = |flipped| if flipped { -1.0 } else { 1.0 };
+		let (sx, sy,) = (
+			params.scale_x * flip(params.flip_x,),
+			params.scale_y * flip(params.flip_y,),
+		);
+		let (sin, cos,) = params.rotation.sin_cos();
+		// translate to pivot, rotate, scale, as `draw_image_with` does
+		let local = [cos * sx, sin * sx, -sin * sy, cos * sy, x + px, y + py,];
+
+		let mut s = self.state.borrow_mut();
+		let m = viewport::compose(s.transform, local,);
+		let alpha = s.alpha * params.alpha;
+		fill_image(&mut s.pixmap, image, clip, [-px, -py, w, h,], m, alpha,);
+	}
+
+	pub fn begin_path(&self,) {
+		self.state.borrow_mut().path.clear();
+	}
+
+	pub fn move_to(&self, x: f64, y: f64,) {
+		let mut s = self.state.borrow_mut();
+		let p = apply(s.transform, (x, y,),);
+		s.path.push(SubPath { points: vec![p], closed: false, },);
+	}
+
+	pub fn line_to(&self, x: f64, y: f64,) {
+		let mut s = self.state.borrow_mut();
+		let p = apply(s.transform, (x, y,),);
+		match s.path.last_mut() {
+			Some(sub,) if !sub.closed => sub.points.push(p,),
+			_ => s.path.push(SubPath { points: vec![p], closed: false, },),
+		}
+	}
+
+	pub fn close_path(&self,) {
+		if let Some(sub,) = self.state.borrow_mut().path.last_mut() {
+			sub.closed = true;
+		}
+	}
+
+	pub fn rect(&self, x: f64, y: f64, w: f64, h: f64,) {
+		self.move_to(x, y,);
+		self.line_to(x + w, y,);
+		self.line_to(x + w, y + h,);
+		self.line_to(x, y + h,);
+		self.close_path();
+	}
+
+	/// clockwise arc, joined to the current point by a straight line
+	pub fn arc(&self, cx: f64, cy: f64, r: f64, start: f64, end: f64,) {
+		let sweep = end - start;
+		let sweep = if sweep >= TAU { TAU } else { sweep.rem_euclid(TAU,) };
+		let steps = ((sweep / TAU * 64.0).ceil() as usize).max(2,);
+		for i in 0..=steps {
+			let a = start + sweep * i as f64 / steps as f64;
+			self.line_to(cx + r * a.cos(), cy + r * a.sin(),);
+		}
+	}
+
+	/// fill and/or stroke current path, like its canvas counterpart
+	pub fn paint_path(
+		&self,
+		fill: Option<Color,>,
+		stroke: Option<Color,>,
+		line_width: f64,
+		dash: &[f64],
+		alpha: f64,
+	) {
+		let mut s = self.state.borrow_mut();
+		let alpha = s.alpha * alpha;
+		if let Some(color,) = fill {
+			let edges = s
+				.path
+				.iter()
+				.flat_map(|sub| edges(&sub.points, true,),)
+				.collect::<Vec<_,>>();
+			fill_polygon(&mut s.pixmap, &edges, color, alpha,);
+		}
+		if let Some(color,) = stroke {
+			let [a, b, c, d, ..,] = s.transform;
+			let scale = (a * d - b * c).abs().sqrt();
+			let half = line_width * scale / 2.0;
+			let dash = dash.iter().map(|d| d * scale,).collect::<Vec<_,>>();
+			let mut quads = vec![];
+			for sub in &s.path {
+				for (p, q,) in dashed(edges(&sub.points, sub.closed,), &dash,) {
+					quads.extend(edges(&quad(p, q, half,), true,),);
+				}
+			}
+			fill_polygon(&mut s.pixmap, &quads, color, alpha,);
+		}
+	}
+}
+
+fn apply([a, b, c, d, e, f,]: Transform, (x, y,): (f64, f64,),) -> (f64, f64,) {
+	(a * x + c * y + e, b * x + d * y + f,)
+}
+
+fn invert([a, b, c, d, e, f,]: Transform,) -> Option<Transform,> {
+	let det = a * d - b * c;
+	if det.abs() < f64::EPSILON {
+		return None;
+	}
+	Some([
+		d / det,
+		-b / det,
+		-c / det,
+		a / det,
+		(c * f - d * e) / det,
+		(b * e - a * f) / det,
+	],)
+}
+
+/// draw `clip` of `image` over `rect` in the space `m` maps onto pixels
+fn fill_image(
+	pixmap: &mut Pixmap,
+	image: &Pixmap,
+	[cx, cy, cw, ch,]: [f64; 4],
+	[rx, ry, rw, rh,]: [f64; 4],
+	m: Transform,
+	alpha: f64,
+) {
+	let Some(inv,) = invert(m,) else {
+		return;
+	};
+	if rw <= 0.0 || rh <= 0.0 {
+		return;
+	}
+	let corners =
+		[(rx, ry,), (rx + rw, ry,), (rx, ry + rh,), (rx + rw, ry + rh,),]
+			.map(|p| apply(m, p,),);
+	let Some((x0, y0, x1, y1,),) =
+		pixel_bounds(pixmap, corners.iter().copied(),)
+	else {
+		return;
+	};
+	for py in y0..y1 {
+		for px in x0..x1 {
+			let (u, v,) = apply(inv, (px as f64 + 0.5, py as f64 + 0.5,),);
+			if u < rx || v < ry || u >= rx + rw || v >= ry + rh {
+				continue;
+			}
+			let sx = (cx + (u - rx) * cw / rw).floor();
+			let sy = (cy + (v - ry) * ch / rh).floor();
+			if sx < 0.0
+				|| sy < 0.0 || sx >= image.w as f64
+				|| sy >= image.h as f64
+			{
+				continue;
+			}
+			let [r, g, b, a,] = image.pixel(sx as u32, sy as u32,);
+			pixmap.blend(px, py, Color::rgba(r, g, b, a,), alpha,);
+		}
+	}
+}
+
+/// pixel rows and columns `points` cover, clipped to `pixmap`
+fn pixel_bounds(
+	pixmap: &Pixmap,
+	points: impl Iterator<Item = (f64, f64,),>,
+) -> Option<(u32, u32, u32, u32,),> {
+	let (mut x0, mut y0, mut x1, mut y1,) =
+		(f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY,);
+	for (x, y,) in points {
+		(x0, y0, x1, y1,) = (x0.min(x,), y0.min(y,), x1.max(x,), y1.max(y,),);
+	}
+	let clamp = |v: f64, max: u32| v.clamp(0.0, max as f64,) as u32;
+	let bounds = (
+		clamp(x0.floor(), pixmap.w,),
+		clamp(y0.floor(), pixmap.h,),
+		clamp(x1.ceil(), pixmap.w,),
+		clamp(y1.ceil(), pixmap.h,),
+	);
+	(bounds.0 < bounds.2 && bounds.1 < bounds.3).then_some(bounds,)
+}
+
+type Edge = ((f64, f64,), (f64, f64,),);
+
+/// segments joining `points`, plus the one back to the first when `closed`
+fn edges(points: &[(f64, f64,)], closed: bool,) -> Vec<Edge,> {
+	let mut out =
+		points.windows(2,).map(|w| (w[0], w[1],),).collect::<Vec<_,>>();
+	if closed && points.len() > 2 {
+		out.push((points[points.len() - 1], points[0],),);
+	}
+	out
+}
+
+/// parts of `edges` drawn by dash pattern `dash`, all of them when empty
+fn dashed(edges: Vec<Edge,>, dash: &[f64],) -> Vec<Edge,> {
+	if dash.is_empty() || dash.iter().sum::<f64>() <= 0.0 {
+		return edges;
+	}
+	// odd patterns repeat twice, as on a canvas
+	let pattern = if dash.len() % 2 == 1 {
+		[dash, dash,].concat()
+	} else {
+		dash.to_vec()
+	};
+	let (mut i, mut left, mut on,) = (0, pattern[0], true,);
+	let mut out = vec![];
+	for ((x0, y0,), (x1, y1,),) in edges {
+		let len = (x1 - x0).hypot(y1 - y0,);
+		let mut at = 0.0;
+		while at < len {
+			let step = left.min(len - at,);
+			if on {
+				let lerp = |t: f64| {
+					(x0 + (x1 - x0) * t / len, y0 + (y1 - y0) * t / len,)
+				};
+				out.push((lerp(at,), lerp(at + step,),),);
+			}
+			at += step;
+			left -= step;
+			if left <= 0.0 {
+				i = (i + 1) % pattern.len();
+				left = pattern[i];
+				on = !on;
+			}
+		}
+	}
+	out
+}
+
+/// rectangle `half` wide on each side of segment `p` to `q`
+fn quad(p: (f64, f64,), q: (f64, f64,), half: f64,) -> [(f64, f64,); 4] {
+	let len = (q.0 - p.0).hypot(q.1 - p.1,);
+	if len == 0.0 {
+		return [p; 4];
+	}
+	let n = (-(q.1 - p.1) / len * half, (q.0 - p.0) / len * half,);
+	[
+		(p.0 + n.0, p.1 + n.1,),
+		(q.0 + n.0, q.1 + n.1,),
+		(q.0 - n.0, q.1 - n.1,),
+		(p.0 - n.0, p.1 - n.1,),
+	]
+}
+
+/// fill the area `edges` enclose by the nonzero rule, sampling pixel
+/// centers
+fn fill_polygon(
+	pixmap: &mut Pixmap,
+	edges: &[Edge],
+	color: Color,
+	alpha: f64,
+) {
+	let Some((x0, y0, x1, y1,),) =
+		pixel_bounds(pixmap, edges.iter().flat_map(|&(p, q,)| [p, q,],),)
+	else {
+		return;
+	};
+	let mut crossings = vec![];
+	for py in y0..y1 {
+		let y = py as f64 + 0.5;
+		crossings.clear();
+		for &((ax, ay,), (bx, by,),) in edges {
+			if (ay <= y) == (by <= y) {
+				continue;
+			}
+			let x = ax + (y - ay) * (bx - ax) / (by - ay);
+			crossings.push((x, if by > ay { 1 } else { -1 },),);
+		}
+		crossings.sort_by(|a, b| a.0.total_cmp(&b.0,),);
+
+		let mut winding = 0;
+		for pair in crossings.windows(2,) {
+			winding += pair[0].1;
+			if winding == 0 {
+				continue;
+			}
+			// pixels whose centers lie between both crossings
+			let from = ((pair[0].0 - 0.5).ceil().max(x0 as f64,)) as u32;
+			let to = ((pair[1].0 - 0.5).ceil().min(x1 as f64,)) as u32;
+			for px in from..to {
+				pixmap.blend(px, py, color, alpha,);
+			}
+		}
+	}
+}
+
+/// result of comparing a frame against its reference
+#[derive(Clone, Debug, PartialEq, Eq,)]
+pub struct Comparison {
+	/// pixels with a channel further than the tolerance from the reference
+	pub mismatched: usize,
+	/// largest channel difference seen
+	pub max_delta:  u8,
+	/// reference faded to grey with mismatched pixels in red
+	pub diff:       Pixmap,
+}
+
+/// compare `actual` with `expected`, allowing each channel to be off by
+/// `tolerance`
+pub fn compare(
+	actual: &Pixmap,
+	expected: &Pixmap,
+	tolerance: u8,
+) -> Rslt<Comparison,> {
+	if (actual.w, actual.h,) != (expected.w, expected.h,) {
+		bail!(
+			"frame is {}x{} but reference is {}x{}",
+			actual.w,
+			actual.h,
+			expected.w,
+			expected.h
+		);
+	}
+	let mut diff = Pixmap::new(actual.w, actual.h,);
+	let (mut mismatched, mut max_delta,) = (0, 0,);
+	for y in 0..actual.h {
+		for x in 0..actual.w {
+			let (a, e,) = (actual.pixel(x, y,), expected.pixel(x, y,),);
+			let delta =
+				(0..4).map(|i| a[i].abs_diff(e[i],),).max().unwrap_or(0,);
+			max_delta = max_delta.max(delta,);
+			if delta > tolerance {
+				mismatched += 1;
+				diff.put(x, y, [255, 0, 0, 255,],);
+			} else {
+				let grey =
+					((e[0] as u32 + e[1] as u32 + e[2] as u32) / 3 / 3) as u8;
+				diff.put(x, y, [grey, grey, grey, 255,],);
+			}
+		}
+	}
+	Ok(Comparison { mismatched, max_delta, diff, },)
+}
+
+/// compare `actual` against the png reference at `path`
+///
+/// a missing reference is recorded from `actual` and reported as an error
+/// so it gets reviewed. set `UPDATE_GOLDEN` to rewrite references instead
+/// of comparing. on mismatch, `<name>.actual.png` and `<name>.diff.png` are
+/// written beside the reference
+#[cfg(not(target_arch = "wasm32"))]
+pub fn assert_golden(
+	path: impl AsRef<std::path::Path,>,
+	actual: &Pixmap,
+	tolerance: u8,
+) -> Rslt<(),> {
+	use crate::engn::atlas;
+
+	let path = path.as_ref();
+	let update = std::env::var_os("UPDATE_GOLDEN",).is_some();
+	if update || !path.exists() {
+		if let Some(dir,) = path.parent() {
+			std::fs::create_dir_all(dir,)?;
+		}
+		atlas::write_png(path, actual.w, actual.h, &actual.rgba,)?;
+		if update {
+			return Ok((),);
+		}
+		bail!("no reference at {}, recorded this frame as one", path.display());
+	}
+
+	let expected = atlas::read_png(path,)?;
+	let expected = Pixmap::from_rgba(expected.w, expected.h, expected.rgba,)?;
+	let cmp = compare(actual, &expected, tolerance,)?;
+	if cmp.mismatched == 0 {
+		return Ok((),);
+	}
+	let beside = |suffix: &str| path.with_extension(format!("{suffix}.png"),);
+	atlas::write_png(&beside("actual",), actual.w, actual.h, &actual.rgba,)?;
+	atlas::write_png(&beside("diff",), cmp.diff.w, cmp.diff.h, &cmp.diff.rgba,)?;
+	bail!(
+		"{} pixels differ from {} by up to {}, see {}",
+		cmp.mismatched,
+		path.display(),
+		cmp.max_delta,
+		beside("diff",).display()
+	)
+}
@@ -0,0 +1,296 @@
+//! grids of tiles cut out of a tileset image, for ground and platforms
+//!
+//! tile ids follow Tiled's convention so its csv exports load as they are.
+//! `0` is an empty cell, any other id is a tile index plus one, with the top
+//! bits carrying flip flags
+
+use crate::brwsr::Rslt;
+use crate::engn::Image;
+use crate::engn::camera::Bounds;
+use anyhow::Context;
+use anyhow::bail;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+const FLIP_X: u32 = 0x8000_0000;
+const FLIP_Y: u32 = 0x4000_0000;
+/// flags Tiled may set beside the two flips, ignored here
+const OTHER_FLAGS: u32 = 0x3000_0000;
+
+/// where tiles sit inside of a tileset image, in pixels
+#[derive(Clone, Copy, Debug, PartialEq, Eq,)]
+pub struct Grid {
+	pub tile_w:  u16,
+	pub tile_h:  u16,
+	/// gap between neighbouring tiles
+	pub spacing: u16,
+	/// gap between the image's edges and the outermost tiles
+	pub margin:  u16,
+	pub columns: u16,
+}
+
+impl Grid {
+	/// top left corner of tile `index` inside of the image
+	pub fn clip(&self, index: u32,) -> (f64, f64,) {
+		let columns = self.columns.max(1,) as u32;
+		let (col, row,) = (index % columns, index / columns,);
+		(
+			(self.margin as u32 + col * (self.tile_w + self.spacing) as u32)
+				as f64,
+			(self.margin as u32 + row * (self.tile_h + self.spacing) as u32)
+				as f64,
+		)
+	}
+
+	/// cell under `x`, `y` of a grid of these tiles placed at `origin`
+	pub fn cell_at(&self, origin: (f64, f64,), x: f64, y: f64,) -> (i64, i64,) {
+		(
+			((x - origin.0) / self.tile_w.max(1,) as f64).floor() as i64,
+			((y - origin.1) / self.tile_h.max(1,) as f64).floor() as i64,
+		)
+	}
+}
+
+/// tile cycling through `frames` indices, `ticks_per_frame` ticks each
+#[derive(Clone, Debug, PartialEq, Eq,)]
+pub struct TileAnimation {
+	pub frames:          Vec<u32,>,
+	pub ticks_per_frame: u32,
+}
+
+impl TileAnimation {
+	/// index shown at simulation tick `tick`
+	pub fn frame_at(&self, tick: u64,) -> Option<u32,> {
+		if self.frames.is_empty() {
+			return None;
+		}
+		let step = tick / self.ticks_per_frame.max(1,) as u64;
+		Some(self.frames[(step % self.frames.len() as u64) as usize],)
+	}
+}
+
+pub struct Tileset {
+	pub(super) image: Image,
+	grid:             Grid,
+	animations:       BTreeMap<u32, TileAnimation,>,
+}
+
+impl Tileset {
+	pub fn new(image: Image, grid: Grid,) -> Self {
+		Self { image, grid, animations: BTreeMap::new(), }
+	}
+
+	pub async fn load(src: &str, grid: Grid,) -> Rslt<Self,> {
+		let image = Image::new().await?;
+		image.load(src,).await?;
+		Ok(Self::new(image, grid,),)
+	}
+
+	pub fn grid(&self,) -> Grid {
+		self.grid
+	}
+
+	/// draw tile `index` as `animation` wherever it is placed
+	pub fn animate(&mut self, index: u32, animation: TileAnimation,) {
+		self.animations.insert(index, animation,);
+	}
+
+	/// index actually drawn for `index` at simulation tick `tick`
+	pub fn resolve(&self, index: u32, tick: u64,) -> u32 {
+		self.animations
+			.get(&index,)
+			.and_then(|anim| anim.frame_at(tick,),)
+			.unwrap_or(index,)
+	}
+}
+
+/// one placed tile
+#[derive(Clone, Copy, Debug, PartialEq, Eq,)]
+pub struct Tile {
+	pub index:  u32,
+	pub flip_x: bool,
+	pub flip_y: bool,
+}
+
+impl Tile {
+	pub fn new(index: u32,) -> Self {
+		Self { index, flip_x: false, flip_y: false, }
+	}
+
+	/// tile a Tiled global id stands for, `None` for an empty cell
+	pub fn from_gid(gid: u32,) -> Option<Self,> {
+		let index = gid & !(FLIP_X | FLIP_Y | OTHER_FLAGS);
+		if index == 0 {
+			return None;
+		}
+		Some(Self {
+			index:  index - 1,
+			flip_x: gid & FLIP_X != 0,
+			flip_y: gid & FLIP_Y != 0,
+		},)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq,)]
+pub struct TileLayer {
+	pub name: String,
+	width:    u32,
+	height:   u32,
+	/// row major
+	tiles:    Vec<Option<Tile,>,>,
+}
+
+impl TileLayer {
+	pub fn new(name: impl Into<String,>, width: u32, height: u32,) -> Self {
+		Self {
+			name: name.into(),
+			width,
+			height,
+			tiles: vec![None; (width * height) as usize],
+		}
+	}
+
+	/// layer out of comma separated tile ids, one row per line
+	pub fn from_csv(name: impl Into<String,>, csv: &str,) -> Rslt<Self,> {
+		let mut tiles = vec![];
+		let mut width = None;
+		let mut height = 0;
+		for (i, line,) in csv.lines().map(str::trim,).enumerate() {
+			if line.is_empty() {
+				continue;
+			}
+			let row = line
+				.trim_end_matches(',',)
+				.split(',',)
+				.map(|id| {
+					id.trim().parse::<u32>().with_context(|| {
+						format!("bad tile id `{id}` on line {}", i + 1)
+					},)
+				},)
+				.collect::<Rslt<Vec<_,>,>>()?;
+			match width {
+				None => width = Some(row.len() as u32,),
+				Some(w,) if w as usize != row.len() => bail!(
+					"line {} has {} tiles where the first one has {w}",
+					i + 1,
+					row.len()
+				),
+				_ => {},
+			}
+			tiles.extend(row.into_iter().map(Tile::from_gid,),);
+			height += 1;
+		}
+		Ok(Self {
+			name: name.into(),
+			width: width.unwrap_or(0,),
+			height,
+			tiles,
+		},)
+	}
+
+	pub fn width(&self,) -> u32 {
+		self.width
+	}
+
+	pub fn height(&self,) -> u32 {
+		self.height
+	}
+
+	pub fn get(&self, col: u32, row: u32,) -> Option<Tile,> {
+		if col >= self.width || row >= self.height {
+			return None;
+		}
+		self.tiles[(row * self.width + col) as usize]
+	}
+
+	/// columns and rows overlapping `view`, with the layer placed at `origin`
+	/// and tiles `grid` large
+	pub fn visible_cells(
+		&self,
+		grid: &Grid,
+		origin: (f64, f64,),
+		view: &Bounds,
+	) -> (Range<u32,>, Range<u32,>,) {
+		let (left, top,) = grid.cell_at(origin, view.x, view.y,);
+		let (right, bottom,) =
+			grid.cell_at(origin, view.x + view.w, view.y + view.h,);
+		let clamp = |v: i64, len: u32| v.clamp(0, len as i64,) as u32;
+		(
+			clamp(left, self.width,)..clamp(right + 1, self.width,),
+			clamp(top, self.height,)..clamp(bottom + 1, self.height,),
+		)
+	}
+
+	/// place `tile` at `col`, `row`. ignored outside of the layer
+	pub fn set(&mut self, col: u32, row: u32, tile: Option<Tile,>,) {
+		if col < self.width && row < self.height {
+			self.tiles[(row * self.width + col) as usize] = tile;
+		}
+	}
+}
+
+pub struct Tilemap {
+	pub tileset: Tileset,
+	/// world position of the top left corner of every layer
+	pub origin:  (f64, f64,),
+	layers:      Vec<TileLayer,>,
+	/// simulation ticks so far, driving animated tiles
+	tick:        u64,
+}
+
+impl Tilemap {
+	pub fn new(tileset: Tileset,) -> Self {
+		Self { tileset, origin: (0.0, 0.0,), layers: vec![], tick: 0, }
+	}
+
+	/// add a layer drawn over those added before it, returning its index
+	pub fn add_layer(&mut self, layer: TileLayer,) -> usize {
+		self.layers.push(layer,);
+		self.layers.len() - 1
+	}
+
+	pub fn layers(&self,) -> &[TileLayer] {
+		&self.layers
+	}
+
+	pub fn layer_mut(&mut self, index: usize,) -> Option<&mut TileLayer,> {
+		self.layers.get_mut(index,)
+	}
+
+	/// advance animated tiles one simulation tick
+	pub fn update(&mut self,) {
+		self.tick += 1;
+	}
+
+	pub fn tick(&self,) -> u64 {
+		self.tick
+	}
+
+	/// world area layer `index` spans
+	pub fn bounds(&self, index: usize,) -> Option<Bounds,> {
+		let layer = self.layers.get(index,)?;
+		let grid = self.tileset.grid;
+		Some(Bounds {
+			x: self.origin.0,
+			y: self.origin.1,
+			w: layer.width as f64 * grid.tile_w as f64,
+			h: layer.height as f64 * grid.tile_h as f64,
+		},)
+	}
+
+	/// cell under world position `x`, `y`
+	pub fn cell_at(&self, x: f64, y: f64,) -> (i64, i64,) {
+		self.tileset.grid.cell_at(self.origin, x, y,)
+	}
+
+	/// columns and rows of layer `index` overlapping `view`
+	pub fn visible_cells(
+		&self,
+		index: usize,
+		view: &Bounds,
+	) -> (Range<u32,>, Range<u32,>,) {
+		self.layers.get(index,).map_or((0..0, 0..0,), |layer| {
+			layer.visible_cells(&self.tileset.grid, self.origin, view,)
+		},)
+	}
+}
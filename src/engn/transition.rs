@@ -0,0 +1,132 @@
+//! animated cover drawn over the screen while one scene is swapped for
+//! another
+//!
+//! a transition first covers the screen, then uncovers it again. the scene
+//! underneath is swapped on the tick it becomes fully covered
+
+use crate::brwsr::Rslt;
+use crate::engn::Renderer;
+use crate::engn::color::Color;
+use crate::engn::shape::Shape;
+use crate::engn::shape::ShapeStyle;
+
+/// edge a wipe starts from
+#[derive(Clone, Copy, Debug, PartialEq, Eq,)]
+pub enum Edge {
+	Left,
+	Right,
+	Top,
+	Bottom,
+}
+
+#[derive(Clone, Debug, PartialEq,)]
+pub enum Style {
+	/// whole screen fades into `color`
+	Fade { color: Color, },
+	/// `color` slides in from `from`, then slides out of the opposite edge
+	Wipe { color: Color, from: Edge, },
+	/// circle around `center`, in logical pixels, shrinks to nothing, then
+	/// grows back
+	Iris { color: Color, center: (f64, f64,), },
+}
+
+#[derive(Clone, Debug, PartialEq,)]
+pub struct Transition {
+	style: Style,
+	/// ticks each half takes
+	ticks: u32,
+	age:   u32,
+}
+
+impl Transition {
+	/// cover the screen over `ticks`, then uncover it over as many
+	pub fn new(style: Style, ticks: u32,) -> Self {
+		Self { style, ticks: ticks.max(1,), age: 0, }
+	}
+
+	/// start fully covered and only uncover, as when a game boots
+	pub fn reveal(style: Style, ticks: u32,) -> Self {
+		let ticks = ticks.max(1,);
+		Self { style, ticks, age: ticks, }
+	}
+
+	/// advance one tick. returns `true` on the tick the screen becomes fully
+	/// covered, which is when scenes should be swapped
+	pub fn update(&mut self,) -> bool {
+		if self.is_done() {
+			return false;
+		}
+		self.age += 1;
+		self.age == self.ticks
+	}
+
+	pub fn is_done(&self,) -> bool {
+		self.age >= self.ticks * 2
+	}
+
+	/// whether the screen is still being covered
+	pub fn is_covering(&self,) -> bool {
+		self.age < self.ticks
+	}
+
+	/// fraction of the screen hidden, rising to `1.0` and falling back to
+	/// `0.0`
+	pub fn coverage(&self,) -> f64 {
+		let t = self.age.min(self.ticks * 2,) as f64 / self.ticks as f64;
+		if t <= 1.0 { t } else { 2.0 - t }
+	}
+
+	pub fn draw(&self, rndrr: &Renderer,) -> Rslt<(),> {
+		let coverage = self.coverage();
+		if coverage <= 0.0 {
+			return Ok((),);
+		}
+		let (w, h,) = rndrr.logical_size();
+		let (shape, style,) = match &self.style {
+			Style::Fade { color, } => (
+				Shape::Rect { x: 0.0, y: 0.0, w, h, },
+				ShapeStyle { alpha: coverage, ..ShapeStyle::fill(*color,) },
+			),
+			Style::Wipe { color, from, } => {
+				(self.wipe_rect(*from, w, h,), ShapeStyle::fill(*color,),)
+			},
+			Style::Iris { color, center: (cx, cy,), } => {
+				// a stroke as wide as the screen's diagonal covers everything
+				// outside of the opening
+				let reach = w.hypot(h,) * 2.0;
+				let opening = reach / 2.0 * (1.0 - coverage);
+				(
+					Shape::Circle {
+						cx: *cx,
+						cy: *cy,
+						r:  opening + reach / 2.0,
+					},
+					ShapeStyle::stroke(*color, reach,),
+				)
+			},
+		};
+		rndrr.screen_space(|r| r.draw_shape(&shape, &style,),)
+	}
+
+	/// area a wipe covers. it grows from `from` while covering, then shrinks
+	/// toward the opposite edge
+	fn wipe_rect(&self, from: Edge, w: f64, h: f64,) -> Shape {
+		let c = self.coverage();
+		// covering grows from the leading edge, uncovering leaves from it
+		let lead = self.is_covering();
+		match (from, lead,) {
+			(Edge::Left, true,) | (Edge::Right, false,) => {
+				Shape::Rect { x: 0.0, y: 0.0, w: w * c, h, }
+			},
+			(Edge::Right, true,) | (Edge::Left, false,) => {
+				Shape::Rect { x: w * (1.0 - c), y: 0.0, w: w * c, h, }
+			},
+			(Edge::Top, true,) | (Edge::Bottom, false,) => {
+				Shape::Rect { x: 0.0, y: 0.0, w, h: h * c, }
+			},
+			(Edge::Bottom, true,) | (Edge::Top, false,) => {
+				Shape::Rect { x: 0.0, y: h * (1.0 - c), w, h: h * c, }
+			},
+		}
+	}
+}
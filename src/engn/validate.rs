@@ -0,0 +1,105 @@
+//! static checks of sprite sheets, catching what would otherwise only fail
+//! while drawing
+
+use crate::engn::Sheet;
+use crate::engn::Size;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+/// animation played by cycling frames named `{name} (1).png` to
+/// `{name} ({cards}).png`
+#[derive(Clone, Copy, Debug,)]
+pub struct Clip {
+	pub name:  &'static str,
+	pub cards: u8,
+}
+
+#[derive(Debug, PartialEq, Eq,)]
+pub enum Problem {
+	OutOfBounds { frame: String, },
+	SizeMismatch { meta: Size, image: Size, },
+	MissingCards { clip: &'static str, cards: Vec<u8,>, },
+	Gap { clip: &'static str, after: u8, before: u8, },
+}
+
+impl Display for Problem {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_,>,) -> std::fmt::Result {
+		match self {
+			Self::OutOfBounds { frame, } => {
+				write!(f, "{frame} lies outside of the image")
+			},
+			Self::SizeMismatch { meta, image, } => write!(
+				f,
+				"sheet says image is {}x{} but it is {}x{}",
+				meta.w, meta.h, image.w, image.h
+			),
+			Self::MissingCards { clip, cards, } => {
+				write!(f, "{clip} is missing cards {cards:?}")
+			},
+			Self::Gap { clip, after, before, } => {
+				write!(f, "{clip} has no cards between {after} and {before}")
+			},
+		}
+	}
+}
+
+/// every problem of `sheet` drawn from an image of `image` size
+pub fn validate(sheet: &Sheet, image: Size, clips: &[Clip],) -> Vec<Problem,> {
+	let mut problems = vec![];
+
+	if let Some(meta,) = sheet.meta()
+		&& meta.size != image
+	{
+		problems.push(Problem::SizeMismatch { meta: meta.size, image, },);
+	}
+
+	for (name, sprite,) in sheet.iter() {
+		let r = sprite.frame();
+		if r.x as u32 + r.w as u32 > image.w as u32
+			|| r.y as u32 + r.h as u32 > image.h as u32
+		{
+			problems.push(Problem::OutOfBounds { frame: name.clone(), },);
+		}
+	}
+
+	for clip in clips {
+		let cards: BTreeSet<u8,> = sheet
+			.iter()
+			.filter_map(|(name, _,)| card_of(name, clip.name,),)
+			.collect();
+
+		let missing: Vec<u8,> =
+			(1..=clip.cards).filter(|c| !cards.contains(c,),).collect();
+		if !missing.is_empty() {
+			problems.push(Problem::MissingCards {
+				clip:  clip.name,
+				cards: missing,
+			},);
+		}
+
+		// cards past the ones the game plays must still follow on
+		let extra = cards.range(clip.cards.saturating_add(1,)..,);
+		let mut prev = clip.cards;
+		for &card in extra {
+			if card != prev + 1 {
+				problems.push(Problem::Gap {
+					clip:   clip.name,
+					after:  prev,
+					before: card,
+				},);
+			}
+			prev = card;
+		}
+	}
+
+	problems
+}
+
+/// card number of frame `name` if it belongs to `clip`
+fn card_of(name: &str, clip: &str,) -> Option<u8,> {
+	name.strip_prefix(clip,)?
+		.strip_prefix(" (",)?
+		.strip_suffix(").png",)?
+		.parse()
+		.ok()
+}
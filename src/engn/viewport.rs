@@ -0,0 +1,97 @@
+//! fitting the logical resolution the game draws at onto the canvas
+
+/// canvas `setTransform` arguments `[a, b, c, d, e, f]`
+pub type Transform = [f64; 6];
+
+/// how a fixed logical resolution is stretched over the canvas
+#[derive(Clone, Copy, Debug, PartialEq, Eq,)]
+pub enum ScalePolicy {
+	/// largest uniform scale that fits, leaving bars on the sides
+	Letterbox,
+	/// fill the whole canvas, distorting the aspect ratio
+	Stretch,
+	/// largest whole number scale that fits, so pixel art stays crisp
+	PixelPerfect,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq,)]
+pub struct Viewport {
+	/// resolution game draws at. follows the canvas' css size when `None`
+	pub logical: Option<(f64, f64,),>,
+	pub policy:  ScalePolicy,
+}
+
+/// result of fitting a [`Viewport`] onto a canvas
+#[derive(Clone, Copy, Debug, PartialEq,)]
+pub struct Fit {
+	/// size canvas' backing store has to be
+	pub backing:   (u32, u32,),
+	/// resolution drawn at
+	pub logical:   (f64, f64,),
+	/// maps logical units onto backing store pixels
+	pub transform: Transform,
+}
+
+impl Viewport {
+	/// draw at the canvas' own css resolution
+	pub fn native() -> Self {
+		Self { logical: None, policy: ScalePolicy::Letterbox, }
+	}
+
+	pub fn fixed(w: f64, h: f64, policy: ScalePolicy,) -> Self {
+		Self { logical: Some((w, h,),), policy, }
+	}
+
+	/// fit onto a canvas of `css_w` x `css_h` css pixels on a screen with
+	/// `dpr` device pixels per css pixel
+	pub fn fit(&self, css_w: f64, css_h: f64, dpr: f64,) -> Fit {
+		let dpr = if dpr > 0.0 { dpr } else { 1.0 };
+		let backing = (
+			(css_w * dpr).round().max(1.0,) as u32,
+			(css_h * dpr).round().max(1.0,) as u32,
+		);
+		let (bw, bh,) = (backing.0 as f64, backing.1 as f64,);
+
+		let Some((lw, lh,),) = self.logical else {
+			return Fit {
+				backing,
+				logical: (css_w, css_h,),
+				transform: [dpr, 0.0, 0.0, dpr, 0.0, 0.0,],
+			};
+		};
+
+		let (sx, sy,) = match self.policy {
+			ScalePolicy::Stretch => (bw / lw, bh / lh,),
+			ScalePolicy::Letterbox => {
+				let s = (bw / lw).min(bh / lh,);
+				(s, s,)
+			},
+			ScalePolicy::PixelPerfect => {
+				let s = (bw / lw).min(bh / lh,).floor().max(1.0,);
+				(s, s,)
+			},
+		};
+		let ox = ((bw - lw * sx) / 2.0).round();
+		let oy = ((bh - lh * sy) / 2.0).round();
+
+		Fit {
+			backing,
+			logical: (lw, lh,),
+			transform: [sx, 0.0, 0.0, sy, ox, oy,],
+		}
+	}
+}
+
+/// transform applying `inner` first, then `outer`
+pub fn compose(outer: Transform, inner: Transform,) -> Transform {
+	let [a1, b1, c1, d1, e1, f1,] = outer;
+	let [a2, b2, c2, d2, e2, f2,] = inner;
+	[
+		a1 * a2 + c1 * b2,
+		b1 * a2 + d1 * b2,
+		a1 * c2 + c1 * d2,
+		b1 * c2 + d1 * d2,
+		a1 * e2 + c1 * f2 + e1,
+		b1 * e2 + d1 * f2 + f1,
+	]
+}
@@ -1,94 +1,1133 @@
 use crate::Rslt;
+use crate::brwsr;
+use crate::brwsr::Fetch;
+use crate::brwsr::storage::Storage;
+use crate::engn::DrawParams;
 use crate::engn::Game;
 use crate::engn::Image;
 use crate::engn::KeyboardState;
 use crate::engn::Point;
 use crate::engn::Renderer;
+use crate::engn::cache::CachedLayer;
+use crate::engn::camera::Bounds;
+use crate::engn::color::Color;
+use crate::engn::debug::DebugOverlay;
+use crate::engn::debug::EntityInfo;
+use crate::engn::effects::CameraEffects;
+use crate::engn::entity::Components;
+use crate::engn::entity::Entities;
+use crate::engn::entity::Entity;
+use crate::engn::entity::Schedule;
+use crate::engn::font::Align;
+use crate::engn::particle::Curve;
+use crate::engn::particle::Emitter;
+use crate::engn::particle::KindId;
+use crate::engn::particle::Look;
+use crate::engn::particle::ParticleKind;
+use crate::engn::particle::ParticleSystem;
+use crate::engn::queue;
+use crate::engn::queue::Layer;
+use crate::engn::queue::RenderQueue;
+use crate::engn::rope::Rope;
+use crate::engn::scene::Change;
+use crate::engn::scene::Input;
+use crate::engn::scene::Scene;
+use crate::engn::scene::SceneSnapshot;
+use crate::engn::scene::SceneStack;
+use crate::engn::shape::Shape;
+use crate::engn::shape::ShapeStyle;
+use crate::engn::shape::Vertex;
+use crate::engn::transition;
+use crate::engn::transition::Transition;
+use crate::engn::validate::Clip;
+use crate::engn::viewport::ScalePolicy;
+use crate::engn::viewport::Viewport;
+use crate::game::difficulty::DIFFICULTY_FILE;
+use crate::game::difficulty::Director;
+use crate::game::difficulty::RELOAD_KEY;
+use crate::game::dog::Dog;
+use crate::game::dog::Follow;
+use crate::game::dog::Leader;
 use crate::game::red_hat_boy_states::Idle;
+use crate::game::red_hat_boy_states::Jumping;
 use crate::game::red_hat_boy_states::RedHatBoyState;
 use crate::game::red_hat_boy_states::Running;
+use crate::game::save::Bindings;
+use crate::game::scenes::GameOver;
+use crate::game::scenes::Pause;
+use crate::game::scenes::Title;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::rc::Rc;
 
+mod difficulty;
+mod dog;
+mod dog_states;
 mod red_hat_boy_states;
+mod save;
+mod scenes;
+mod score;
+
+pub use difficulty::Difficulty;
+pub use difficulty::DifficultyCurves;
+pub use dog::DOG_CLIPS;
+pub use save::HighScore;
+pub use save::Save;
+pub use score::Score;
 
 /// count of idle cards
 const IDLE_CARDS: u8 = 10;
 /// count of running cards
 const RUN_CARDS: u8 = 8;
-const WALK_SPEED: i16 = 3;
+/// count of jumping cards
+const JUMP_CARDS: u8 = 12;
+/// width of the world boy walks around in
+const WORLD_WIDTH: f64 = 2400.0;
+/// height of the world boy walks around in
+const WORLD_HEIGHT: f64 = 600.0;
+/// logical resolution the game is drawn at, whatever the canvas size is
+const VIEW_WIDTH: f64 = 600.0;
+const VIEW_HEIGHT: f64 = 600.0;
+/// offset from boy's position to the middle of his sprite
+const RHB_CENTER: Point = Point { x: 80, y: 68, };
+/// offset from boy's position to where his feet touch the ground
+const RHB_FEET: Point = Point { x: 80, y: 130, };
+/// offset from boy's position to the hand holding the leash, facing right
+const RHB_HAND: Point = Point { x: 66, y: 90, };
+/// most particles alive at once
+const PARTICLE_CAPACITY: usize = 256;
+/// dust particles thrown up by boy landing from a jump
+const LANDING_DUST: u32 = 12;
+/// ticks the screen takes to fade in once the game is ready
+const FADE_IN_TICKS: u32 = 30;
+/// heroes the player gets each game
+const LIVES: u8 = 3;
+/// x of the first pickup, with the rest spaced out to the end of the world
+const PICKUP_START: f64 = 360.0;
+const PICKUP_SPACING: f64 = 150.0;
+/// height of the pickups' centers, around boy's middle
+const PICKUP_HEIGHT: f64 = 530.0;
+const PICKUP_RADIUS: f64 = 10.0;
+/// color of the hud's text
+const HUD_COLOR: Color = Color::MIDNIGHTBLUE;
+/// longest the leash lets boy and his dog get apart
+const LEASH_LENGTH: f64 = 120.0;
+/// straight pieces the leash is drawn with
+const LEASH_SEGMENTS: usize = 12;
+/// boy and his dog pull each other along the leash in inverse proportion
+/// to these
+const BOY_MASS: f64 = 2.0;
+const DOG_MASS: f64 = 1.0;
+/// how far ahead of boy the dog smells a pickup and runs after it
+const SNIFF: i16 = 220;
+/// path to the dog's sprite sheet
+const DOG_SHEET: &str = "dog.png";
+/// path to the dog's sprite sheet mapper
+const DOG_SHEET_MAPPER: &str = "dog.json";
+
+/// every animation clip drawn from the sprite sheet
+pub const ANIMATION_CLIPS: &[Clip] = &[
+	Clip { name: "Idle", cards: IDLE_CARDS, },
+	Clip { name: "Run", cards: RUN_CARDS, },
+	Clip { name: "Jump", cards: JUMP_CARDS, },
+];
+
+/// clips played from each sprite sheet, by the name of its image
+pub const SHEET_CLIPS: &[(&str, &[Clip],)] =
+	&[("rhb.png", ANIMATION_CLIPS,), ("dog.png", DOG_CLIPS,),];
+
+/// what every scene shares
+pub struct Shared {
+	renderer:  Renderer,
+	/// boy's sprite sheet
+	sheet:     Rc<Image,>,
+	dog_sheet: Rc<Image,>,
+	/// curves runs get harder along
+	curves:    Rc<DifficultyCurves,>,
+	/// curves fetched again, waiting to be played along
+	fetched:   Rc<RefCell<Option<DifficultyCurves,>,>,>,
+	storage:   Storage,
+	/// high scores and settings, as last stored
+	save:      Save,
+}
+
+impl Shared {
+	/// write the save back to storage
+	fn persist(&self,) {
+		if let Err(e,) = self.save.store(&self.storage,) {
+			log!("failed to store save: {e:?}");
+		}
+	}
+}
+
+impl AsRef<Renderer,> for Shared {
+	fn as_ref(&self,) -> &Renderer {
+		&self.renderer
+	}
+}
 
 pub struct WalkTheDog {
-	renderer: Option<Renderer,>,
-	rhb:      Option<RedHatBoy,>,
+	shared:      Option<Shared,>,
+	scenes:      SceneStack<Shared,>,
+	/// reload key was down last tick
+	reload_held: bool,
 }
 
 impl WalkTheDog {
 	pub fn new() -> Self {
-		Self { renderer: None, rhb: None, }
+		Self {
+			shared:      None,
+			scenes:      SceneStack::new(),
+			reload_held: false,
+		}
+	}
+
+	/// renderer drawn through, once set up
+	pub fn renderer(&self,) -> Option<&Renderer,> {
+		self.shared.as_ref().map(|s| &s.renderer,)
+	}
+
+	/// entities of every visible scene, as the debug overlay shows them
+	pub fn entities(&self,) -> Vec<EntityInfo,> {
+		self.scenes.entities()
+	}
+
+	/// `(name, value)` of what visible scenes tune, as the debug overlay
+	/// shows them
+	pub fn tuning(&self,) -> Vec<(String, String,),> {
+		self.scenes.tuning()
+	}
+
+	/// play along `curves`, from where the run in progress has got to
+	pub fn set_difficulty(&mut self, curves: DifficultyCurves,) -> Rslt<(),> {
+		curves.check()?;
+		if let Some(shared,) = self.shared.as_mut() {
+			shared.curves = Rc::new(curves,);
+		}
+		Ok((),)
+	}
+
+	/// names of the scenes on the stack from bottom to top
+	pub fn scene_names(&self,) -> Vec<&str,> {
+		self.scenes.names()
+	}
+
+	/// json of the run being played, to pick it back up later with
+	/// [`WalkTheDog::restore`] or attach to a bug report
+	pub fn snapshot(&self,) -> Rslt<Option<String,>,> {
+		match self.scenes.snapshot()? {
+			Some(snapshot,) => Ok(Some(serde_json::to_string(&snapshot,)?,),),
+			None => Ok(None,),
+		}
+	}
+
+	/// pick up the run `json` was taken from in place of whatever is on
+	/// screen, transitions included. a snapshot that fails to restore
+	/// leaves the game as it was
+	pub fn restore(&mut self, json: &str,) -> Rslt<(),> {
+		let Some(shared,) = self.shared.as_mut() else {
+			bail!("game is not set up yet");
+		};
+		let snapshot: SceneSnapshot = serde_json::from_str(json,)?;
+		if snapshot.scene != "Play" {
+			bail!("no {} scene keeps a run to restore", snapshot.scene);
+		}
+		let mut play = Play::new(shared,);
+		play.restore(shared, snapshot.state,)?;
+		self.scenes.cancel_transition();
+		self.scenes.apply(shared, Change::reset(play,),)
+	}
+
+	/// get ready to play with `renderer`, the boy's sprite sheet `image` and
+	/// the dog's `dog` loaded already, keeping the save in `storage`. this
+	/// is all of [`Game::init`] but loading, so a software renderer can
+	/// drive the game without a browser
+	pub fn setup(
+		&mut self,
+		mut renderer: Renderer,
+		image: Image,
+		dog: Image,
+		storage: Storage,
+	) -> Rslt<(),> {
+		renderer.set_viewport(Viewport::fixed(
+			VIEW_WIDTH,
+			VIEW_HEIGHT,
+			ScalePolicy::Letterbox,
+		),)?;
+		// a save which can not be read is started over rather than keeping
+		// the game from starting
+		let save = Save::load(&storage,).unwrap_or_else(|e| {
+			log!("failed to load save, starting over: {e:?}");
+			Save::default()
+		},);
+		let mut shared = Shared {
+			renderer,
+			sheet: Rc::new(image,),
+			dog_sheet: Rc::new(dog,),
+			curves: Rc::new(DifficultyCurves::builtin(),),
+			fetched: Rc::default(),
+			storage,
+			save,
+		};
+		self.scenes.apply(&mut shared, Change::push(Title::new(),),)?;
+		self.scenes.play(Transition::reveal(
+			transition::Style::Fade { color: Color::BLACK, },
+			FADE_IN_TICKS,
+		),);
+		self.shared = Some(shared,);
+		Ok((),)
+	}
+}
+
+impl Default for WalkTheDog {
+	fn default() -> Self {
+		Self::new()
 	}
 }
 
 impl Game for WalkTheDog {
 	async fn init(&mut self,) -> Rslt<(),> {
-		self.renderer = Some(Renderer::new("game_canvas",).await?,);
-
+		let renderer = Renderer::new("game_canvas",).await?;
 		let image = Image::new_sprite_sheet().await?;
-
-		self.rhb = Some(RedHatBoy::new(image,)?,);
+		let dog =
+			Image::load_sprite_sheet(DOG_SHEET, DOG_SHEET_MAPPER,).await?;
+		self.setup(renderer, image, dog, Storage::local(),)?;
+		// curves fetched rather than built in can be tuned by editing the
+		// file and pressing the reload key
+		if let Some(curves,) = fetch_difficulty().await {
+			self.set_difficulty(curves,)?;
+		}
 		Ok((),)
 	}
 
 	fn update(&mut self, kb_state: &KeyboardState,) {
-		let Some(ref mut rhb,) = self.rhb else {
+		let Some(shared,) = self.shared.as_mut() else {
 			return;
 		};
-
-		rhb.update(kb_state,);
+		let reload = kb_state.is_pressed(RELOAD_KEY,);
+		// software renderers run outside a browser, with nothing to fetch from
+		if reload && !self.reload_held && !shared.renderer.is_software() {
+			let fetched = shared.fetched.clone();
+			brwsr::spawn_local(async move {
+				if let Some(curves,) = fetch_difficulty().await {
+					*fetched.borrow_mut() = Some(curves,);
+				}
+			},);
+		}
+		self.reload_held = reload;
+		if let Some(curves,) = shared.fetched.take() {
+			shared.curves = Rc::new(curves,);
+		}
+		if let Err(e,) = self.scenes.update(shared, kb_state,) {
+			log!("failed to update scenes: {e:?}");
+		}
 	}
 
 	fn draw(&self,) {
-		let Some(ref rndrr,) = self.renderer else {
+		let Some(shared,) = self.shared.as_ref() else {
+			return;
+		};
+		shared.renderer.clear();
+		self.scenes.draw(shared,).expect("error happen while drawing frame",);
+	}
+
+	fn draw_debug(&self, overlay: &DebugOverlay,) {
+		let Some(shared,) = self.shared.as_ref() else {
+			return;
+		};
+		if let Err(e,) = overlay.draw(
+			&shared.renderer,
+			&self.scenes.entities(),
+			&self.scenes.tuning(),
+		) {
+			log!("failed to draw debug overlay: {e:?}");
+		}
+	}
+}
+
+/// curves in [`DIFFICULTY_FILE`], unless they could not be fetched or can
+/// not be played along
+async fn fetch_difficulty() -> Option<DifficultyCurves,> {
+	let fetched = match brwsr::window_obj() {
+		Ok(window,) => {
+			window.fetch_json_de::<DifficultyCurves>(DIFFICULTY_FILE,).await
+		},
+		Err(e,) => Err(e,),
+	};
+	fetched
+		.and_then(|curves| curves.check().map(|()| curves,),)
+		.inspect_err(|e| log!("failed to fetch difficulty curves: {e:?}"),)
+		.ok()
+}
+
+/// everything walking around the world, keyed by entity
+struct World {
+	entities:  Entities,
+	heroes:    Components<RedHatBoy,>,
+	/// particles kicked up from an entity's feet
+	emitters:  Components<Emitter,>,
+	pickups:   Components<Pickup,>,
+	/// dogs following the player around
+	dogs:      Components<Dog,>,
+	/// leash tied to each dog
+	leashes:   Components<Leash,>,
+	particles: ParticleSystem,
+	/// hero steered by the keyboard and followed by the camera
+	player:    Entity,
+	/// what happened to the player this tick, for the dogs to react to
+	events:    Vec<BoyEvent,>,
+	/// heroes the player has left, the one playing included
+	lives:     u8,
+	score:     Score,
+	director:  Director,
+	/// best score of the games before this one
+	best:      u64,
+	bindings:  Bindings,
+	/// hud made to stand out against anything behind it
+	contrast:  bool,
+	sheet:     Rc<Image,>,
+	dog_sheet: Rc<Image,>,
+	dust:      KindId,
+}
+
+impl World {
+	fn new(shared: &Shared,) -> Self {
+		let mut particles = ParticleSystem::new(PARTICLE_CAPACITY, 0x5eed,);
+		let dust = particles.add_kind(dust(),);
+		let mut entities = Entities::new();
+		let player = entities.spawn();
+		let mut world = Self {
+			entities,
+			heroes: Components::new(),
+			emitters: Components::new(),
+			pickups: Components::new(),
+			dogs: Components::new(),
+			leashes: Components::new(),
+			particles,
+			player,
+			events: vec![],
+			lives: LIVES,
+			score: Score::new(),
+			director: Director::new(shared.curves.clone(),),
+			best: shared.save.best(),
+			bindings: shared.save.settings.bindings.clone(),
+			contrast: shared.save.settings.accessibility.high_contrast,
+			sheet: shared.sheet.clone(),
+			dog_sheet: shared.dog_sheet.clone(),
+			dust,
+		};
+		world.make_hero(player,);
+		let dog = world.entities.spawn();
+		if let Some(rhb,) = world.heroes.get(player,) {
+			let mut companion = Dog::new(rhb.feet_point(), Follow::default(),);
+			companion.heel(rhb.leader(),);
+			world.dogs.insert(dog, companion,);
+		}
+		world.leashes.insert(
+			dog,
+			Leash { holder: player, rope: Rope::new(LEASH_LENGTH,), },
+		);
+
+		let mut x = PICKUP_START;
+		while x < WORLD_WIDTH - PICKUP_SPACING {
+			let pickup = world.entities.spawn();
+			world.pickups.insert(pickup, Pickup { pos: (x, PICKUP_HEIGHT,), },);
+			x += PICKUP_SPACING;
+		}
+		world
+	}
+
+	/// make `entity` a hero standing at the start of the world
+	fn make_hero(&mut self, entity: Entity,) {
+		self.heroes.insert(entity, RedHatBoy::new(),);
+		self.emitters.insert(entity, Emitter::new(self.dust, 0.4,),);
+	}
+
+	/// where the camera looks, while the player is alive
+	fn focus(&self,) -> Option<Point,> {
+		let rhb = self.heroes.get(self.player,)?;
+		Some(rhb.state_machine.context().pos + RHB_CENTER,)
+	}
+}
+
+/// everything simulated during play, which is enough to pick a run back up
+/// exactly where it was
+#[derive(Clone, Serialize, Deserialize,)]
+struct PlayState {
+	entities:  Entities,
+	heroes:    Components<RedHatBoy,>,
+	emitters:  Components<Emitter,>,
+	pickups:   Components<Pickup,>,
+	/// none in snapshots taken before there were dogs
+	#[serde(default)]
+	dogs:      Components<Dog,>,
+	/// none in snapshots taken before there were leashes
+	#[serde(default)]
+	leashes:   Components<Leash,>,
+	particles: ParticleSystem,
+	player:    Entity,
+	lives:     u8,
+	score:     Score,
+	fx:        CameraEffects,
+}
+
+/// rope from a hero's hand to a dog's collar
+#[derive(Clone, Serialize, Deserialize,)]
+struct Leash {
+	/// hero holding the leash
+	holder: Entity,
+	rope:   Rope,
+}
+
+/// coin waiting to be picked up
+#[derive(Clone, Serialize, Deserialize,)]
+struct Pickup {
+	/// center in world units
+	pos: (f64, f64,),
+}
+
+impl Pickup {
+	fn bounds(&self,) -> Bounds {
+		let (x, y,) = self.pos;
+		Bounds {
+			x: x - PICKUP_RADIUS,
+			y: y - PICKUP_RADIUS,
+			w: PICKUP_RADIUS * 2.0,
+			h: PICKUP_RADIUS * 2.0,
+		}
+	}
+}
+
+/// what can happen to boy that his dog cares about
+#[derive(Clone, Copy, Debug, PartialEq, Eq,)]
+enum BoyEvent {
+	Jumped,
+	/// a life lost
+	Hurt,
+}
+
+/// player's hero follows the keys held at the pace the difficulty sets, and
+/// is scored for the walk. he throws up a puff of dust where he lands
+fn steer(world: &mut World, kb_state: &KeyboardState,) {
+	if let Some(rhb,) = world.heroes.get_mut(world.player,) {
+		let from = rhb.state_machine.context().pos;
+		let was_jumping = rhb.is_jumping();
+		let speed = world.director.current().scroll_speed.round() as i16;
+		rhb.update(kb_state, &world.bindings, speed,);
+		world.score.travel(from, rhb.state_machine.context().pos,);
+		match (was_jumping, rhb.is_jumping(),) {
+			(false, true,) => world.events.push(BoyEvent::Jumped,),
+			(true, false,) => {
+				world.particles.burst(world.dust, rhb.feet(), LANDING_DUST,)
+			},
+			_ => {},
+		}
+	}
+}
+
+/// time survived counts while the player is around
+fn clock(world: &mut World, _: &KeyboardState,) {
+	if world.entities.is_alive(world.player,) {
+		world.score.tick();
+	}
+}
+
+/// pickups the player or a dog touches are scored and gone
+fn collect(world: &mut World, _: &KeyboardState,) {
+	let Some(hero,) = world
+		.heroes
+		.get(world.player,)
+		.and_then(|rhb| rhb.bounds(&world.sheet,),)
+	else {
+		return;
+	};
+	// dogs fetch pickups for the player
+	let mut collectors = vec![hero];
+	collectors.extend(
+		world
+			.dogs
+			.iter()
+			.filter_map(|(_, dog,)| dog.bounds(&world.dog_sheet,),),
+	);
+	let touched: Vec<_,> = world
+		.pickups
+		.iter()
+		.filter(|(_, p,)| {
+			collectors.iter().any(|c| queue::intersects(c, &p.bounds(),),)
+		},)
+		.map(|(entity, _,)| entity,)
+		.collect();
+	for entity in touched {
+		world.score.pickup();
+		world.entities.despawn(entity,);
+	}
+	world.pickups.prune(&world.entities,);
+}
+
+/// difficulty keeps up with how long and how well the player is doing
+fn direct(world: &mut World, _: &KeyboardState,) {
+	world.director.update(&world.score,);
+}
+
+/// heroes kick up dust while running
+fn kick_dust(world: &mut World, _: &KeyboardState,) {
+	for (entity, emitter,) in world.emitters.iter_mut() {
+		let Some(rhb,) = world.heroes.get(entity,) else {
+			continue;
+		};
+		emitter.active = rhb.is_running();
+		emitter.update(&mut world.particles, rhb.feet(),);
+	}
+}
+
+/// heroes wandering out of the world are gone for good. the player's costs
+/// a life
+fn despawn_lost(world: &mut World, _: &KeyboardState,) {
+	let lost: Vec<_,> = world
+		.heroes
+		.iter()
+		.filter(|(_, rhb,)| {
+			let center = rhb.state_machine.context().pos + RHB_CENTER;
+			let (x, y,) = (center.x_into(), center.y_into(),);
+			!(0.0..=WORLD_WIDTH).contains(&x,)
+				|| !(0.0..=WORLD_HEIGHT).contains(&y,)
+		},)
+		.map(|(entity, _,)| entity,)
+		.collect();
+	for entity in lost {
+		world.entities.despawn(entity,);
+		if entity == world.player {
+			world.lives = world.lives.saturating_sub(1,);
+			world.events.push(BoyEvent::Hurt,);
+		}
+	}
+	world.heroes.prune(&world.entities,);
+	world.emitters.prune(&world.entities,);
+}
+
+/// player gets a new hero while lives are left
+fn respawn(world: &mut World, _: &KeyboardState,) {
+	if !world.entities.is_alive(world.player,) && world.lives > 0 {
+		world.player = world.entities.spawn();
+		world.make_hero(world.player,);
+		let Some(leader,) =
+			world.heroes.get(world.player,).map(RedHatBoy::leader,)
+		else {
 			return;
 		};
-		rndrr.clear();
-		if let Some(rhb,) = self.rhb.as_ref() {
-			rhb.draw(rndrr,).expect("error happen while drawing rhb",);
+		// leashes dropped by the lost hero are picked up by the new one,
+		// with the dogs brought along
+		for (dog, leash,) in world.leashes.iter_mut() {
+			if world.entities.is_alive(leash.holder,) {
+				continue;
+			}
+			leash.holder = world.player;
+			if let Some(dog,) = world.dogs.get_mut(dog,) {
+				dog.heel(leader,);
+			}
+		}
+	}
+}
+
+/// dogs keep up with the player, reacting to whatever happened to him.
+/// any pickup a little ahead of him is run after
+fn follow(world: &mut World, _: &KeyboardState,) {
+	let leader = world.heroes.get(world.player,).map(RedHatBoy::leader,);
+	let scent = leader.and_then(|boy| {
+		let feet = f64::from(boy.feet.x,);
+		let ahead = |x: f64| if boy.facing_left { feet - x } else { x - feet };
+		world
+			.pickups
+			.iter()
+			.map(|(_, p,)| p.pos.0,)
+			.filter(|&x| (0.0..=f64::from(SNIFF,)).contains(&ahead(x,),),)
+			.min_by(|a, b| ahead(*a,).total_cmp(&ahead(*b,),),)
+			.map(|x| x as i16,)
+	},);
+	for (_, dog,) in world.dogs.iter_mut() {
+		dog.update(leader, scent, &world.events,);
+	}
+	world.events.clear();
+}
+
+/// leashes pull their dogs and the heroes holding them back within reach
+/// of each other
+fn tug(world: &mut World, _: &KeyboardState,) {
+	for (dog, leash,) in world.leashes.iter() {
+		let (Some(rhb,), Some(dog,),) =
+			(world.heroes.get_mut(leash.holder,), world.dogs.get_mut(dog,),)
+		else {
+			continue;
+		};
+		if let Some((hand, collar,),) =
+			leash.rope.solve(rhb.hand(), dog.collar(), BOY_MASS, DOG_MASS,)
+		{
+			rhb.pull(rounded(hand,),);
+			dog.pull(rounded(collar,),);
 		}
-		// let frame_name = format!("Run ({}).png", (self.frame / 3) + 1);
-		//
-		// if let Some(r,) = self.renderer.as_ref() {
-		// 	r.clear();
-		// 	if let Some(RedHatBoy { image, .. },) = self.rhb.as_ref() {
-		// 		r.draw_sprite_sheet(image, &frame_name, self.pos,)
-		// 			.expect("failed to draw sheet",);
-		// 	}
-		// }
 	}
 }
 
+/// nearest point to `v`
+fn rounded(v: Vertex,) -> Point {
+	Point { x: v.0.round() as i16, y: v.1.round() as i16, }
+}
+
+fn move_particles(world: &mut World, _: &KeyboardState,) {
+	world.particles.update();
+}
+
+fn draw_pickups<'a,>(world: &'a World, queue: &mut RenderQueue<'a,>,) {
+	for (_, pickup,) in world.pickups.iter() {
+		let (cx, cy,) = pickup.pos;
+		queue.primitive(
+			Layer::World,
+			-1,
+			Shape::Circle { cx, cy, r: PICKUP_RADIUS, },
+			ShapeStyle {
+				fill: Some(Color::GOLD,),
+				stroke: Some(Color::DARKGOLDENROD,),
+				line_width: 2.0,
+				..Default::default()
+			},
+		);
+	}
+}
+
+fn draw_dogs<'a,>(world: &'a World, queue: &mut RenderQueue<'a,>,) {
+	for (_, dog,) in world.dogs.iter() {
+		dog.draw(&world.dog_sheet, queue,);
+	}
+}
+
+/// leashes sag between hand and collar, drawn over both
+fn draw_leashes<'a,>(world: &'a World, queue: &mut RenderQueue<'a,>,) {
+	for (dog, leash,) in world.leashes.iter() {
+		let (Some(rhb,), Some(dog,),) =
+			(world.heroes.get(leash.holder,), world.dogs.get(dog,),)
+		else {
+			continue;
+		};
+		queue.primitive(
+			Layer::World,
+			1,
+			Shape::Polyline(leash.rope.curve(
+				rhb.hand(),
+				dog.collar(),
+				LEASH_SEGMENTS,
+			),),
+			ShapeStyle::stroke(Color::SADDLEBROWN, 3.0,),
+		);
+	}
+}
+
+fn draw_heroes<'a,>(world: &'a World, queue: &mut RenderQueue<'a,>,) {
+	for (_, rhb,) in world.heroes.iter() {
+		rhb.draw(&world.sheet, queue,);
+	}
+}
+
+fn draw_particles<'a,>(world: &'a World, queue: &mut RenderQueue<'a,>,) {
+	world.particles.draw(queue, Layer::Effects, 0, None,);
+}
+
+/// score, best score and lives over the top of the screen
+fn draw_hud<'a,>(world: &'a World, queue: &mut RenderQueue<'a,>,) {
+	const MARGIN: f64 = 16.0;
+	let (text, combo,) = if world.contrast {
+		queue.primitive(
+			Layer::Hud,
+			-1,
+			Shape::Rect { x: 0.0, y: 0.0, w: VIEW_WIDTH, h: MARGIN * 4.0, },
+			ShapeStyle::fill(Color::WHITE,),
+		);
+		(Color::BLACK, Color::BLACK,)
+	} else {
+		(HUD_COLOR, Color::DARKGOLDENROD,)
+	};
+	let total = world.score.total();
+	queue.label(
+		Layer::Hud,
+		0,
+		format!("SCORE {total}"),
+		(MARGIN, MARGIN,),
+		text,
+		20.0,
+		Align::Left,
+	);
+	queue.label(
+		Layer::Hud,
+		0,
+		format!("BEST {}", world.best.max(total,)),
+		(VIEW_WIDTH - MARGIN, MARGIN,),
+		text,
+		20.0,
+		Align::Right,
+	);
+	if world.score.combo() > 1 {
+		queue.label(
+			Layer::Hud,
+			0,
+			format!("COMBO x{}", world.score.multiplier()),
+			(MARGIN, MARGIN + 48.0,),
+			combo,
+			16.0,
+			Align::Left,
+		);
+	}
+	for i in 0..world.lives {
+		queue.primitive(
+			Layer::Hud,
+			0,
+			Shape::Circle {
+				cx: MARGIN + 8.0 + f64::from(i,) * 24.0,
+				cy: MARGIN + 32.0,
+				r:  8.0,
+			},
+			ShapeStyle::fill(Color::CRIMSON,),
+		);
+	}
+}
+
+/// walking around the world
+struct Play {
+	/// sky and hills behind everything, drawn once
+	scenery:       Option<CachedLayer,>,
+	world:         World,
+	systems:       Schedule<World, KeyboardState,>,
+	fx:            CameraEffects,
+	/// no shaking or flashing when a hero is lost
+	reduce_motion: bool,
+}
+
+impl Play {
+	fn new(shared: &Shared,) -> Self {
+		let systems = Schedule::new()
+			.on_update("steer", steer,)
+			.on_update("clock", clock,)
+			.on_update("collect", collect,)
+			.on_update("direct", direct,)
+			.on_update("kick dust", kick_dust,)
+			.on_update("despawn lost", despawn_lost,)
+			.on_update("respawn", respawn,)
+			.on_update("follow", follow,)
+			.on_update("tug", tug,)
+			.on_update("move particles", move_particles,)
+			.on_draw("pickups", draw_pickups,)
+			.on_draw("dogs", draw_dogs,)
+			.on_draw("heroes", draw_heroes,)
+			.on_draw("leashes", draw_leashes,)
+			.on_draw("particles", draw_particles,)
+			.on_draw("hud", draw_hud,);
+		Self {
+			scenery: None,
+			world: World::new(shared,),
+			reduce_motion: shared.save.settings.accessibility.reduce_motion,
+			systems,
+			fx: CameraEffects::new(),
+		}
+	}
+
+	fn state(&self,) -> PlayState {
+		let world = &self.world;
+		PlayState {
+			entities:  world.entities.clone(),
+			heroes:    world.heroes.clone(),
+			emitters:  world.emitters.clone(),
+			pickups:   world.pickups.clone(),
+			dogs:      world.dogs.clone(),
+			leashes:   world.leashes.clone(),
+			particles: world.particles.clone(),
+			player:    world.player,
+			lives:     world.lives,
+			score:     world.score.clone(),
+			fx:        self.fx.clone(),
+		}
+	}
+
+	/// fail unless `state` holds together, as a snapshot attached to a
+	/// report may be corrupt or edited by hand
+	fn check(state: &PlayState,) -> Rslt<(),> {
+		state.entities.check()?;
+		state.particles.check()?;
+		for (_, emitter,) in state.emitters.iter() {
+			if !state.particles.has_kind(emitter.kind,) {
+				bail!("emitter of unknown particle kind");
+			}
+		}
+		if !state.entities.is_alive(state.player,) && state.lives > 0 {
+			bail!("player is gone with {} lives left", state.lives);
+		}
+		Ok((),)
+	}
+
+	fn set_state(&mut self, state: PlayState,) {
+		let world = &mut self.world;
+		world.entities = state.entities;
+		world.heroes = state.heroes;
+		world.emitters = state.emitters;
+		world.pickups = state.pickups;
+		world.dogs = state.dogs;
+		world.leashes = state.leashes;
+		world.particles = state.particles;
+		world.player = state.player;
+		world.lives = state.lives;
+		world.score = state.score;
+		self.fx = state.fx;
+	}
+
+	/// whether every life is spent
+	fn is_lost(&self,) -> bool {
+		self.world.lives == 0
+	}
+}
+
+impl Scene<Shared,> for Play {
+	fn name(&self,) -> &str {
+		"Play"
+	}
+
+	fn init(&mut self, ctx: &mut Shared,) -> Rslt<(),> {
+		let world = Bounds { x: 0.0, y: 0.0, w: WORLD_WIDTH, h: WORLD_HEIGHT, };
+		self.scenery = Some(CachedLayer::new(&ctx.renderer, world,)?,);
+		let camera = ctx.renderer.camera_mut();
+		camera.bounds = Some(world,);
+		camera.dead_zone = (120.0, 80.0,);
+		camera.smoothing = 0.1;
+		camera.offset = (0.0, 0.0,);
+		if let Some(focus,) = self.world.focus() {
+			camera.snap_to(focus,);
+		}
+		Ok((),)
+	}
+
+	fn update(
+		&mut self,
+		ctx: &mut Shared,
+		input: &Input<'_,>,
+	) -> Change<Shared,> {
+		if input.just_pressed(&ctx.save.settings.bindings.pause,) {
+			return Change::push(Pause,);
+		}
+
+		let step = self.fx.update();
+		let camera = ctx.renderer.camera_mut();
+		camera.offset = self.fx.shake_offset();
+		if !step {
+			return Change::Stay;
+		}
+
+		// curves tuned mid run take over from where it has got to
+		self.world.director.set_curves(ctx.curves.clone(),);
+		let lives = self.world.lives;
+		self.systems.update(&mut self.world, input.keyboard(),);
+		if self.world.lives < lives && !self.reduce_motion {
+			self.fx.shake(8.0, 0.85,);
+			self.fx.flash(Color::WHITE, 12,);
+		}
+		if let Some(focus,) = self.world.focus() {
+			camera.follow(focus,);
+		}
+
+		if self.is_lost() {
+			let score = &self.world.score;
+			let place = ctx.save.record(HighScore {
+				score:    score.total(),
+				distance: score.distance(),
+				seconds:  score.seconds(),
+				pickups:  score.pickups(),
+			},);
+			ctx.persist();
+			return Change::push(GameOver {
+				score: score.total(),
+				best: ctx.save.best(),
+				place,
+			},);
+		}
+		Change::Stay
+	}
+
+	fn draw(&self, ctx: &Shared,) -> Rslt<(),> {
+		let rndrr = &ctx.renderer;
+		let mut queue = RenderQueue::new();
+		if let Some(scenery,) = self.scenery.as_ref() {
+			rndrr.update_cache(scenery, draw_scenery,)?;
+			queue.cached(Layer::Background, 0, scenery,);
+		}
+		self.systems.draw(&self.world, &mut queue,);
+		rndrr.flush(queue,)?;
+		self.fx.draw(rndrr,)
+	}
+
+	fn entities(&self,) -> Vec<EntityInfo,> {
+		let world = &self.world;
+		let heroes =
+			world.heroes.iter().map(|(_, rhb,)| rhb.debug_info(&world.sheet,),);
+		let dogs = world
+			.dogs
+			.iter()
+			.map(|(_, dog,)| dog.debug_info(&world.dog_sheet,),);
+		heroes.chain(dogs,).collect()
+	}
+
+	fn tuning(&self,) -> Vec<(String, String,),> {
+		self.world.director.tuning()
+	}
+
+	fn snapshot(&self,) -> Rslt<Option<Value,>,> {
+		Ok(Some(serde_json::to_value(self.state(),)?,),)
+	}
+
+	fn restore(&mut self, ctx: &mut Shared, state: Value,) -> Rslt<(),> {
+		let state = serde_json::from_value(state,)?;
+		Self::check(&state,)?;
+		self.set_state(state,);
+		if let Some(focus,) = self.world.focus() {
+			ctx.renderer.camera_mut().snap_to(focus,);
+		}
+		Ok((),)
+	}
+}
+
+/// sky with a row of hills along the bottom of the world
+fn draw_scenery(rndrr: &Renderer,) -> Rslt<(),> {
+	rndrr.draw_shape(
+		&Shape::Rect { x: 0.0, y: 0.0, w: WORLD_WIDTH, h: WORLD_HEIGHT, },
+		&ShapeStyle::fill(Color::LIGHTSKYBLUE,),
+	)?;
+
+	const HILL_WIDTH: f64 = 300.0;
+	const PEAKS: [f64; 3] = [380.0, 440.0, 410.0,];
+	let mut hills = vec![(0.0, WORLD_HEIGHT,)];
+	let count = (WORLD_WIDTH / HILL_WIDTH).ceil() as usize;
+	for i in 0..count {
+		let x = i as f64 * HILL_WIDTH;
+		hills.push((x + HILL_WIDTH / 2.0, PEAKS[i % PEAKS.len()],),);
+		hills.push((x + HILL_WIDTH, WORLD_HEIGHT - 80.0,),);
+	}
+	hills.push((WORLD_WIDTH, WORLD_HEIGHT,),);
+	rndrr.draw_shape(
+		&Shape::Polygon(hills,),
+		&ShapeStyle::fill(Color::SEAGREEN,),
+	)
+}
+
+/// puffs kicked up by boy's feet
+fn dust() -> ParticleKind {
+	ParticleKind {
+		lifetime: (20, 35,),
+		speed:    (0.3, 1.2,),
+		// upward, fanning out to both sides
+		angle:    (-std::f64::consts::PI * 0.9, -std::f64::consts::PI * 0.1,),
+		gravity:  0.02,
+		alpha:    Curve::linear(0.7, 0.0,),
+		scale:    Curve::linear(0.5, 1.5,),
+		look:     Look::Circle { radius: 6.0, color: Color::BURLYWOOD, },
+	}
+}
+
+/// boy drawn from a sprite sheet kept by whoever owns him
+#[derive(Clone, Serialize, Deserialize,)]
 struct RedHatBoy {
 	state_machine: RedHatBoyStateMachine,
-	image:         Image,
 }
 
 impl RedHatBoy {
-	fn new(image: Image,) -> Rslt<Self,> {
-		Ok(Self {
+	fn new() -> Self {
+		Self {
 			state_machine: RedHatBoyStateMachine::Idle(RedHatBoyState::new(),),
-			image,
-		},)
+		}
 	}
 
-	fn draw(&self, rndrr: &Renderer,) -> Rslt<(),> {
-		rndrr.draw_sprite_sheet(
-			&self.image,
-			&self.state_machine.frame_name(),
-			self.state_machine.context().pos,
-		)
+	fn is_running(&self,) -> bool {
+		matches!(self.state_machine, RedHatBoyStateMachine::Running(_,))
 	}
 
-	fn update(&mut self, kb_state: &KeyboardState,) {
-		let vel = Self::keyboard_velocity(kb_state,);
-		let event = if vel.x == 0 && vel.y == 0 {
+	fn is_jumping(&self,) -> bool {
+		matches!(self.state_machine, RedHatBoyStateMachine::Jumping(_,))
+	}
+
+	/// where boy's feet touch the ground
+	fn feet(&self,) -> (f64, f64,) {
+		let feet = self.feet_point();
+		(feet.x_into(), feet.y_into(),)
+	}
+
+	/// where boy's feet touch the ground, even while he is in the air
+	fn feet_point(&self,) -> Point {
+		self.state_machine.context().pos + RHB_FEET
+	}
+
+	/// what a dog following boy goes by
+	fn leader(&self,) -> Leader {
+		Leader {
+			feet:        self.feet_point(),
+			facing_left: self.state_machine.context().facing_left,
+		}
+	}
+
+	/// where boy holds a leash, in world units
+	fn hand(&self,) -> Vertex {
+		let context = self.state_machine.context();
+		let x = if context.facing_left {
+			RHB_CENTER.x * 2 - RHB_HAND.x
+		} else {
+			RHB_HAND.x
+		};
+		let at = context.lifted();
+		(f64::from(at.x + x,), f64::from(at.y + RHB_HAND.y,),)
+	}
+
+	/// move boy `by`, as a leash does
+	fn pull(&mut self, by: Point,) {
+		self.state_machine.pull(by,);
+	}
+
+	fn draw<'a,>(&self, sheet: &'a Image, queue: &mut RenderQueue<'a,>,) {
+		let context = self.state_machine.context();
+		queue.sprite(
+			Layer::World,
+			0,
+			sheet,
+			self.state_machine.frame_name(),
+			context.lifted(),
+			DrawParams { flip_x: context.facing_left, ..Default::default() },
+		);
+	}
+
+	/// area boy's current frame covers in the world
+	fn bounds(&self, sheet: &Image,) -> Option<Bounds,> {
+		let frame = self.state_machine.frame_name();
+		sheet.sprite_bounds(&frame, self.state_machine.context().lifted(),)
+	}
+
+	fn debug_info(&self, sheet: &Image,) -> EntityInfo {
+		let context = self.state_machine.context();
+		let frame = self.state_machine.frame_name();
+		EntityInfo {
+			name: "RedHatBoy".into(),
+			state: self.state_machine.to_string(),
+			bounds: sheet.sprite_bounds(&frame, context.lifted(),),
+			frame,
+			pos: context.pos,
+			vel: context.vel,
+		}
+	}
+
+	/// follow the keys held, walking `speed` world units a tick
+	fn update(
+		&mut self,
+		kb_state: &KeyboardState,
+		bindings: &Bindings,
+		speed: i16,
+	) {
+		let vel = Self::keyboard_velocity(kb_state, bindings, speed,);
+		let event = if kb_state.is_pressed(&bindings.jump,) {
+			GameEvent::Jump { vel, }
+		} else if vel.x == 0 && vel.y == 0 {
 			GameEvent::Idle
 		} else {
 			GameEvent::Run { vel, }
@@ -98,31 +1137,37 @@ impl RedHatBoy {
 		self.state_machine = self.state_machine.update();
 	}
 
-	fn keyboard_velocity(kb_state: &KeyboardState,) -> Point {
+	fn keyboard_velocity(
+		kb_state: &KeyboardState,
+		bindings: &Bindings,
+		speed: i16,
+	) -> Point {
 		let mut vel = Point { x: 0, y: 0, };
-		let pressed = |code| kb_state.is_pressed(code,);
-		if pressed("KeyA",) {
-			vel.x -= WALK_SPEED;
+		let pressed = |code: &str| kb_state.is_pressed(code,);
+		if pressed(&bindings.left,) {
+			vel.x -= speed;
 		}
-		if pressed("KeyF",) {
-			vel.x += WALK_SPEED;
+		if pressed(&bindings.right,) {
+			vel.x += speed;
 		}
-		if pressed("KeyS",) {
-			vel.y += WALK_SPEED;
+		if pressed(&bindings.down,) {
+			vel.y += speed;
 		}
-		if pressed("KeyD",) {
-			vel.y -= WALK_SPEED;
+		if pressed(&bindings.up,) {
+			vel.y -= speed;
 		}
 
 		vel
 	}
 }
 
-#[derive(Clone, Copy, strum::Display,)]
+#[derive(Clone, Copy, strum::Display, Serialize, Deserialize,)]
 enum RedHatBoyStateMachine {
 	Idle(RedHatBoyState<Idle,>,),
 	#[strum(to_string = "Run")]
 	Running(RedHatBoyState<Running,>,),
+	#[strum(to_string = "Jump")]
+	Jumping(RedHatBoyState<Jumping,>,),
 }
 
 impl From<RedHatBoyState<Running,>,> for RedHatBoyStateMachine {
@@ -131,10 +1176,17 @@ impl From<RedHatBoyState<Running,>,> for RedHatBoyStateMachine {
 	}
 }
 
+impl From<RedHatBoyState<Jumping,>,> for RedHatBoyStateMachine {
+	fn from(value: RedHatBoyState<Jumping,>,) -> Self {
+		Self::Jumping(value,)
+	}
+}
+
 #[derive(Clone, Copy,)]
 pub enum GameEvent {
 	Idle,
 	Run { vel: Point, },
+	Jump { vel: Point, },
 }
 
 impl RedHatBoyStateMachine {
@@ -153,6 +1205,20 @@ impl RedHatBoyStateMachine {
 			(Self::Running(state,), GameEvent::Run { vel, },) => {
 				Self::Running(state.with_velocity(vel,),)
 			},
+			(Self::Idle(state,), GameEvent::Jump { vel, },) => {
+				state.jump(vel,).into()
+			},
+			(Self::Running(state,), GameEvent::Jump { vel, },) => {
+				state.jump(vel,).into()
+			},
+			// boy can not jump again before landing, but steers in the air
+			(Self::Jumping(state,), GameEvent::Idle,) => {
+				Self::Jumping(state.with_velocity(Point { x: 0, y: 0, },),)
+			},
+			(
+				Self::Jumping(state,),
+				GameEvent::Run { vel, } | GameEvent::Jump { vel, },
+			) => Self::Jumping(state.with_velocity(vel,),),
 		}
 	}
 
@@ -165,6 +1231,7 @@ impl RedHatBoyStateMachine {
 		match self {
 			Self::Idle(_,) => IDLE_CARDS,
 			Self::Running(_,) => RUN_CARDS,
+			Self::Jumping(_,) => JUMP_CARDS,
 		}
 	}
 
@@ -178,6 +1245,14 @@ impl RedHatBoyStateMachine {
 				red_hat_boy_state.update();
 				Self::Running(red_hat_boy_state,)
 			},
+			Self::Jumping(mut red_hat_boy_state,) => {
+				red_hat_boy_state.update();
+				if red_hat_boy_state.has_landed() {
+					Self::Idle(red_hat_boy_state.land(),)
+				} else {
+					Self::Jumping(red_hat_boy_state,)
+				}
+			},
 		}
 	}
 }
@@ -0,0 +1,132 @@
+//! how hard a run gets as it goes on, along curves read from
+//! [`DIFFICULTY_FILE`]
+//!
+//! seconds survived and points scored are weighed into one intensity, and
+//! every parameter is a curve over it. the only parameter is the scroll
+//! speed boy runs at: nothing spawns obstacles, gaps or enemies, so there is
+//! no density, width or frequency of them to drive yet
+
+use crate::Rslt;
+use crate::engn::keyframes::Keyframes;
+use crate::game::Score;
+use anyhow::Context;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+use std::rc::Rc;
+
+/// path to the curves, fetched so they can be tuned without a rebuild
+pub const DIFFICULTY_FILE: &str = "difficulty.json";
+/// key fetching [`DIFFICULTY_FILE`] again, into the run in progress
+pub const RELOAD_KEY: &str = "Backslash";
+/// fastest boy may run, in world units a tick, so his position can not
+/// overflow
+pub const MAX_SCROLL_SPEED: f64 = 32.0;
+/// ticks between the values the change of each parameter is measured from
+const TICKS_PER_SECOND: u32 = 60;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize,)]
+pub struct DifficultyCurves {
+	/// intensity each second survived adds
+	pub time_weight:  f64,
+	/// intensity each point scored adds
+	pub score_weight: f64,
+	pub scroll_speed: Keyframes,
+}
+
+/// every parameter at one intensity
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize,)]
+pub struct Difficulty {
+	/// world units boy runs each tick, with the camera scrolling along
+	pub scroll_speed: f64,
+}
+
+impl DifficultyCurves {
+	/// curves built into the game, played with until the file is fetched
+	pub fn builtin() -> Self {
+		Self::from_json(include_str!("../../static/difficulty.json"),)
+			.expect("built-in difficulty curves are broken",)
+	}
+
+	pub fn from_json(json: &str,) -> Rslt<Self,> {
+		let curves: Self =
+			serde_json::from_str(json,).context("bad difficulty curves",)?;
+		curves.check()?;
+		Ok(curves,)
+	}
+
+	/// refuse curves a run can not be played along. the scroll speed must
+	/// stay forward and at most [`MAX_SCROLL_SPEED`], which holding it flat
+	/// past the ends keeps true as long as every keyframe does
+	pub fn check(&self,) -> Rslt<(),> {
+		let points = self.scroll_speed.points();
+		if let Some((_, speed,),) = points
+			.iter()
+			.find(|(_, speed,)| !(*speed > 0.0 && *speed <= MAX_SCROLL_SPEED),)
+		{
+			bail!("scroll speed {speed} is not within 0 to {MAX_SCROLL_SPEED}");
+		}
+		Ok((),)
+	}
+
+	/// how far into the curves `seconds` survived and `score` put a run
+	pub fn intensity(&self, seconds: f64, score: u64,) -> f64 {
+		seconds * self.time_weight + score as f64 * self.score_weight
+	}
+
+	/// every parameter at `intensity`
+	pub fn at(&self, intensity: f64,) -> Difficulty {
+		Difficulty { scroll_speed: self.scroll_speed.at(intensity,), }
+	}
+}
+
+/// keeps the difficulty in step with a run
+pub(super) struct Director {
+	curves:     Rc<DifficultyCurves,>,
+	intensity:  f64,
+	current:    Difficulty,
+	/// parameters a second ago, to show how fast they are changing
+	second_ago: Difficulty,
+}
+
+impl Director {
+	pub(super) fn new(curves: Rc<DifficultyCurves,>,) -> Self {
+		let current = curves.at(0.0,);
+		Self { curves, intensity: 0.0, current, second_ago: current, }
+	}
+
+	/// move the parameters along with `score`
+	pub(super) fn update(&mut self, score: &Score,) {
+		if score.ticks().is_multiple_of(TICKS_PER_SECOND,) {
+			self.second_ago = self.current;
+		}
+		let seconds = f64::from(score.ticks(),) / f64::from(TICKS_PER_SECOND,);
+		self.intensity = self.curves.intensity(seconds, score.total(),);
+		self.current = self.curves.at(self.intensity,);
+	}
+
+	/// go on along `curves` from the intensity reached so far
+	pub(super) fn set_curves(&mut self, curves: Rc<DifficultyCurves,>,) {
+		if !Rc::ptr_eq(&self.curves, &curves,) {
+			self.current = curves.at(self.intensity,);
+			self.curves = curves;
+		}
+	}
+
+	/// parameters where the run is now
+	pub(super) fn current(&self,) -> Difficulty {
+		self.current
+	}
+
+	/// every parameter and how much it changed over the last second
+	pub(super) fn tuning(&self,) -> Vec<(String, String,),> {
+		let (now, then,) = (self.current, self.second_ago,);
+		let line = |name: &str, now: f64, then: f64| {
+			(name.to_string(), format!("{now:.2} ({:+.2}/s)", now - then),)
+		};
+		vec![
+			("intensity".to_string(), format!("{:.1}", self.intensity),),
+			line("scroll speed", now.scroll_speed, then.scroll_speed,),
+		]
+	}
+}
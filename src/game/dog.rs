@@ -0,0 +1,337 @@
+//! boy's dog, trotting after him wherever he walks
+
+use crate::engn::DrawParams;
+use crate::engn::Image;
+use crate::engn::Point;
+use crate::engn::camera::Bounds;
+use crate::engn::debug::EntityInfo;
+use crate::engn::queue::Layer;
+use crate::engn::queue::RenderQueue;
+use crate::engn::shape::Vertex;
+use crate::engn::validate::Clip;
+use crate::game::BoyEvent;
+use crate::game::WORLD_WIDTH;
+use crate::game::dog_states::Barking;
+use crate::game::dog_states::DogState;
+use crate::game::dog_states::Running;
+use crate::game::dog_states::Sitting;
+use crate::game::dog_states::Walking;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// count of sitting cards
+pub(super) const SIT_CARDS: u8 = 4;
+/// count of walking cards
+const WALK_CARDS: u8 = 6;
+/// count of running cards
+const RUN_CARDS: u8 = 6;
+/// count of barking cards
+const BARK_CARDS: u8 = 4;
+/// offset from the dog's position to where its feet touch the ground
+pub(super) const DOG_FEET: Point = Point { x: 50, y: 76, };
+/// how much further back than boy the dog walks, so it is drawn behind him
+const DEPTH: i16 = 10;
+/// width of the dog's frames, which flip around their middle
+const FRAME_WIDTH: i16 = 100;
+
+/// every animation clip drawn from the dog's sprite sheet
+pub const DOG_CLIPS: &[Clip] = &[
+	Clip { name: "Sit", cards: SIT_CARDS, },
+	Clip { name: "Walk", cards: WALK_CARDS, },
+	Clip { name: "Run", cards: RUN_CARDS, },
+	Clip { name: "Bark", cards: BARK_CARDS, },
+];
+
+/// how the dog keeps up with whoever it follows
+#[derive(Clone, Copy, Debug, Serialize, Deserialize,)]
+pub(super) struct Follow {
+	/// how far behind boy's feet the dog likes to be
+	pub distance:   i16,
+	/// further than this from where it likes to be, the dog runs
+	pub catch_up:   i16,
+	pub walk_speed: i16,
+	pub run_speed:  i16,
+}
+
+impl Default for Follow {
+	fn default() -> Self {
+		Self { distance: 90, catch_up: 120, walk_speed: 3, run_speed: 7, }
+	}
+}
+
+/// what the dog knows of boy when deciding what to do
+#[derive(Clone, Copy,)]
+pub(super) struct Leader {
+	/// where boy's feet touch the ground
+	pub feet:        Point,
+	pub facing_left: bool,
+}
+
+/// dog drawn from a sprite sheet kept by whoever owns it
+#[derive(Clone, Serialize, Deserialize,)]
+pub(super) struct Dog {
+	state_machine: DogStateMachine,
+	follow:        Follow,
+}
+
+impl Dog {
+	/// dog sitting with its feet at `feet`
+	pub(super) fn new(feet: Point, follow: Follow,) -> Self {
+		let pos = Point { x: feet.x - DOG_FEET.x, y: feet.y - DOG_FEET.y, };
+		Self {
+			state_machine: DogStateMachine::Sitting(DogState::new(pos,),),
+			follow,
+		}
+	}
+
+	/// sit the dog down by `boy` at once, wherever it was
+	pub(super) fn heel(&mut self, boy: Leader,) {
+		let spot = self.spot(boy,);
+		let pos = Point { x: spot.x - DOG_FEET.x, y: spot.y - DOG_FEET.y, };
+		self.state_machine = DogStateMachine::Sitting(DogState::new(pos,),);
+	}
+
+	/// where the dog's feet touch the ground
+	pub(super) fn feet(&self,) -> Point {
+		self.state_machine.context().pos + DOG_FEET
+	}
+
+	/// where a leash is tied to the dog, in world units
+	pub(super) fn collar(&self,) -> Vertex {
+		let context = self.state_machine.context();
+		// the head is lower and further back while sitting, and stretched
+		// forwards while running
+		let collar = match self.state_machine {
+			DogStateMachine::Sitting(_,) => Point { x: 52, y: 40, },
+			DogStateMachine::Running(_,) => Point { x: 69, y: 40, },
+			_ => Point { x: 66, y: 42, },
+		};
+		let x =
+			if context.facing_left { FRAME_WIDTH - collar.x } else { collar.x };
+		(f64::from(context.pos.x + x,), f64::from(context.pos.y + collar.y,),)
+	}
+
+	/// move the dog `by`, as a leash does
+	pub(super) fn pull(&mut self, by: Point,) {
+		self.state_machine.pull(by,);
+	}
+
+	pub(super) fn draw<'a,>(
+		&self,
+		sheet: &'a Image,
+		queue: &mut RenderQueue<'a,>,
+	) {
+		let context = self.state_machine.context();
+		queue.sprite(
+			Layer::World,
+			0,
+			sheet,
+			self.state_machine.frame_name(),
+			context.pos,
+			DrawParams { flip_x: context.facing_left, ..Default::default() },
+		);
+	}
+
+	/// area the dog's current frame covers in the world
+	pub(super) fn bounds(&self, sheet: &Image,) -> Option<Bounds,> {
+		let frame = self.state_machine.frame_name();
+		sheet.sprite_bounds(&frame, self.state_machine.context().pos,)
+	}
+
+	pub(super) fn debug_info(&self, sheet: &Image,) -> EntityInfo {
+		let context = self.state_machine.context();
+		let frame = self.state_machine.frame_name();
+		EntityInfo {
+			name: "Dog".into(),
+			state: self.state_machine.to_string(),
+			bounds: sheet.sprite_bounds(&frame, context.pos,),
+			frame,
+			pos: context.pos,
+			vel: context.vel,
+		}
+	}
+
+	/// follow `boy`, if he is around, reacting to what happened to him
+	/// this tick. a `scent` is the x of something worth running ahead to
+	pub(super) fn update(
+		&mut self,
+		boy: Option<Leader,>,
+		scent: Option<i16,>,
+		events: &[BoyEvent],
+	) {
+		let event = self.think(boy, scent, events,);
+		self.state_machine = self.state_machine.transition(event,);
+		self.state_machine = self.state_machine.update();
+	}
+
+	/// where the dog likes to be, behind `boy` on the ground
+	fn spot(&self, boy: Leader,) -> Point {
+		let distance = self.follow.distance;
+		let behind = if boy.facing_left { distance } else { -distance };
+		let room = DOG_FEET.x..=WORLD_WIDTH as i16 - DOG_FEET.x;
+		// with no room behind boy at the edge of the world, it waits ahead
+		let x = if room.contains(&(boy.feet.x + behind),) {
+			boy.feet.x + behind
+		} else {
+			boy.feet.x - behind
+		};
+		Point { x, y: boy.feet.y - DEPTH, }
+	}
+
+	fn think(
+		&self,
+		boy: Option<Leader,>,
+		scent: Option<i16,>,
+		events: &[BoyEvent],
+	) -> DogEvent {
+		let Some(boy,) = boy else {
+			return DogEvent::Sit;
+		};
+		let feet = self.feet();
+		if !events.is_empty() {
+			return DogEvent::Bark { facing_left: boy.feet.x < feet.x, };
+		}
+
+		let follow = self.follow;
+		let spot = match scent {
+			Some(x,) => Point { x, y: boy.feet.y - DEPTH, },
+			None => self.spot(boy,),
+		};
+		let (dx, dy,) =
+			(f64::from(spot.x - feet.x,), f64::from(spot.y - feet.y,),);
+		let gap = dx.hypot(dy,);
+		// anything worth chasing is run after. otherwise, once running, the
+		// dog keeps running until it is back at its spot
+		let run = scent.is_some()
+			|| gap > f64::from(follow.catch_up,)
+			|| (matches!(self.state_machine, DogStateMachine::Running(_,))
+				&& gap > f64::from(follow.walk_speed,));
+		let speed =
+			f64::from(if run { follow.run_speed } else { follow.walk_speed },);
+		let vel = if gap <= speed {
+			Point { x: dx as i16, y: dy as i16, }
+		} else {
+			let scale = speed / gap;
+			Point {
+				x: (dx * scale).round() as i16,
+				y: (dy * scale).round() as i16,
+			}
+		};
+
+		if vel == (Point { x: 0, y: 0, }) {
+			DogEvent::Sit
+		} else if run {
+			DogEvent::Run { vel, }
+		} else {
+			DogEvent::Walk { vel, }
+		}
+	}
+}
+
+#[derive(Clone, Copy, strum::Display, Serialize, Deserialize,)]
+pub(super) enum DogStateMachine {
+	#[strum(to_string = "Sit")]
+	Sitting(DogState<Sitting,>,),
+	#[strum(to_string = "Walk")]
+	Walking(DogState<Walking,>,),
+	#[strum(to_string = "Run")]
+	Running(DogState<Running,>,),
+	#[strum(to_string = "Bark")]
+	Barking(DogState<Barking,>,),
+}
+
+#[derive(Clone, Copy,)]
+enum DogEvent {
+	Sit,
+	Walk {
+		vel: Point,
+	},
+	Run {
+		vel: Point,
+	},
+	/// stop and bark towards `facing_left`
+	Bark {
+		facing_left: bool,
+	},
+}
+
+impl DogStateMachine {
+	fn transition(self, event: DogEvent,) -> Self {
+		match (self, event,) {
+			// a bark is let out in full, whatever happens meanwhile
+			(Self::Barking(state,), event,) => {
+				if state.is_done() {
+					Self::Sitting(state.sit(),).transition(event,)
+				} else {
+					Self::Barking(state,)
+				}
+			},
+			(Self::Sitting(state,), DogEvent::Sit,) => Self::Sitting(state,),
+			(Self::Sitting(state,), DogEvent::Walk { vel, },) => {
+				Self::Walking(state.walk(vel,),)
+			},
+			(Self::Sitting(state,), DogEvent::Run { vel, },) => {
+				Self::Running(state.run(vel,),)
+			},
+			(Self::Sitting(state,), DogEvent::Bark { facing_left, },) => {
+				Self::Barking(state.bark(facing_left,),)
+			},
+			(Self::Walking(state,), DogEvent::Sit,) => {
+				Self::Sitting(state.sit(),)
+			},
+			(Self::Walking(state,), DogEvent::Walk { vel, },) => {
+				Self::Walking(state.with_velocity(vel,),)
+			},
+			(Self::Walking(state,), DogEvent::Run { vel, },) => {
+				Self::Running(state.run(vel,),)
+			},
+			(Self::Walking(state,), DogEvent::Bark { facing_left, },) => {
+				Self::Barking(state.bark(facing_left,),)
+			},
+			(Self::Running(state,), DogEvent::Sit,) => {
+				Self::Sitting(state.sit(),)
+			},
+			(Self::Running(state,), DogEvent::Walk { vel, },) => {
+				Self::Walking(state.walk(vel,),)
+			},
+			(Self::Running(state,), DogEvent::Run { vel, },) => {
+				Self::Running(state.with_velocity(vel,),)
+			},
+			(Self::Running(state,), DogEvent::Bark { facing_left, },) => {
+				Self::Barking(state.bark(facing_left,),)
+			},
+		}
+	}
+
+	fn frame_name(&self,) -> String {
+		let (cards, pace,) = match self {
+			Self::Sitting(_,) => (SIT_CARDS, 6,),
+			Self::Walking(_,) => (WALK_CARDS, 4,),
+			Self::Running(_,) => (RUN_CARDS, 3,),
+			Self::Barking(_,) => (BARK_CARDS, 6,),
+		};
+		let card = (self.context().frame / pace) % cards + 1;
+		format!("{self} ({card}).png")
+	}
+
+	fn update(self,) -> Self {
+		match self {
+			Self::Sitting(mut dog_state,) => {
+				dog_state.update();
+				Self::Sitting(dog_state,)
+			},
+			Self::Walking(mut dog_state,) => {
+				dog_state.update();
+				Self::Walking(dog_state,)
+			},
+			Self::Running(mut dog_state,) => {
+				dog_state.update();
+				Self::Running(dog_state,)
+			},
+			Self::Barking(mut dog_state,) => {
+				dog_state.update();
+				Self::Barking(dog_state,)
+			},
+		}
+	}
+}
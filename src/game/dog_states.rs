@@ -0,0 +1,164 @@
+use crate::engn::Point;
+use crate::game::dog::DogStateMachine;
+use serde::Deserialize;
+use serde::Serialize;
+use std::marker::PhantomData;
+
+/// ticks a bark lasts before the dog sits back down
+const BARK_TICKS: u8 = 24;
+
+/// serialized as its context alone, the state being the variant of
+/// [`DogStateMachine`] holding it
+#[derive(Clone, Copy, Serialize, Deserialize,)]
+#[serde(bound = "")]
+pub(super) struct DogState<S,> {
+	context: DogContext,
+	#[doc(hidden)]
+	#[serde(skip)]
+	_state:  PhantomData<S,>,
+}
+
+impl<S,> DogState<S,> {
+	pub(super) fn update(&mut self,) {
+		self.context.update();
+	}
+
+	/// same context in state `T`, with its animation started over
+	fn into_state<T,>(self, vel: Point,) -> DogState<T,> {
+		let mut context = self.context;
+		context.frame = 0;
+		context.vel = vel;
+		DogState { context, _state: PhantomData, }
+	}
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize,)]
+pub(super) struct DogContext {
+	pub frame:       u8,
+	pub pos:         Point,
+	pub vel:         Point,
+	/// last horizontal direction the dog moved or barked in
+	pub facing_left: bool,
+}
+
+impl DogContext {
+	fn update(&mut self,) {
+		self.frame = self.frame.wrapping_add(1,);
+		self.pos += self.vel;
+		if self.vel.x != 0 {
+			self.facing_left = self.vel.x < 0;
+		}
+	}
+}
+
+#[derive(Clone, Copy,)]
+pub(super) struct Sitting;
+#[derive(Clone, Copy,)]
+pub(super) struct Walking;
+#[derive(Clone, Copy,)]
+pub(super) struct Running;
+#[derive(Clone, Copy,)]
+pub(super) struct Barking;
+
+impl DogState<Sitting,> {
+	pub fn new(pos: Point,) -> Self {
+		Self {
+			context: DogContext {
+				frame: 0,
+				pos,
+				vel: Point { x: 0, y: 0, },
+				facing_left: false,
+			},
+			_state:  PhantomData,
+		}
+	}
+
+	pub fn walk(self, vel: Point,) -> DogState<Walking,> {
+		self.into_state(vel,)
+	}
+
+	pub fn run(self, vel: Point,) -> DogState<Running,> {
+		self.into_state(vel,)
+	}
+
+	pub fn bark(self, facing_left: bool,) -> DogState<Barking,> {
+		bark(self, facing_left,)
+	}
+}
+
+impl DogState<Walking,> {
+	pub fn with_velocity(mut self, vel: Point,) -> Self {
+		self.context.vel = vel;
+		self
+	}
+
+	pub fn run(self, vel: Point,) -> DogState<Running,> {
+		self.into_state(vel,)
+	}
+
+	pub fn sit(self,) -> DogState<Sitting,> {
+		self.into_state(Point { x: 0, y: 0, },)
+	}
+
+	pub fn bark(self, facing_left: bool,) -> DogState<Barking,> {
+		bark(self, facing_left,)
+	}
+}
+
+impl DogState<Running,> {
+	pub fn with_velocity(mut self, vel: Point,) -> Self {
+		self.context.vel = vel;
+		self
+	}
+
+	pub fn walk(self, vel: Point,) -> DogState<Walking,> {
+		self.into_state(vel,)
+	}
+
+	pub fn sit(self,) -> DogState<Sitting,> {
+		self.into_state(Point { x: 0, y: 0, },)
+	}
+
+	pub fn bark(self, facing_left: bool,) -> DogState<Barking,> {
+		bark(self, facing_left,)
+	}
+}
+
+impl DogState<Barking,> {
+	/// whether the bark has been let out in full
+	pub fn is_done(&self,) -> bool {
+		self.context.frame >= BARK_TICKS
+	}
+
+	pub fn sit(self,) -> DogState<Sitting,> {
+		self.into_state(Point { x: 0, y: 0, },)
+	}
+}
+
+/// dog stopped where it is, barking towards `facing_left`
+fn bark<S,>(state: DogState<S,>, facing_left: bool,) -> DogState<Barking,> {
+	let mut state = state.into_state(Point { x: 0, y: 0, },);
+	state.context.facing_left = facing_left;
+	state
+}
+
+impl DogStateMachine {
+	/// move the dog `by`, whatever it is doing
+	pub(super) fn pull(&mut self, by: Point,) {
+		match self {
+			Self::Sitting(dog_state,) => dog_state.context.pos += by,
+			Self::Walking(dog_state,) => dog_state.context.pos += by,
+			Self::Running(dog_state,) => dog_state.context.pos += by,
+			Self::Barking(dog_state,) => dog_state.context.pos += by,
+		}
+	}
+
+	pub(super) fn context(&self,) -> &DogContext {
+		match self {
+			Self::Sitting(dog_state,) => &dog_state.context,
+			Self::Walking(dog_state,) => &dog_state.context,
+			Self::Running(dog_state,) => &dog_state.context,
+			Self::Barking(dog_state,) => &dog_state.context,
+		}
+	}
+}
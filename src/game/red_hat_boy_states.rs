@@ -1,13 +1,23 @@
 use crate::engn::Point;
 use crate::game::RedHatBoyStateMachine;
+use serde::Deserialize;
+use serde::Serialize;
 use std::marker::PhantomData;
 
 const FLOOR: i16 = 475;
+/// how fast boy leaves the ground when he jumps
+const JUMP_SPEED: i16 = 14;
+/// how much a jump slows each tick, until boy falls back down
+const GRAVITY: i16 = 1;
 
-#[derive(Clone, Copy,)]
+/// serialized as its context alone, the state being the variant of
+/// [`RedHatBoyStateMachine`] holding it
+#[derive(Clone, Copy, Serialize, Deserialize,)]
+#[serde(bound = "")]
 pub(super) struct RedHatBoyState<S,> {
 	context: RedHatBoyContext,
 	#[doc(hidden)]
+	#[serde(skip)]
 	_state:  PhantomData<S,>,
 }
 
@@ -17,17 +27,39 @@ impl<S,> RedHatBoyState<S,> {
 	}
 }
 
-#[derive(Clone, Copy,)]
+#[derive(Clone, Copy, Serialize, Deserialize,)]
 pub(super) struct RedHatBoyContext {
-	pub frame: u8,
-	pub pos:   Point,
-	pub vel:   Point,
+	pub frame:       u8,
+	pub pos:         Point,
+	pub vel:         Point,
+	/// last horizontal direction boy moved in
+	pub facing_left: bool,
+	/// how high off the ground at `pos` a jump has taken boy
+	#[serde(default)]
+	pub height:      i16,
+	/// how fast boy is rising, falling once it is negative
+	#[serde(default)]
+	pub climb:       i16,
 }
 
 impl RedHatBoyContext {
 	fn update(&mut self,) {
 		self.frame = self.frame.wrapping_add(1,);
 		self.pos += self.vel;
+		if self.vel.x != 0 {
+			self.facing_left = self.vel.x < 0;
+		}
+		self.height = (self.height + self.climb).max(0,);
+		if self.height > 0 {
+			self.climb -= GRAVITY;
+		} else {
+			self.climb = 0;
+		}
+	}
+
+	/// where boy is drawn, lifted off the ground by a jump
+	pub fn lifted(&self,) -> Point {
+		Point { x: self.pos.x, y: self.pos.y - self.height, }
 	}
 }
 
@@ -35,14 +67,19 @@ impl RedHatBoyContext {
 pub(super) struct Idle;
 #[derive(Clone, Copy,)]
 pub(super) struct Running;
+#[derive(Clone, Copy,)]
+pub(super) struct Jumping;
 
 impl RedHatBoyState<Idle,> {
 	pub fn new() -> Self {
 		Self {
 			context: RedHatBoyContext {
-				frame: 0,
-				pos:   Point { x: 0, y: FLOOR, },
-				vel:   Point { x: 0, y: 0, },
+				frame:       0,
+				pos:         Point { x: 0, y: FLOOR, },
+				vel:         Point { x: 0, y: 0, },
+				facing_left: false,
+				height:      0,
+				climb:       0,
 			},
 			_state:  PhantomData,
 		}
@@ -58,6 +95,10 @@ impl RedHatBoyState<Idle,> {
 	pub fn reset(&mut self,) {
 		self.context.vel = Point { x: 0, y: 0, };
 	}
+
+	pub fn jump(self, vel: Point,) -> RedHatBoyState<Jumping,> {
+		jump(self.context, vel,)
+	}
 }
 
 impl RedHatBoyState<Running,> {
@@ -72,13 +113,64 @@ impl RedHatBoyState<Running,> {
 		context.vel = Point { x: 0, y: 0, };
 		RedHatBoyState { context, _state: PhantomData, }
 	}
+
+	pub fn jump(self, vel: Point,) -> RedHatBoyState<Jumping,> {
+		jump(self.context, vel,)
+	}
+}
+
+impl RedHatBoyState<Jumping,> {
+	/// still steered through the air
+	pub fn with_velocity(mut self, vel: Point,) -> Self {
+		self.context.vel = vel;
+		self
+	}
+
+	/// whether boy is back on the ground
+	pub fn has_landed(&self,) -> bool {
+		self.context.height == 0 && self.context.climb == 0
+	}
+
+	pub fn land(self,) -> RedHatBoyState<Idle,> {
+		let mut context = self.context;
+		context.frame = 0;
+		context.vel = Point { x: 0, y: 0, };
+		RedHatBoyState { context, _state: PhantomData, }
+	}
+}
+
+/// boy leaving the ground from `context`, moving along at `vel`
+fn jump(
+	mut context: RedHatBoyContext,
+	vel: Point,
+) -> RedHatBoyState<Jumping,> {
+	context.frame = 0;
+	context.vel = vel;
+	context.climb = JUMP_SPEED;
+	RedHatBoyState { context, _state: PhantomData, }
 }
 
 impl RedHatBoyStateMachine {
+	/// move boy `by`, whatever he is doing
+	pub(super) fn pull(&mut self, by: Point,) {
+		match self {
+			Self::Idle(red_hat_boy_state,) => {
+				red_hat_boy_state.context.pos += by
+			},
+			Self::Running(red_hat_boy_state,) => {
+				red_hat_boy_state.context.pos += by
+			},
+			Self::Jumping(red_hat_boy_state,) => {
+				red_hat_boy_state.context.pos += by
+			},
+		}
+	}
+
 	pub(super) fn context(&self,) -> &RedHatBoyContext {
 		match self {
 			Self::Idle(red_hat_boy_state,) => &red_hat_boy_state.context,
 			Self::Running(red_hat_boy_state,) => &red_hat_boy_state.context,
+			Self::Jumping(red_hat_boy_state,) => &red_hat_boy_state.context,
 		}
 	}
 }
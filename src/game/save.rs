@@ -0,0 +1,169 @@
+//! what the game keeps across page loads, stored as json under [`SAVE_KEY`]
+//!
+//! every save carries the [`VERSION`] of the format it was written in.
+//! older saves are brought up to date by [`MIGRATIONS`] before they are
+//! read, so changing the format means bumping [`VERSION`] and adding the
+//! step from the last one
+
+use crate::Rslt;
+use crate::brwsr::storage::Storage;
+use anyhow::Context;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+pub const SAVE_KEY: &str = "walk-the-dog.save";
+/// version saves are written in
+pub const VERSION: u32 = 1;
+/// scores a high-score table keeps
+pub const HIGH_SCORES: usize = 5;
+
+/// turns json of a save written in some version into the next version's
+pub type Migration = fn(Value,) -> Rslt<Value,>;
+
+/// step from each version to the next, the first taking version 1 saves
+pub const MIGRATIONS: &[Migration] = &[];
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize,)]
+#[serde(default)]
+pub struct Save {
+	pub version:     u32,
+	/// best first
+	pub high_scores: Vec<HighScore,>,
+	pub settings:    Settings,
+}
+
+impl Default for Save {
+	fn default() -> Self {
+		Self {
+			version:     VERSION,
+			high_scores: vec![],
+			settings:    Settings::default(),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize,)]
+pub struct HighScore {
+	pub score:    u64,
+	/// world pixels walked
+	pub distance: f64,
+	pub seconds:  u32,
+	pub pickups:  u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize,)]
+#[serde(default)]
+pub struct Settings {
+	pub bindings:      Bindings,
+	/// from 0 for silence to 1 for full volume
+	pub volume:        f64,
+	pub accessibility: Accessibility,
+}
+
+impl Default for Settings {
+	fn default() -> Self {
+		Self {
+			bindings:      Bindings::default(),
+			volume:        0.8,
+			accessibility: Accessibility::default(),
+		}
+	}
+}
+
+/// `KeyboardEvent.code` of the key doing each action
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize,)]
+#[serde(default)]
+pub struct Bindings {
+	pub left:    String,
+	pub right:   String,
+	pub up:      String,
+	pub down:    String,
+	pub jump:    String,
+	pub pause:   String,
+	pub confirm: String,
+}
+
+impl Default for Bindings {
+	fn default() -> Self {
+		Self {
+			left:    "KeyA".into(),
+			right:   "KeyF".into(),
+			up:      "KeyD".into(),
+			down:    "KeyS".into(),
+			jump:    "Space".into(),
+			pause:   "Escape".into(),
+			confirm: "Enter".into(),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize,)]
+#[serde(default)]
+pub struct Accessibility {
+	/// no screen shake or flashes
+	pub reduce_motion: bool,
+	/// hud drawn in colors standing out against anything behind it
+	pub high_contrast: bool,
+}
+
+impl Save {
+	/// save kept in `storage`, or a fresh one if there is none yet
+	pub fn load(storage: &Storage,) -> Rslt<Self,> {
+		match storage.get(SAVE_KEY,)? {
+			Some(json,) => Self::from_json(&json, MIGRATIONS,),
+			None => Ok(Self::default(),),
+		}
+	}
+
+	pub fn store(&self, storage: &Storage,) -> Rslt<(),> {
+		storage.set(SAVE_KEY, &serde_json::to_string(self,)?,)
+	}
+
+	/// read `json` written in any version up to [`VERSION`], migrating it
+	/// through `migrations` first
+	pub fn from_json(json: &str, migrations: &[Migration],) -> Rslt<Self,> {
+		let mut value: Value =
+			serde_json::from_str(json,).context("save is not json",)?;
+		let version = value
+			.get("version",)
+			.and_then(Value::as_u64,)
+			.context("save has no version",)? as u32;
+		let latest = migrations.len() as u32 + 1;
+		if version == 0 || version > latest {
+			bail!("save version {version} is not one of 1 to {latest}");
+		}
+
+		for (i, migrate,) in
+			migrations.iter().enumerate().skip(version as usize - 1,)
+		{
+			value = migrate(value,).with_context(|| {
+				format!("failed to migrate save from version {}", i + 1)
+			},)?;
+			let Some(fields,) = value.as_object_mut() else {
+				bail!("save migrated from version {} is not an object", i + 1);
+			};
+			fields.insert("version".into(), (i as u32 + 2).into(),);
+		}
+		serde_json::from_value(value,)
+			.context("save does not match its version",)
+	}
+
+	pub fn best(&self,) -> u64 {
+		self.high_scores.first().map_or(0, |h| h.score,)
+	}
+
+	/// put `score` in the high-score table if it makes it, returning its
+	/// place from 0
+	pub fn record(&mut self, score: HighScore,) -> Option<usize,> {
+		let place =
+			self.high_scores.partition_point(|h| h.score >= score.score,);
+		if place >= HIGH_SCORES {
+			return None;
+		}
+		self.high_scores.insert(place, score,);
+		self.high_scores.truncate(HIGH_SCORES,);
+		Some(place,)
+	}
+}
@@ -0,0 +1,358 @@
+//! menus around the gameplay and the triangle demo brought over from the
+//! archived canvas experiments
+
+use crate::Rslt;
+use crate::engn::Point;
+use crate::engn::Renderer;
+use crate::engn::cache::CachedLayer;
+use crate::engn::camera::Bounds;
+use crate::engn::color::Color;
+use crate::engn::color::Hsl;
+use crate::engn::font::Align;
+use crate::engn::scene::Change;
+use crate::engn::scene::Input;
+use crate::engn::scene::Scene;
+use crate::engn::shape::Shape;
+use crate::engn::shape::ShapeStyle;
+use crate::engn::transition;
+use crate::engn::transition::Transition;
+use crate::game::IDLE_CARDS;
+use crate::game::Play;
+use crate::game::Shared;
+use crate::game::VIEW_HEIGHT;
+use crate::game::VIEW_WIDTH;
+use crate::game::dog::SIT_CARDS;
+
+const QUIT_KEY: &str = "KeyQ";
+const DEMO_KEY: &str = "KeyT";
+/// ticks each half of a fade between scenes takes
+const FADE_TICKS: u32 = 20;
+/// deepest the triangle demo recurses
+const MAX_DEPTH: u8 = 7;
+
+/// what a key with `KeyboardEvent.code` of `code` is called on screen
+fn key_label(code: &str,) -> &str {
+	match code {
+		"Escape" => "Esc",
+		_ => code
+			.strip_prefix("Key",)
+			.or_else(|| code.strip_prefix("Digit",),)
+			.unwrap_or(code,),
+	}
+}
+
+/// `change` made behind a fade through black
+fn faded(change: Change<Shared,>,) -> Change<Shared,> {
+	change.through(Transition::new(
+		transition::Style::Fade { color: Color::BLACK, },
+		FADE_TICKS,
+	),)
+}
+
+/// heading and hint lines centered on the screen, in logical pixels
+fn draw_caption(
+	rndrr: &Renderer,
+	heading: &str,
+	hint: &str,
+	color: Color,
+) -> Rslt<(),> {
+	rndrr.screen_space(|r| {
+		r.draw_label_with(
+			heading,
+			(VIEW_WIDTH / 2.0, VIEW_HEIGHT * 0.3,),
+			color,
+			48.0,
+			Align::Center,
+		)?;
+		r.draw_label_with(
+			hint,
+			(VIEW_WIDTH / 2.0, VIEW_HEIGHT * 0.7,),
+			color,
+			16.0,
+			Align::Center,
+		)
+	},)
+}
+
+/// screen darkened by `color` over whatever is below
+fn draw_dim(rndrr: &Renderer, color: Color,) -> Rslt<(),> {
+	rndrr.screen_space(|r| {
+		r.draw_shape(
+			&Shape::Rect { x: 0.0, y: 0.0, w: VIEW_WIDTH, h: VIEW_HEIGHT, },
+			&ShapeStyle::fill(color,),
+		)
+	},)
+}
+
+/// first screen, with boy idling by his dog under the game's name
+pub(super) struct Title {
+	tick: u32,
+}
+
+impl Title {
+	pub(super) fn new() -> Self {
+		Self { tick: 0, }
+	}
+}
+
+impl Scene<Shared,> for Title {
+	fn name(&self,) -> &str {
+		"Title"
+	}
+
+	fn update(
+		&mut self,
+		ctx: &mut Shared,
+		input: &Input<'_,>,
+	) -> Change<Shared,> {
+		self.tick = self.tick.wrapping_add(1,);
+		if input.just_pressed(&ctx.save.settings.bindings.confirm,) {
+			faded(Change::replace(Play::new(ctx,),),)
+		} else if input.just_pressed(DEMO_KEY,) {
+			faded(Change::push(Sierpinski::new(),),)
+		} else {
+			Change::Stay
+		}
+	}
+
+	fn draw(&self, ctx: &Shared,) -> Rslt<(),> {
+		let rndrr = &ctx.renderer;
+		draw_dim(rndrr, Color::LIGHTSKYBLUE,)?;
+		let card = (self.tick / 3) % IDLE_CARDS as u32 + 1;
+		rndrr.screen_space(|r| {
+			r.draw_sprite_sheet(
+				&ctx.sheet,
+				&format!("Idle ({card}).png"),
+				Point { x: 220, y: 250, },
+			)?;
+			let card = (self.tick / 6) % SIT_CARDS as u32 + 1;
+			r.draw_sprite_sheet(
+				&ctx.dog_sheet,
+				&format!("Sit ({card}).png"),
+				Point { x: 140, y: 304, },
+			)
+		},)?;
+		draw_caption(
+			rndrr,
+			"WALK THE DOG",
+			&format!(
+				"{}: play   T: triangles   Best {}",
+				key_label(&ctx.save.settings.bindings.confirm,),
+				ctx.save.best(),
+			),
+			Color::WHITE,
+		)
+	}
+}
+
+/// gameplay frozen under a menu
+pub(super) struct Pause;
+
+impl Scene<Shared,> for Pause {
+	fn name(&self,) -> &str {
+		"Pause"
+	}
+
+	fn update(
+		&mut self,
+		ctx: &mut Shared,
+		input: &Input<'_,>,
+	) -> Change<Shared,> {
+		if input.just_pressed(&ctx.save.settings.bindings.pause,) {
+			Change::Pop
+		} else if input.just_pressed(QUIT_KEY,) {
+			faded(Change::reset(Title::new(),),)
+		} else {
+			Change::Stay
+		}
+	}
+
+	fn draw(&self, ctx: &Shared,) -> Rslt<(),> {
+		draw_dim(&ctx.renderer, Color::BLACK.with_alpha(0.5,),)?;
+		draw_caption(
+			&ctx.renderer,
+			"PAUSED",
+			&format!(
+				"{}: resume   Q: quit to title",
+				key_label(&ctx.save.settings.bindings.pause,),
+			),
+			Color::WHITE,
+		)
+	}
+
+	fn is_overlay(&self,) -> bool {
+		true
+	}
+}
+
+/// shown over the last moment of a lost game
+pub(super) struct GameOver {
+	pub(super) score: u64,
+	pub(super) best:  u64,
+	/// place in the high-score table, from 0, if the score made it
+	pub(super) place: Option<usize,>,
+}
+
+impl Scene<Shared,> for GameOver {
+	fn name(&self,) -> &str {
+		"GameOver"
+	}
+
+	fn update(
+		&mut self,
+		ctx: &mut Shared,
+		input: &Input<'_,>,
+	) -> Change<Shared,> {
+		let bindings = &ctx.save.settings.bindings;
+		if input.just_pressed(&bindings.confirm,) {
+			faded(Change::reset(Play::new(ctx,),),)
+		} else if input.just_pressed(&bindings.pause,) {
+			faded(Change::reset(Title::new(),),)
+		} else {
+			Change::Stay
+		}
+	}
+
+	fn draw(&self, ctx: &Shared,) -> Rslt<(),> {
+		let bindings = &ctx.save.settings.bindings;
+		draw_dim(&ctx.renderer, Color::DARKRED.with_alpha(0.6,),)?;
+		draw_caption(
+			&ctx.renderer,
+			if self.place == Some(0,) { "NEW BEST" } else { "GAME OVER" },
+			&format!(
+				"Score {}   Best {}   {}: try again   {}: title",
+				self.score,
+				self.best,
+				key_label(&bindings.confirm,),
+				key_label(&bindings.pause,),
+			),
+			Color::WHITE,
+		)
+	}
+
+	fn is_overlay(&self,) -> bool {
+		true
+	}
+}
+
+/// nested triangles, redrawn only when their depth changes
+pub(super) struct Sierpinski {
+	depth: u8,
+	cache: Option<CachedLayer,>,
+}
+
+impl Sierpinski {
+	pub(super) fn new() -> Self {
+		Self { depth: 5, cache: None, }
+	}
+}
+
+impl Scene<Shared,> for Sierpinski {
+	fn name(&self,) -> &str {
+		"Sierpinski"
+	}
+
+	fn init(&mut self, ctx: &mut Shared,) -> Rslt<(),> {
+		let screen = Bounds { x: 0.0, y: 0.0, w: VIEW_WIDTH, h: VIEW_HEIGHT, };
+		self.cache = Some(CachedLayer::new(&ctx.renderer, screen,)?,);
+		Ok((),)
+	}
+
+	fn update(
+		&mut self,
+		ctx: &mut Shared,
+		input: &Input<'_,>,
+	) -> Change<Shared,> {
+		let bindings = &ctx.save.settings.bindings;
+		let depth = if input.just_pressed(&bindings.left,) {
+			self.depth.saturating_sub(1,)
+		} else if input.just_pressed(&bindings.right,) {
+			(self.depth + 1).min(MAX_DEPTH,)
+		} else {
+			self.depth
+		};
+		if depth != self.depth {
+			self.depth = depth;
+			if let Some(cache,) = self.cache.as_ref() {
+				cache.mark_dirty();
+			}
+		}
+		if input.just_pressed(&bindings.pause,) {
+			faded(Change::Pop,)
+		} else {
+			Change::Stay
+		}
+	}
+
+	fn draw(&self, ctx: &Shared,) -> Rslt<(),> {
+		let rndrr = &ctx.renderer;
+		draw_dim(rndrr, Color::BLACK,)?;
+		if let Some(cache,) = self.cache.as_ref() {
+			// margin of 40 around a triangle as wide as the screen allows
+			let side = VIEW_WIDTH - 80.0;
+			let top = (VIEW_WIDTH / 2.0, 40.0,);
+			let height = side * 3.0_f64.sqrt() / 2.0;
+			let left = (40.0, 40.0 + height,);
+			let right = (VIEW_WIDTH - 40.0, 40.0 + height,);
+			rndrr.update_cache(cache, |r| {
+				draw_sierpinski(r, [top, left, right,], 200.0, self.depth,)
+			},)?;
+			rndrr.screen_space(|r| r.draw_cached(cache,),)?;
+		}
+		let bindings = &ctx.save.settings.bindings;
+		let hint = format!(
+			"{}/{}: depth {}   {}: back",
+			key_label(&bindings.left,),
+			key_label(&bindings.right,),
+			self.depth,
+			key_label(&bindings.pause,),
+		);
+		rndrr.screen_space(|r| {
+			r.draw_label_with(
+				&hint,
+				(VIEW_WIDTH / 2.0, VIEW_HEIGHT - 30.0,),
+				Color::WHITE,
+				16.0,
+				Align::Center,
+			)
+		},)
+	}
+}
+
+type Triangle = [(f64, f64,); 3];
+
+/// fill `tri` with `hue`, then each of its corner triangles with a hue
+/// further around the wheel, `depth` levels deep. the middle keeps the
+/// parent's color
+fn draw_sierpinski(
+	rndrr: &Renderer,
+	tri: Triangle,
+	hue: f64,
+	depth: u8,
+) -> Rslt<(),> {
+	let color = Color::from_hsl(Hsl { h: hue, s: 0.7, l: 0.55, a: 1.0, },);
+	rndrr.draw_shape(
+		&Shape::Polygon(tri.to_vec(),),
+		&ShapeStyle::fill(color,),
+	)?;
+	if depth == 0 {
+		return Ok((),);
+	}
+
+	let mid = |a: (f64, f64,), b: (f64, f64,)| {
+		((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0,)
+	};
+	let [a, b, c,] = tri;
+	let (ab, bc, ca,) = (mid(a, b,), mid(b, c,), mid(c, a,),);
+	for (i, corner,) in
+		[[a, ab, ca,], [ab, b, bc,], [ca, bc, c,],].into_iter().enumerate()
+	{
+		draw_sierpinski(
+			rndrr,
+			corner,
+			hue + 40.0 + 120.0 * i as f64,
+			depth - 1,
+		)?;
+	}
+	Ok((),)
+}
@@ -0,0 +1,103 @@
+//! points for how far and how long boy keeps going, and for what he picks
+//! up on the way
+
+use crate::engn::Point;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// world pixels of progress per point
+const PIXELS_PER_POINT: f64 = 10.0;
+/// ticks survived per point
+const TICKS_PER_POINT: u32 = 60;
+/// points a pickup is worth before its combo multiplier
+const PICKUP_POINTS: u64 = 50;
+/// ticks after a pickup in which the next one extends the combo
+pub const COMBO_TICKS: u32 = 90;
+/// highest multiplier a combo reaches
+const MAX_MULTIPLIER: u32 = 5;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize,)]
+pub struct Score {
+	/// world pixels gained past the start
+	distance:    f64,
+	/// x boy set off from and the furthest x he reached, once he moved
+	#[serde(default)]
+	reach:       Option<(i16, i16,),>,
+	/// ticks survived
+	ticks:       u32,
+	pickups:     u32,
+	/// points from pickups, multipliers included
+	bonus:       u64,
+	/// pickups in a row, each within [`COMBO_TICKS`] of the last
+	combo:       u32,
+	/// ticks left to extend the combo
+	combo_timer: u32,
+}
+
+impl Score {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// one more tick survived
+	pub fn tick(&mut self,) {
+		self.ticks += 1;
+		self.combo_timer = self.combo_timer.saturating_sub(1,);
+		if self.combo_timer == 0 {
+			self.combo = 0;
+		}
+	}
+
+	/// count the walk from `from` to `to`. only ground never reached before
+	/// counts, so pacing back and forth earns nothing
+	pub fn travel(&mut self, from: Point, to: Point,) {
+		// a score from before `reach` was kept carries on from its distance
+		let start = from.x - self.distance as i16;
+		let (start, furthest,) = self.reach.get_or_insert((start, from.x,),);
+		*furthest = (*furthest).max(to.x,);
+		self.distance = f64::from(*furthest - *start,);
+	}
+
+	/// count a pickup, returning the points it was worth
+	pub fn pickup(&mut self,) -> u64 {
+		self.pickups += 1;
+		self.combo += 1;
+		self.combo_timer = COMBO_TICKS;
+		let points = PICKUP_POINTS * u64::from(self.multiplier(),);
+		self.bonus += points;
+		points
+	}
+
+	/// what the next pickup is multiplied by
+	pub fn multiplier(&self,) -> u32 {
+		self.combo.clamp(1, MAX_MULTIPLIER,)
+	}
+
+	pub fn distance(&self,) -> f64 {
+		self.distance
+	}
+
+	/// ticks survived
+	pub fn ticks(&self,) -> u32 {
+		self.ticks
+	}
+
+	/// seconds survived at 60 ticks a second
+	pub fn seconds(&self,) -> u32 {
+		self.ticks / TICKS_PER_POINT
+	}
+
+	pub fn pickups(&self,) -> u32 {
+		self.pickups
+	}
+
+	pub fn combo(&self,) -> u32 {
+		self.combo
+	}
+
+	pub fn total(&self,) -> u64 {
+		(self.distance / PIXELS_PER_POINT) as u64
+			+ u64::from(self.ticks / TICKS_PER_POINT,)
+			+ self.bonus
+	}
+}
@@ -1,5 +1,8 @@
+// everything runs on the single wasm thread, so `Send` bounds on the futures
+// returned by trait `async fn`s are never needed
+#![allow(async_fn_in_trait)]
+
 use crate::engn::GameLoop;
-use crate::game::WalkTheDog;
 use anyhow::Result as Rslt;
 use wasm_bindgen::prelude::*;
 use web_sys::console;
@@ -8,13 +11,23 @@ use web_sys::console;
 
 // this attribute enables using `log!` macro when `brwsr` module is used
 #[macro_use]
-mod brwsr;
-mod engn;
+pub mod brwsr;
+pub mod engn;
 mod game;
 
+pub use game::ANIMATION_CLIPS;
+pub use game::DOG_CLIPS;
+pub use game::Difficulty;
+pub use game::DifficultyCurves;
+pub use game::HighScore;
+pub use game::SHEET_CLIPS;
+pub use game::Save;
+pub use game::Score;
+pub use game::WalkTheDog;
+
 type JRslt<T,> = Result<T, JsValue,>;
 
-trait ContainerFixer {
+pub trait ContainerFixer {
 	type ErrFlipped;
 	type Flipped;
 	type Consumed;
@@ -1,4 +1,6 @@
-use futures::prelude::*;
+// the template placeholders below compare a literal against itself on purpose
+#![allow(clippy::eq_op)]
+
 use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::JsFuture;
 use wasm_bindgen_test::wasm_bindgen_test;
@@ -20,15 +22,14 @@ fn web_test() {
 
 // This runs a unit test in the browser, and in addition it supports
 // asynchronous Future APIs.
-#[wasm_bindgen_test(async)]
-fn async_test() -> impl Future<Item = (), Error = JsValue,> {
+#[wasm_bindgen_test]
+async fn async_test() {
 	// Creates a JavaScript Promise which will asynchronously resolve with the
 	// value 42.
 	let promise = js_sys::Promise::resolve(&JsValue::from(42,),);
 
 	// Converts that Promise into a Future.
 	// The unit test will wait for the Future to resolve.
-	JsFuture::from(promise,).map(|x| {
-		assert_eq!(x, 42);
-	},)
+	let x = JsFuture::from(promise,).await.unwrap();
+	assert_eq!(x, 42);
 }
@@ -0,0 +1,128 @@
+use rust_webpack_template::engn::Sheet;
+use rust_webpack_template::engn::atlas;
+use rust_webpack_template::engn::atlas::Frame;
+use rust_webpack_template::engn::atlas::PackOptions;
+
+/// frame filled with `color`, surrounded by `margin` transparent pixels
+fn frame(name: &str, w: u32, h: u32, margin: u32, color: u8,) -> Frame {
+	let mut rgba = vec![0; (w * h * 4) as usize];
+	for y in margin..h - margin {
+		for x in margin..w - margin {
+			let i = ((y * w + x) * 4) as usize;
+			rgba[i..i + 4].copy_from_slice(&[color, color, color, 255,],);
+		}
+	}
+	Frame { name: name.to_string(), w, h, rgba, }
+}
+
+fn frames() -> Vec<Frame,> {
+	vec![
+		frame("Run (1).png", 30, 20, 2, 10,),
+		frame("Run (2).png", 30, 20, 2, 20,),
+		frame("Idle (1).png", 16, 40, 0, 30,),
+		frame("Jump (1).png", 8, 8, 1, 40,),
+	]
+}
+
+/// rects as `(x, y, w, h)` sorted by frame name
+fn rects(sheet: &Sheet,) -> Vec<(f64, f64, f64, f64,),> {
+	sheet
+		.iter()
+		.map(|(_, s,)| {
+			let f = s.frame();
+			(f.x_f64(), f.y_f64(), f.w_f64(), f.h_f64(),)
+		},)
+		.collect()
+}
+
+fn assert_disjoint(rects: &[(f64, f64, f64, f64,)], gap: f64,) {
+	for (i, a,) in rects.iter().enumerate() {
+		for b in rects[i + 1..].iter() {
+			let apart = a.0 + a.2 + gap <= b.0
+				|| b.0 + b.2 + gap <= a.0
+				|| a.1 + a.3 + gap <= b.1
+				|| b.1 + b.3 + gap <= a.1;
+			assert!(apart, "{a:?} and {b:?} are closer than {gap}");
+		}
+	}
+}
+
+#[test]
+fn packs_without_overlap() {
+	let packed = atlas::pack(frames(), &PackOptions::default(), "out.png",)
+		.expect("failed to pack",);
+	let rects = rects(&packed.sheet,);
+	assert_eq!(rects.len(), 4);
+	assert_disjoint(&rects, 0.0,);
+	for r in rects.iter() {
+		assert!(r.0 + r.2 <= packed.w as f64 && r.1 + r.3 <= packed.h as f64);
+	}
+
+	// pixels are copied to where the sheet says they are
+	let jump =
+		packed.sheet.get("Jump (1).png",).expect("jump is missing",).frame();
+	let (x, y,) = (jump.x_f64() as u32 + 1, jump.y_f64() as u32 + 1,);
+	let i = ((y * packed.w + x) * 4) as usize;
+	assert_eq!(packed.rgba[i..i + 4], [40, 40, 40, 255]);
+}
+
+#[test]
+fn trims_and_pads() {
+	let opts = PackOptions { trim: true, padding: 2, ..Default::default() };
+	let packed =
+		atlas::pack(frames(), &opts, "out.png",).expect("failed to pack",);
+	assert_disjoint(&rects(&packed.sheet,), 2.0,);
+
+	let run = packed.sheet.get("Run (1).png",).expect("run is missing",);
+	assert_eq!((run.frame().w_f64(), run.frame().h_f64()), (26.0, 16.0));
+	assert_eq!((run.trim_offset().x, run.trim_offset().y), (2, 2));
+	let idle = packed.sheet.get("Idle (1).png",).expect("idle is missing",);
+	assert_eq!((idle.trim_offset().x, idle.trim_offset().y), (0, 0));
+}
+
+#[test]
+fn sheet_round_trips() {
+	let packed = atlas::pack(frames(), &PackOptions::default(), "out.png",)
+		.expect("failed to pack",);
+	let json =
+		serde_json::to_string(&packed.sheet,).expect("failed to serialize",);
+	let sheet: Sheet =
+		serde_json::from_str(&json,).expect("failed to deserialize",);
+	assert_eq!(rects(&sheet,), rects(&packed.sheet,));
+	assert_eq!(sheet.meta().map(|m| m.image.as_str()), Some("out.png"));
+}
+
+#[test]
+fn too_small_atlas_is_an_error() {
+	let opts = PackOptions { max_size: 32, ..Default::default() };
+	assert!(atlas::pack(frames(), &opts, "out.png",).is_err());
+}
+
+#[test]
+fn sizes_past_what_a_sheet_holds_are_errors() {
+	let opts = PackOptions { max_size: 70_000, ..Default::default() };
+	assert!(atlas::pack(frames(), &opts, "out.png",).is_err());
+	let wide = vec![frame("wide", 70_000, 1, 0, 255,)];
+	let opts = PackOptions::default();
+	assert!(atlas::pack(wide, &opts, "out.png",).is_err());
+}
+
+#[test]
+fn paddings_adding_up_past_u32_are_too_small_errors() {
+	for (padding, max_size,) in [(u32::MAX, 4096,), (40_000, 65_535,),] {
+		let opts = PackOptions { padding, max_size, ..Default::default() };
+		let Err(e,) = atlas::pack(frames(), &opts, "out.png",) else {
+			panic!("{padding} of padding fitted in {max_size}");
+		};
+		assert!(e.to_string().contains("do not fit",), "{e}");
+	}
+}
+
+#[test]
+fn reads_texture_packer_sheet() {
+	let sheet: Sheet =
+		serde_json::from_str(include_str!("../static/rhb.json"),)
+			.expect("failed to parse rhb.json",);
+	assert!(sheet.get("Idle (1).png",).is_some());
+	assert_eq!(sheet.meta().map(|m| m.image.as_str()), Some("rhb.png"));
+}
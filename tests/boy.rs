@@ -0,0 +1,55 @@
+mod common;
+
+use common::START;
+use rust_webpack_template::WalkTheDog;
+use serde_json::Value;
+
+/// particles alive in the run being played
+fn particles(game: &WalkTheDog,) -> usize {
+	let snapshot: Value =
+		serde_json::from_str(&game.snapshot().unwrap().unwrap(),).unwrap();
+	snapshot["state"]["particles"]["particles"].as_array().unwrap().len()
+}
+
+#[test]
+fn boy_lands_in_a_puff_of_dust() {
+	let mut game = common::game();
+	common::run(&mut game, &START,);
+	let mut counts = vec![];
+	common::run_with(&mut game, &[(1, &["Space",],), (40, &[],),], |game| {
+		counts.push(particles(game,),)
+	},);
+	// nothing while standing or in the air, then a puff all at once
+	let landed = counts.iter().position(|&n| n > 0,).unwrap();
+	assert!(landed > 20, "{counts:?}");
+	assert_eq!(counts[landed], 12);
+}
+
+/// whether the player faces left, as the snapshot of the run has it
+fn facing_left(game: &WalkTheDog,) -> bool {
+	let snapshot: Value =
+		serde_json::from_str(&game.snapshot().unwrap().unwrap(),).unwrap();
+	let (_, boy,) = snapshot["state"]["heroes"]["slots"][0]
+		.as_array()
+		.map(|slot| (&slot[0], &slot[1],),)
+		.unwrap();
+	let state =
+		boy["state_machine"].as_object().unwrap().values().next().unwrap();
+	state["context"]["facing_left"].as_bool().unwrap()
+}
+
+#[test]
+fn boy_faces_the_way_he_runs() {
+	let mut game = common::game();
+	common::run(&mut game, &START,);
+	assert!(!facing_left(&game,));
+	common::run(&mut game, &[(20, &["KeyF",],),],);
+	assert!(!facing_left(&game,));
+	common::run(&mut game, &[(5, &["KeyA",],),],);
+	assert!(facing_left(&game,));
+	// standing still keeps the way he last ran
+	common::run(&mut game, &[(10, &[],),],);
+	assert!(facing_left(&game,));
+	common::run(&mut game, &[(5, &["KeyF",],),],);
+	assert!(!facing_left(&game,));
+}
@@ -0,0 +1,53 @@
+use rust_webpack_template::brwsr::manifest;
+use std::path::Path;
+
+fn static_dir() -> std::path::PathBuf {
+	Path::new(env!("CARGO_MANIFEST_DIR"),).join("static",)
+}
+
+#[test]
+fn manifest_skips_blank_lines_and_comments() {
+	let listed = "# embedded\nrhb.png\n\n  rhb.json  \n# dog.png\n";
+	let entries: Vec<_,> = manifest::entries(listed,).collect();
+	assert_eq!(entries, ["rhb.png", "rhb.json"]);
+}
+
+#[test]
+fn shipped_manifest_lists_files_that_exist() {
+	let listed =
+		include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets.manifest"));
+	let table = manifest::table(listed, &static_dir(),).unwrap();
+	for entry in manifest::entries(listed,) {
+		assert!(
+			table.contains(&format!("({entry:?}, include_bytes!(")),
+			"{entry}"
+		);
+	}
+}
+
+#[test]
+fn missing_assets_are_named() {
+	let e =
+		manifest::table("rhb.png\nnowhere.png\n", &static_dir(),).unwrap_err();
+	assert!(e.contains("nowhere.png",), "{e}");
+}
+
+#[test]
+fn fetched_paths_are_looked_up_as_listed() {
+	assert_eq!(manifest::normalize("rhb.png",), "rhb.png");
+	assert_eq!(manifest::normalize("./rhb.png",), "rhb.png");
+	assert_eq!(manifest::normalize("/rhb.png",), "rhb.png");
+	assert_eq!(manifest::normalize("fonts/pixel.fnt",), "fonts/pixel.fnt");
+}
+
+#[cfg(feature = "embedded-assets")]
+#[test]
+fn embedded_assets_are_served_however_they_are_fetched() {
+	use rust_webpack_template::brwsr::bundle;
+
+	let json = std::fs::read(static_dir().join("rhb.json",),).unwrap();
+	for src in ["rhb.json", "./rhb.json", "/rhb.json",] {
+		assert_eq!(bundle::get(src,), Some(json.as_slice()), "{src}");
+	}
+	assert_eq!(bundle::get("nowhere.png",), None);
+}
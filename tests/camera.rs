@@ -0,0 +1,45 @@
+use rust_webpack_template::engn::Camera;
+use rust_webpack_template::engn::Point;
+use rust_webpack_template::engn::camera::Bounds;
+
+#[test]
+fn default_camera_maps_world_onto_pixels() {
+	let camera = Camera::new(600.0, 400.0,);
+	assert_eq!(camera.world_to_screen(10.0, 20.0,), (10.0, 20.0));
+}
+
+#[test]
+fn zoom_round_trips() {
+	let mut camera = Camera::new(600.0, 400.0,);
+	camera.snap_to(Point { x: 100, y: 100, },);
+	camera.set_zoom(2.0,);
+	assert_eq!(camera.world_to_screen(100.0, 100.0,), (300.0, 200.0));
+	assert_eq!(camera.world_to_screen(110.0, 90.0,), (320.0, 180.0));
+	assert_eq!(camera.screen_to_world(320.0, 180.0,), (110.0, 90.0));
+}
+
+#[test]
+fn dead_zone_and_smoothing() {
+	let mut camera = Camera::new(600.0, 400.0,);
+	camera.snap_to(Point { x: 0, y: 0, },);
+	camera.dead_zone = (100.0, 100.0,);
+
+	camera.follow(Point { x: 40, y: -40, },);
+	assert_eq!(camera.center(), (0.0, 0.0));
+
+	camera.smoothing = 0.5;
+	camera.follow(Point { x: 150, y: 0, },);
+	assert_eq!(camera.center(), (50.0, 0.0));
+}
+
+#[test]
+fn bounds_keep_view_inside_world() {
+	let mut camera = Camera::new(600.0, 400.0,);
+	camera.bounds = Some(Bounds { x: 0.0, y: 0.0, w: 2000.0, h: 300.0, },);
+
+	camera.follow(Point { x: -500, y: 1000, },);
+	// world is shorter than the view, so it stays vertically centered
+	assert_eq!(camera.center(), (300.0, 150.0));
+	camera.follow(Point { x: 5000, y: 0, },);
+	assert_eq!(camera.center(), (1700.0, 150.0));
+}
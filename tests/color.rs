@@ -0,0 +1,75 @@
+use rust_webpack_template::engn::color::Color;
+use rust_webpack_template::engn::color::Hsl;
+
+#[test]
+fn parses_css_forms() {
+	let seagreen = Color::rgb(46, 139, 87,);
+	assert_eq!("#2e8b57".parse::<Color>().unwrap(), seagreen);
+	assert_eq!("SeaGreen".parse::<Color>().unwrap(), seagreen);
+	assert_eq!("rgb(46, 139, 87)".parse::<Color>().unwrap(), seagreen);
+	assert_eq!("rgb(46 139 87)".parse::<Color>().unwrap(), seagreen);
+	assert_eq!("#fff".parse::<Color>().unwrap(), Color::WHITE);
+	assert_eq!(
+		"rgba(0, 0, 0, 0.6)".parse::<Color>().unwrap(),
+		Color::rgba(0, 0, 0, 153,)
+	);
+	assert_eq!(
+		"rgb(100% 0% 0% / 50%)".parse::<Color>().unwrap(),
+		Color::rgba(255, 0, 0, 128,)
+	);
+	assert_eq!("#ff000080".parse::<Color>().unwrap().a, 128);
+	assert_eq!("transparent".parse::<Color>().unwrap(), Color::TRANSPARENT);
+
+	assert!("#12345".parse::<Color>().is_err());
+	assert!("rgb(1, 2)".parse::<Color>().is_err());
+	assert!("not a color".parse::<Color>().is_err());
+}
+
+#[test]
+fn css_output_round_trips() {
+	assert_eq!(Color::SEAGREEN.to_css(), "#2e8b57");
+	assert_eq!(Color::rgba(0, 0, 0, 153,).to_css(), "rgba(0, 0, 0, 0.6)");
+	for color in [Color::BURLYWOOD, Color::rgba(1, 2, 3, 4,),] {
+		assert_eq!(color.to_css().parse::<Color>().unwrap(), color);
+	}
+
+	let json = serde_json::to_string(&Color::RED,).unwrap();
+	assert_eq!(json, "\"#ff0000\"");
+	assert_eq!(serde_json::from_str::<Color,>(&json,).unwrap(), Color::RED);
+	assert!(serde_json::from_str::<Color,>("\"nope\"",).is_err());
+}
+
+#[test]
+fn arithmetic_saturates() {
+	let bright = Color::rgb(200, 100, 50,);
+	assert_eq!(bright + bright, Color::rgb(255, 200, 100,));
+	assert_eq!(Color::rgb(10, 0, 5,) - bright, Color::BLACK);
+	assert_eq!(bright * 2.0, Color::rgb(255, 200, 100,));
+	assert_eq!((bright + Color::TRANSPARENT).a, 255);
+}
+
+#[test]
+fn blends_like_a_canvas() {
+	let half_red = Color::RED.with_alpha(0.5,);
+	assert_eq!(half_red.over(Color::BLUE, 1.0,), Color::rgb(128, 0, 127,));
+	assert_eq!(Color::RED.over(Color::BLUE, 0.0,), Color::BLUE);
+	assert_eq!(
+		Color::RED.over(Color::TRANSPARENT, 0.5,),
+		Color::rgba(255, 0, 0, 128,)
+	);
+	assert_eq!(
+		Color::BLACK.lerp(Color::WHITE, 0.5,),
+		Color::rgb(128, 128, 128,)
+	);
+}
+
+#[test]
+fn converts_through_hsl() {
+	let hsl = Color::rgb(255, 0, 0,).to_hsl();
+	assert_eq!(hsl, Hsl { h: 0.0, s: 1.0, l: 0.5, a: 1.0, });
+	for color in [Color::SEAGREEN, Color::LIGHTSKYBLUE, Color::GRAY,] {
+		assert_eq!(Color::from_hsl(color.to_hsl(),), color);
+	}
+	assert_eq!(Color::RED.lighten(0.5,), Color::WHITE);
+	assert_eq!(Color::RED.darken(0.5,), Color::BLACK);
+}
@@ -0,0 +1,64 @@
+//! game played through a software renderer with simulated keys, for the
+//! tests driving it like a player would
+
+// every test crate includes this, using only some of it
+#![allow(dead_code)]
+
+use rust_webpack_template::WalkTheDog;
+use rust_webpack_template::brwsr::storage::Storage;
+use rust_webpack_template::engn::Game;
+use rust_webpack_template::engn::Image;
+use rust_webpack_template::engn::KeyboardState;
+use rust_webpack_template::engn::Renderer;
+use std::path::Path;
+
+/// keys held for some ticks
+pub type Step = (u32, &'static [&'static str],);
+
+/// from the title into the game, with every fade finished
+pub const START: [Step; 2] = [(1, &["Enter",],), (45, &[],),];
+
+/// game set up with the shipped sprite sheets and nothing saved, on its
+/// title screen
+pub fn game() -> WalkTheDog {
+	let root = Path::new(env!("CARGO_MANIFEST_DIR"),);
+	let read = |name: &str| {
+		Image::read(
+			root.join(format!("static/{name}.png"),),
+			Some(&root.join(format!("static/{name}.json"),),),
+		)
+		.unwrap()
+	};
+	let mut game = WalkTheDog::new();
+	game.setup(
+		Renderer::software(600, 600,).unwrap(),
+		read("rhb",),
+		read("dog",),
+		Storage::memory(),
+	)
+	.unwrap();
+	game
+}
+
+/// hold the keys of each of `steps` for its ticks
+pub fn run(game: &mut WalkTheDog, steps: &[Step],) {
+	run_with(game, steps, |_| {},);
+}
+
+/// [`run`], calling `each` after every tick
+pub fn run_with(
+	game: &mut WalkTheDog,
+	steps: &[Step],
+	mut each: impl FnMut(&WalkTheDog,),
+) {
+	for (ticks, keys,) in steps {
+		let mut kb = KeyboardState::new();
+		for key in *keys {
+			kb.press(key,);
+		}
+		for _ in 0..*ticks {
+			game.update(&kb,);
+			each(game,);
+		}
+	}
+}
@@ -0,0 +1,20 @@
+use rust_webpack_template::engn::debug::RateCounter;
+use rust_webpack_template::engn::debug::query_flag;
+
+#[test]
+fn rate_counter_averages_over_a_second() {
+	let mut counter = RateCounter::default();
+	for i in 0..=60 {
+		counter.tick(i as f64 * 1000.0 / 60.0,);
+	}
+	assert!((counter.rate() - 60.0).abs() < 0.01, "{}", counter.rate());
+}
+
+#[test]
+fn url_flag() {
+	assert!(query_flag("?debug", "debug",));
+	assert!(query_flag("?lang=en&debug=1", "debug",));
+	assert!(!query_flag("?debug=false", "debug",));
+	assert!(!query_flag("?debugger", "debug",));
+	assert!(!query_flag("", "debug",));
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use common::START;
+use rust_webpack_template::DifficultyCurves;
+use rust_webpack_template::WalkTheDog;
+use rust_webpack_template::engn::keyframes::Keyframes;
+
+#[test]
+fn shipped_curves_get_harder() {
+	let curves = DifficultyCurves::builtin();
+	let (easy, hard,) = (curves.at(0.0,), curves.at(300.0,),);
+	assert!(hard.scroll_speed > easy.scroll_speed);
+}
+
+#[test]
+fn time_and_score_both_count() {
+	let curves = DifficultyCurves::from_json(
+		r#"{
+			"time_weight": 2.0,
+			"score_weight": 0.5,
+			"scroll_speed": [[0, 1], [100, 11]]
+		}"#,
+	)
+	.unwrap();
+	assert_eq!(curves.intensity(10.0, 40,), 40.0);
+	assert_eq!(curves.at(curves.intensity(10.0, 40,),).scroll_speed, 5.0);
+}
+
+#[test]
+fn broken_curves_are_refused() {
+	assert!(DifficultyCurves::from_json("{}").is_err());
+	let unsorted = r#"{
+		"time_weight": 1.0,
+		"score_weight": 0.0,
+		"scroll_speed": [[10, 1], [0, 2]]
+	}"#;
+	assert!(DifficultyCurves::from_json(unsorted).is_err());
+}
+
+#[test]
+fn play_shows_the_difficulty_in_the_overlay() {
+	let mut game = common::game();
+	assert!(game.tuning().is_empty());
+
+	// ramping up from the very start
+	common::run(&mut game, &[(1, &["Enter",],), (60 * 10, &[],),],);
+	let tuning = game.tuning();
+	let scroll =
+		tuning.iter().find(|(name, _,)| name == "scroll speed",).unwrap();
+	assert!(scroll.1.ends_with("(+0.02/s)",), "{scroll:?}");
+}
+
+/// shipped curves, with boy's pace held at `scroll_speed`
+fn paced(scroll_speed: f64,) -> DifficultyCurves {
+	let mut curves = DifficultyCurves::builtin();
+	curves.scroll_speed = Keyframes::constant(scroll_speed,);
+	curves
+}
+
+fn boy_x(game: &WalkTheDog,) -> i16 {
+	let boy = game.entities().into_iter().find(|e| e.name == "RedHatBoy",);
+	boy.unwrap().pos.x
+}
+
+#[test]
+fn boy_runs_at_the_scroll_speed() {
+	let mut game = common::game();
+	game.set_difficulty(paced(6.0,),).unwrap();
+	common::run(&mut game, &START,);
+	common::run(&mut game, &[(5, &["KeyF",],),],);
+	let from = boy_x(&game,);
+	common::run(&mut game, &[(1, &["KeyF",],),],);
+	assert_eq!(boy_x(&game,) - from, 6);
+}
+
+#[test]
+fn curves_set_mid_run_take_over_right_away() {
+	let mut game = common::game();
+	common::run(&mut game, &START,);
+	common::run(&mut game, &[(5, &["KeyF",],),],);
+	game.set_difficulty(paced(8.0,),).unwrap();
+	let from = boy_x(&game,);
+	common::run(&mut game, &[(1, &["KeyF",],),],);
+	assert_eq!(boy_x(&game,) - from, 8);
+	let tuning = game.tuning();
+	let scroll =
+		tuning.iter().find(|(name, _,)| name == "scroll speed",).unwrap();
+	assert!(scroll.1.starts_with("8.00 ",), "{scroll:?}");
+}
+
+#[test]
+fn scroll_speeds_boy_can_not_run_at_are_refused() {
+	let with_speed = |speed: &str| {
+		DifficultyCurves::from_json(&format!(
+			r#"{{
+				"time_weight": 1.0,
+				"score_weight": 0.0,
+				"scroll_speed": [[0, 3], [60, {speed}]]
+			}}"#
+		),)
+	};
+	assert!(with_speed("32",).is_ok());
+	assert!(with_speed("40000",).is_err());
+	assert!(with_speed("0",).is_err());
+	assert!(with_speed("-3",).is_err());
+
+	let mut game = common::game();
+	common::run(&mut game, &START,);
+	assert!(game.set_difficulty(paced(-3.0,),).is_err());
+	assert!(game.set_difficulty(paced(1e9,),).is_err());
+	common::run(&mut game, &[(5, &["KeyF",],),],);
+	let from = boy_x(&game,);
+	common::run(&mut game, &[(1, &["KeyF",],),],);
+	assert_eq!(boy_x(&game,) - from, 3);
+}
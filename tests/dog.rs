@@ -0,0 +1,117 @@
+mod common;
+
+use common::START;
+use common::Step;
+use rust_webpack_template::WalkTheDog;
+use rust_webpack_template::engn::debug::EntityInfo;
+
+/// game started and played until the dog is around
+fn game() -> WalkTheDog {
+	let mut game = common::game();
+	common::run(&mut game, &START,);
+	game
+}
+
+/// states the dog is in over `steps`, one per tick it is around for
+fn run(game: &mut WalkTheDog, steps: &[Step],) -> Vec<String,> {
+	let mut states = vec![];
+	common::run_with(game, steps, |game| {
+		states.extend(find(game, "Dog",).map(|dog| dog.state,),);
+	},);
+	states
+}
+
+fn find(game: &WalkTheDog, name: &str,) -> Option<EntityInfo,> {
+	game.entities().into_iter().find(|e| e.name == name,)
+}
+
+fn entity(game: &WalkTheDog, name: &str,) -> EntityInfo {
+	find(game, name,).unwrap()
+}
+
+fn dog(game: &WalkTheDog,) -> EntityInfo {
+	entity(game, "Dog",)
+}
+
+#[test]
+fn dog_sits_behind_boy_once_he_stops() {
+	let mut game = game();
+	// coins within smelling distance are fetched first
+	run(&mut game, &[(120, &["KeyF",],), (150, &[],),],);
+	let dog = dog(&game,);
+	let boy = entity(&game, "RedHatBoy",);
+	assert_eq!(dog.state, "Sit");
+	// feet 90 behind boy's, a little further back
+	assert_eq!(dog.pos.x + 50, boy.pos.x + 80 - 90);
+	assert_eq!(dog.pos.y + 76, boy.pos.y + 130 - 10);
+}
+
+#[test]
+fn dog_runs_to_catch_up() {
+	let mut game = game();
+	run(&mut game, &[(120, &["KeyF",],), (60, &[],),],);
+	// turning around leaves the dog on the wrong side of boy
+	let states = run(&mut game, &[(10, &["KeyA",],), (60, &[],),],);
+	assert!(states.iter().any(|s| s == "Run"), "{states:?}");
+	assert_eq!(states.last().unwrap(), "Sit");
+	let dog = dog(&game,);
+	let boy = entity(&game, "RedHatBoy",);
+	assert_eq!(dog.pos.x + 50, boy.pos.x + 80 + 90);
+}
+
+#[test]
+fn dog_barks_when_boy_jumps() {
+	let mut game = game();
+	let states = run(&mut game, &[(1, &["Space",],),],);
+	assert_eq!(states, ["Bark"]);
+	assert_eq!(entity(&game, "RedHatBoy",).state, "Jump");
+
+	// the bark is let out in full, then the dog settles back down
+	let states = run(&mut game, &[(60, &[],),],);
+	assert_eq!(states.iter().filter(|s| *s == "Bark").count(), 24 - 1);
+	assert_eq!(entity(&game, "RedHatBoy",).state, "Idle");
+}
+
+#[test]
+fn dog_barks_when_boy_is_hurt() {
+	let mut game = game();
+	let states = run(&mut game, &[(40, &["KeyA",],),],);
+	assert!(states.iter().any(|s| s == "Bark"), "{states:?}");
+}
+
+/// pickups collected so far, as the run's snapshot has it
+fn pickups(game: &WalkTheDog,) -> u64 {
+	let snapshot: serde_json::Value =
+		serde_json::from_str(&game.snapshot().unwrap().unwrap(),).unwrap();
+	snapshot["state"]["score"]["pickups"].as_u64().unwrap()
+}
+
+#[test]
+fn dog_fetches_pickups_dragging_boy_along() {
+	let mut game = game();
+	// the first pickup is within smelling distance ahead, but further than
+	// the leash reaches
+	run(&mut game, &[(25, &["KeyF",],),],);
+	let boy = entity(&game, "RedHatBoy",).pos;
+	assert_eq!(pickups(&game,), 0);
+
+	let states = run(&mut game, &[(90, &[],),],);
+	assert!(states.iter().any(|s| s == "Run"), "{states:?}");
+	assert_eq!(pickups(&game,), 1);
+	// standing still, but pulled towards the pickup by the leash
+	assert!(entity(&game, "RedHatBoy",).pos.x > boy.x);
+}
+
+#[test]
+fn boy_drags_a_barking_dog() {
+	let mut game = game();
+	run(&mut game, &[(40, &["KeyF",],), (30, &[],),],);
+	let from = dog(&game,).pos;
+	// the dog stays put to bark, until the leash pulls it along
+	let states =
+		run(&mut game, &[(1, &["KeyF", "Space",],), (22, &["KeyF",],),],);
+	assert!(states.iter().all(|s| s == "Bark"), "{states:?}");
+	assert!(dog(&game,).pos.x > from.x);
+	let gap = entity(&game, "RedHatBoy",).pos.x + 80 - (dog(&game,).pos.x + 50);
+	assert!(gap <= 120 + 30, "{gap}");
+}
@@ -0,0 +1,72 @@
+use rust_webpack_template::engn::color::Color;
+use rust_webpack_template::engn::effects::CameraEffects;
+use rust_webpack_template::engn::transition::Style;
+use rust_webpack_template::engn::transition::Transition;
+
+fn fade() -> Style {
+	Style::Fade { color: Color::BLACK, }
+}
+
+#[test]
+fn transition_covers_then_uncovers() {
+	let mut t = Transition::new(fade(), 4,);
+	assert_eq!(t.coverage(), 0.0);
+	let midpoints: Vec<_,> = (0..8).map(|_| t.update(),).collect();
+	assert_eq!(
+		midpoints,
+		[false, false, false, true, false, false, false, false]
+	);
+	assert!(t.is_done());
+	assert_eq!(t.coverage(), 0.0);
+}
+
+#[test]
+fn reveal_starts_covered() {
+	let mut t = Transition::reveal(fade(), 2,);
+	assert_eq!(t.coverage(), 1.0);
+	assert!(!t.update());
+	assert_eq!(t.coverage(), 0.5);
+	t.update();
+	assert!(t.is_done());
+}
+
+#[test]
+fn hit_stop_freezes_simulation() {
+	let mut fx = CameraEffects::new();
+	fx.hit_stop(2,);
+	assert!(fx.is_frozen());
+	assert!(!fx.update());
+	assert!(!fx.update());
+	assert!(fx.update());
+}
+
+#[test]
+fn shake_decays_identically() {
+	let run = || {
+		let mut fx = CameraEffects::new();
+		fx.shake(8.0, 0.5,);
+		(0..10)
+			.map(|_| {
+				fx.update();
+				fx.shake_offset()
+			},)
+			.collect::<Vec<_,>>()
+	};
+	let offsets = run();
+	assert_eq!(offsets, run());
+	assert!(offsets[0].0.abs() <= 4.0 && offsets[0].1.abs() <= 4.0);
+	assert_eq!(offsets[9], (0.0, 0.0));
+}
+
+#[test]
+fn flash_fades_out() {
+	let mut fx = CameraEffects::new();
+	fx.flash(Color::WHITE, 4,);
+	assert_eq!(fx.flash_alpha(), 1.0);
+	fx.update();
+	assert_eq!(fx.flash_alpha(), 0.75);
+	for _ in 0..3 {
+		fx.update();
+	}
+	assert_eq!(fx.flash_alpha(), 0.0);
+}
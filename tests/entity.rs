@@ -0,0 +1,87 @@
+use rust_webpack_template::engn::entity::Components;
+use rust_webpack_template::engn::entity::Entities;
+use rust_webpack_template::engn::entity::Schedule;
+use rust_webpack_template::engn::queue::RenderQueue;
+
+#[test]
+fn stale_handles_miss_reused_slots() {
+	let mut entities = Entities::new();
+	let mut names = Components::new();
+	let a = entities.spawn();
+	let b = entities.spawn();
+	names.insert(a, "a",);
+	names.insert(b, "b",);
+
+	assert!(entities.despawn(a,));
+	assert!(!entities.despawn(a,));
+	let c = entities.spawn();
+	assert_eq!(c.index(), a.index());
+	assert_ne!(c, a);
+	assert!(!entities.is_alive(a,));
+
+	// `a`'s name is still around until pruned, but `c` can not see it
+	assert_eq!(names.get(c,), None);
+	names.prune(&entities,);
+	assert_eq!(names.get(a,), None);
+	assert_eq!(names.insert(c, "c",), None);
+	assert_eq!(
+		names.iter().map(|(_, n,)| *n,).collect::<Vec<_,>>(),
+		["c", "b"]
+	);
+	assert_eq!(entities.iter().collect::<Vec<_,>>(), [c, b]);
+}
+
+#[test]
+fn components_follow_their_entity() {
+	let mut entities = Entities::new();
+	let mut hp = Components::new();
+	let e = entities.spawn();
+	assert_eq!(hp.insert(e, 3,), None);
+	assert_eq!(hp.insert(e, 5,), Some(3));
+	*hp.get_mut(e,).unwrap() -= 1;
+	assert_eq!(hp.remove(e,), Some(4));
+	assert!(hp.is_empty());
+}
+
+#[derive(Default,)]
+struct Log(Vec<&'static str,>,);
+
+#[test]
+fn systems_run_in_declared_order() {
+	let schedule = Schedule::<Log, u8,>::new()
+		.on_update("first", |log, _| log.0.push("first",),)
+		.on_update("second", |log, n| {
+			for _ in 0..*n {
+				log.0.push("second",);
+			}
+		},)
+		.on_draw("draw", |_, _| {},);
+	let mut log = Log::default();
+	schedule.update(&mut log, &2,);
+	assert_eq!(log.0, ["first", "second", "second"]);
+	assert_eq!(schedule.update_names(), ["first", "second"]);
+
+	let mut queue = RenderQueue::new();
+	schedule.draw(&log, &mut queue,);
+	assert!(queue.is_empty());
+}
+
+#[test]
+fn tampered_storage_fails_its_check() {
+	let mut entities = Entities::new();
+	let a = entities.spawn();
+	entities.spawn();
+	entities.despawn(a,);
+	assert!(entities.check().is_ok());
+
+	let json = serde_json::to_value(&entities,).unwrap();
+	let tampered = |free: serde_json::Value| {
+		let mut json = json.clone();
+		json["free"] = free;
+		serde_json::from_value::<Entities,>(json,).unwrap()
+	};
+	for free in [[9,], [1,],] {
+		assert!(tampered(free.into()).check().is_err(), "{free:?}");
+	}
+	assert!(tampered([0, 0,].into()).check().is_err());
+}
@@ -0,0 +1,136 @@
+use rust_webpack_template::engn::Image;
+use rust_webpack_template::engn::Point;
+use rust_webpack_template::engn::Renderer;
+use rust_webpack_template::engn::color::Color;
+use rust_webpack_template::engn::font::Align;
+use rust_webpack_template::engn::font::Font;
+use rust_webpack_template::engn::font::FontFace;
+use rust_webpack_template::engn::font::TextStyle;
+use rust_webpack_template::engn::software::Pixmap;
+
+const DESCRIPTOR: &str = r#"info face="Pixel Sans" size=8 bold=0 italic=0
+common lineHeight=10 base=8 scaleW=64 scaleH=64 pages=1 packed=0
+page id=0 file="pixel.png"
+chars count=4
+char id=32 x=0 y=0 width=0 height=0 xoffset=0 yoffset=0 xadvance=4 page=0 chnl=15
+char id=65 x=0 y=0 width=6 height=8 xoffset=0 yoffset=1 xadvance=7 page=0 chnl=15
+char id=86 x=8 y=0 width=6 height=8 xoffset=0 yoffset=1 xadvance=7 page=0 chnl=15
+char id=63 x=16 y=0 width=5 height=8 xoffset=1 yoffset=1 xadvance=6 page=0 chnl=15
+kernings count=1
+kerning first=65 second=86 amount=-2
+"#;
+
+fn face() -> FontFace {
+	DESCRIPTOR.parse().expect("failed to parse descriptor",)
+}
+
+fn xs(face: &FontFace, text: &str, style: &TextStyle,) -> Vec<i16,> {
+	face.layout(text, style,).iter().map(|g| g.pos.x,).collect()
+}
+
+#[test]
+fn text_descriptor() {
+	let face = face();
+	assert_eq!(face.line_height(), 10);
+	assert_eq!(face.base(), 8);
+	assert_eq!(face.kerning('A', 'V',), -2);
+	assert_eq!(face.kerning('V', 'A',), 0);
+}
+
+#[test]
+fn json_descriptor() {
+	let face: FontFace = serde_json::from_str(
+		r#"{
+			"pages": ["pixel.png"],
+			"common": { "lineHeight": 12, "base": 9 },
+			"chars": [
+				{ "id": 65, "x": 0, "y": 0, "width": 6, "height": 8,
+				  "xoffset": 0, "yoffset": 1, "xadvance": 7 }
+			]
+		}"#,
+	)
+	.expect("failed to parse descriptor",);
+	assert_eq!(face.line_height(), 12);
+	assert_eq!(face.measure("AA",), 14);
+}
+
+#[test]
+fn page_ids_are_bounded_by_common() {
+	let bad = DESCRIPTOR.replace("page id=0", "page id=4000000000",);
+	let err = bad.parse::<FontFace>().unwrap_err();
+	assert!(err.to_string().contains("page 4000000000",), "{err}");
+	let before_common = "page id=0 file=\"pixel.png\"\n";
+	assert!(before_common.parse::<FontFace>().is_err());
+}
+
+#[test]
+fn kerning_and_fallback() {
+	let face = face();
+	let style = TextStyle::default();
+	assert_eq!(xs(&face, "AV", &style,), [0, 5]);
+	// `B` is missing, so `?` is drawn with its own offset
+	assert_eq!(xs(&face, "AB", &style,), [0, 8]);
+	assert_eq!(face.measure("AVA",), 19);
+}
+
+#[test]
+fn alignment() {
+	let face = face();
+	let style = |align| TextStyle { align, ..Default::default() };
+	assert_eq!(xs(&face, "AA", &style(Align::Center,),), [-7, 0]);
+	assert_eq!(xs(&face, "AA", &style(Align::Right,),), [-14, -7]);
+}
+
+#[test]
+fn wrapping() {
+	let face = face();
+	let style =
+		TextStyle { max_width: Some(20,), line_gap: 2, ..Default::default() };
+	let placed = face.layout("AA AA\nA", &style,);
+	let pos: Vec<_,> = placed.iter().map(|g| g.pos,).collect();
+	assert_eq!(
+		pos,
+		[
+			Point { x: 0, y: 1, },
+			Point { x: 7, y: 1, },
+			Point { x: 0, y: 13, },
+			Point { x: 7, y: 13, },
+			Point { x: 0, y: 25, },
+		]
+	);
+}
+
+#[test]
+fn text_too_long_for_i16_piles_up_out_of_view() {
+	let face = face();
+	let style = TextStyle::default();
+	assert_eq!(face.measure(&"A".repeat(10_000,),), i16::MAX);
+
+	let long = face.layout(&"A".repeat(10_000,), &style,);
+	assert_eq!(long.last().map(|g| g.pos.x), Some(i16::MAX));
+	let tall = face.layout(&"A\n".repeat(10_000,), &style,);
+	assert_eq!(tall.last().map(|g| g.pos.y), Some(i16::MAX));
+	let right = TextStyle { align: Align::Right, ..TextStyle::default() };
+	let left_of = face.layout(&"A".repeat(10_000,), &right,);
+	assert_eq!(left_of[0].pos.x, -i16::MAX);
+}
+
+#[test]
+fn tinted_text_draws_in_software() {
+	// atlas of opaque white glyphs
+	let atlas = Pixmap::from_rgba(64, 64, vec![255; 64 * 64 * 4],).unwrap();
+	let font = Font::new(face(), vec![Image::from_pixmap(atlas, None,)],);
+	let rndrr = Renderer::software(32, 32,).unwrap();
+	let draw = |tint| {
+		let style = TextStyle { tint, ..TextStyle::default() };
+		let at = Point { x: 10, y: 10, };
+		rndrr.screen_space(|r| r.draw_text(&font, "A", at, &style,),).unwrap();
+		// "A" is drawn one pixel down from where its line starts
+		rndrr.snapshot().unwrap().pixel(12, 13,)
+	};
+	assert_eq!(draw(None), [255, 255, 255, 255]);
+	let red = Color::rgb(255, 0, 0,);
+	assert_eq!(draw(Some(red,)), [255, 0, 0, 255]);
+	let faded = Color::rgba(0, 0, 255, 0,);
+	assert_eq!(draw(Some(faded,)), [255, 0, 0, 255]);
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use common::Step;
+use rust_webpack_template::WalkTheDog;
+use rust_webpack_template::engn::DrawParams;
+use rust_webpack_template::engn::Game;
+use rust_webpack_template::engn::software::Pixmap;
+use rust_webpack_template::engn::software::Raster;
+use rust_webpack_template::engn::software::assert_golden;
+use rust_webpack_template::engn::software::compare;
+use std::path::Path;
+
+/// channel difference allowed against a reference, for float rounding
+const TOLERANCE: u8 = 2;
+
+/// game driven through `steps`, each holding some keys for some ticks
+fn play(steps: &[Step],) -> WalkTheDog {
+	let mut game = common::game();
+	common::run(&mut game, steps,);
+	game
+}
+
+/// frame drawn after `steps`
+fn frame(steps: &[Step],) -> Pixmap {
+	let game = play(steps,);
+	game.draw();
+	game.renderer().unwrap().snapshot().unwrap()
+}
+
+/// from the title into the game, with every fade finished
+const START: Step = (1, &["Enter",],);
+const SETTLE: Step = (45, &[],);
+
+fn golden(name: &str,) -> std::path::PathBuf {
+	Path::new(env!("CARGO_MANIFEST_DIR"),).join("tests/golden",).join(name,)
+}
+
+#[test]
+fn title_matches_reference() {
+	assert_golden(golden("title.png",), &frame(&[SETTLE,],), TOLERANCE,)
+		.unwrap();
+}
+
+#[test]
+fn standing_still_matches_reference() {
+	assert_golden(
+		golden("idle.png",),
+		&frame(&[START, SETTLE, SETTLE,],),
+		TOLERANCE,
+	)
+	.unwrap();
+}
+
+#[test]
+fn running_right_matches_reference() {
+	assert_golden(
+		golden("run.png",),
+		&frame(&[START, SETTLE, (45, &["KeyF",],),],),
+		TOLERANCE,
+	)
+	.unwrap();
+}
+
+#[test]
+fn pause_matches_reference() {
+	assert_golden(
+		golden("pause.png",),
+		&frame(&[START, SETTLE, (1, &["Escape",],), (5, &[],),],),
+		TOLERANCE,
+	)
+	.unwrap();
+}
+
+#[test]
+fn fade_in_covers_first_frame() {
+	let first = frame(&[],);
+	assert_eq!(first.pixel(300, 300,), [0, 0, 0, 255]);
+}
+
+#[test]
+fn walking_off_the_world_ends_the_game() {
+	// the first hero lost still leaves two
+	let game = play(&[START, SETTLE, (40, &["KeyA",],),],);
+	assert_eq!(game.scene_names(), ["Play"]);
+
+	let game = play(&[START, SETTLE, (120, &["KeyA",],),],);
+	assert_eq!(game.scene_names(), ["Play", "GameOver"]);
+
+	let game =
+		play(&[START, SETTLE, (120, &["KeyA",],), (1, &["Enter",],), SETTLE,],);
+	assert_eq!(game.scene_names(), ["Play"]);
+}
+
+#[test]
+fn flipped_image_lands_mirrored() {
+	// left half red, right half blue
+	let mut rgba = vec![];
+	for _ in 0..2 {
+		rgba.extend([255, 0, 0, 255, 0, 0, 255, 255,],);
+	}
+	let image = Pixmap::from_rgba(2, 2, rgba,).unwrap();
+	let raster = Raster::new(4, 2,);
+	raster.draw_image(
+		&image,
+		[0.0, 0.0, 2.0, 2.0,],
+		[1.0, 0.0, 2.0, 2.0,],
+		&DrawParams { flip_x: true, ..DrawParams::default() },
+	);
+	let px = raster.pixmap();
+	assert_eq!(px.pixel(0, 0,), [0, 0, 0, 0]);
+	assert_eq!(px.pixel(1, 0,), [0, 0, 255, 255]);
+	assert_eq!(px.pixel(2, 1,), [255, 0, 0, 255]);
+	assert_eq!(px.pixel(3, 1,), [0, 0, 0, 0]);
+}
+
+#[test]
+fn comparison_respects_tolerance() {
+	let expected =
+		Pixmap::from_rgba(2, 1, vec![10, 10, 10, 255, 0, 0, 0, 255],).unwrap();
+	let actual =
+		Pixmap::from_rgba(2, 1, vec![12, 10, 10, 255, 0, 90, 0, 255],).unwrap();
+
+	let cmp = compare(&actual, &expected, 2,).unwrap();
+	assert_eq!(cmp.mismatched, 1);
+	assert_eq!(cmp.max_delta, 90);
+	assert_eq!(cmp.diff.pixel(1, 0,), [255, 0, 0, 255]);
+	assert_ne!(cmp.diff.pixel(0, 0,), [255, 0, 0, 255]);
+
+	assert!(compare(&actual, &Pixmap::new(1, 1,), 2,).is_err());
+}
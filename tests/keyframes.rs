@@ -0,0 +1,33 @@
+use rust_webpack_template::engn::keyframes::Keyframes;
+
+#[test]
+fn values_run_straight_between_points_and_flat_outside() {
+	let curve =
+		Keyframes::new(vec![(0.0, 1.0,), (10.0, 3.0,), (20.0, 3.0,)],).unwrap();
+	assert_eq!(curve.at(-5.0,), 1.0);
+	assert_eq!(curve.at(0.0,), 1.0);
+	assert_eq!(curve.at(5.0,), 2.0);
+	assert_eq!(curve.at(10.0,), 3.0);
+	assert_eq!(curve.at(15.0,), 3.0);
+	assert_eq!(curve.at(99.0,), 3.0);
+	assert_eq!(Keyframes::constant(4.0,).at(7.0,), 4.0);
+}
+
+#[test]
+fn keyframes_read_as_pairs() {
+	let curve: Keyframes = serde_json::from_str("[[0, 2], [4, 6]]",).unwrap();
+	assert_eq!(curve.points(), [(0.0, 2.0,), (4.0, 6.0,)]);
+	assert_eq!(curve.at(1.0,), 3.0);
+	assert_eq!(
+		serde_json::to_string(&curve,).unwrap(),
+		"[[0.0,2.0],[4.0,6.0]]"
+	);
+}
+
+#[test]
+fn bad_keyframes_are_refused() {
+	assert!(Keyframes::new(vec![]).is_err());
+	assert!(Keyframes::new(vec![(5.0, 0.0,), (5.0, 1.0,)],).is_err());
+	assert!(serde_json::from_str::<Keyframes,>("[[3, 0], [1, 1]]",).is_err());
+	assert!(Keyframes::new(vec![(0.0, f64::NAN,)],).is_err());
+}
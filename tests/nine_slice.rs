@@ -0,0 +1,60 @@
+use rust_webpack_template::engn::Sheet;
+use rust_webpack_template::engn::camera::Bounds;
+use rust_webpack_template::engn::nine_slice::Fill;
+use rust_webpack_template::engn::nine_slice::Insets;
+use rust_webpack_template::engn::nine_slice::slices;
+
+const SRC: Bounds = Bounds { x: 100.0, y: 0.0, w: 30.0, h: 30.0, };
+const INSETS: Insets =
+	Insets { left: 10, top: 10, right: 10, bottom: 10, };
+
+fn bounds(x: f64, y: f64, w: f64, h: f64,) -> Bounds {
+	Bounds { x, y, w, h, }
+}
+
+#[test]
+fn stretch_keeps_corners() {
+	let dest = bounds(0.0, 0.0, 100.0, 50.0,);
+	let patches = slices(SRC, INSETS, dest, Fill::Stretch, Fill::Stretch,);
+	assert_eq!(patches.len(), 9);
+	assert_eq!(patches[0].dest, bounds(0.0, 0.0, 10.0, 10.0));
+	assert_eq!(patches[4].src, bounds(110.0, 10.0, 10.0, 10.0));
+	assert_eq!(patches[4].dest, bounds(10.0, 10.0, 80.0, 30.0));
+	assert_eq!(patches[8].dest, bounds(90.0, 40.0, 10.0, 10.0));
+}
+
+#[test]
+fn tiled_edges_repeat_at_own_size() {
+	let dest = bounds(0.0, 0.0, 45.0, 30.0,);
+	let patches = slices(SRC, INSETS, dest, Fill::Tile, Fill::Stretch,);
+	// top edge spans 25 pixels with a 10 pixel slice
+	let top: Vec<_,> = patches
+		.iter()
+		.filter(|p| p.dest.y == 0.0 && p.dest.x >= 10.0 && p.dest.x < 35.0,)
+		.map(|p| p.dest.w,)
+		.collect();
+	assert_eq!(top, [10.0, 10.0, 5.0]);
+}
+
+#[test]
+fn borders_shrink_to_fit() {
+	let dest = bounds(0.0, 0.0, 10.0, 10.0,);
+	let patches = slices(SRC, INSETS, dest, Fill::Stretch, Fill::Stretch,);
+	assert_eq!(patches.len(), 4);
+	assert_eq!(patches[3].dest, bounds(5.0, 5.0, 5.0, 5.0));
+}
+
+#[test]
+fn insets_come_from_sheet_frame() {
+	let sheet: Sheet = serde_json::from_str(
+		r#"{"frames": {"panel.png": {
+			"frame": {"x": 0, "y": 0, "w": 30, "h": 30},
+			"nineSlice": {"left": 8, "top": 8, "right": 8, "bottom": 12}
+		}}}"#,
+	)
+	.unwrap();
+	assert_eq!(
+		sheet.get("panel.png",).unwrap().nine_slice(),
+		Some(Insets { left: 8, top: 8, right: 8, bottom: 12, })
+	);
+}
@@ -0,0 +1,88 @@
+use rust_webpack_template::engn::color::Color;
+use rust_webpack_template::engn::particle::Curve;
+use rust_webpack_template::engn::particle::Emitter;
+use rust_webpack_template::engn::particle::Look;
+use rust_webpack_template::engn::particle::ParticleKind;
+use rust_webpack_template::engn::particle::ParticleSystem;
+
+fn spark() -> ParticleKind {
+	ParticleKind {
+		lifetime: (10, 10,),
+		speed:    (1.0, 1.0,),
+		angle:    (0.0, 0.0,),
+		gravity:  0.5,
+		alpha:    Curve::linear(1.0, 0.0,),
+		scale:    Curve::constant(1.0,),
+		look:     Look::Circle { radius: 2.0, color: Color::WHITE, },
+	}
+}
+
+#[test]
+fn particles_fall_and_expire() {
+	let mut system = ParticleSystem::new(8, 1,);
+	let spark = system.add_kind(spark(),);
+	system.burst(spark, (0.0, 0.0,), 3,);
+	assert_eq!(system.len(), 3);
+
+	system.update();
+	system.update();
+	let p = system.particles()[0];
+	assert_eq!(p.pos, (2.0, 1.5));
+	assert_eq!(system.kind(spark,).alpha.at(p.progress(),), 0.8);
+
+	for _ in 0..8 {
+		system.update();
+	}
+	assert!(system.is_empty());
+}
+
+#[test]
+fn pool_never_grows() {
+	let mut system = ParticleSystem::new(4, 1,);
+	let spark = system.add_kind(spark(),);
+	system.burst(spark, (0.0, 0.0,), 10,);
+	assert_eq!(system.len(), 4);
+}
+
+#[test]
+fn emitter_carries_fractions() {
+	let mut system = ParticleSystem::new(16, 1,);
+	let spark = system.add_kind(spark(),);
+	let mut emitter = Emitter::new(spark, 0.5,);
+	emitter.update(&mut system, (0.0, 0.0,),);
+	assert_eq!(system.len(), 0);
+
+	emitter.active = true;
+	for _ in 0..4 {
+		emitter.update(&mut system, (0.0, 0.0,),);
+	}
+	assert_eq!(system.len(), 2);
+}
+
+#[test]
+fn same_seed_same_effect() {
+	let run = || {
+		let mut kind = spark();
+		kind.speed = (0.0, 5.0,);
+		kind.angle = (0.0, 6.0,);
+		let mut system = ParticleSystem::new(16, 42,);
+		let id = system.add_kind(kind,);
+		system.burst(id, (0.0, 0.0,), 16,);
+		system.update();
+		system.particles().to_vec()
+	};
+	assert_eq!(run(), run());
+}
+
+#[test]
+fn particles_of_unknown_kinds_fail_the_check() {
+	let mut system = ParticleSystem::new(8, 1,);
+	let spark = system.add_kind(spark(),);
+	system.burst(spark, (0.0, 0.0,), 1,);
+	assert!(system.check().is_ok());
+
+	let mut json = serde_json::to_value(&system,).unwrap();
+	json["particles"][0]["kind"] = 7.into();
+	let tampered: ParticleSystem = serde_json::from_value(json,).unwrap();
+	assert!(tampered.check().is_err());
+}
@@ -0,0 +1,132 @@
+use rust_webpack_template::engn::DrawParams;
+use rust_webpack_template::engn::Image;
+use rust_webpack_template::engn::Point;
+use rust_webpack_template::engn::Renderer;
+use rust_webpack_template::engn::camera::Bounds;
+use rust_webpack_template::engn::color::Color;
+use rust_webpack_template::engn::queue::Command;
+use rust_webpack_template::engn::queue::FrameStats;
+use rust_webpack_template::engn::queue::Layer;
+use rust_webpack_template::engn::queue::RenderQueue;
+use rust_webpack_template::engn::queue::intersection;
+use rust_webpack_template::engn::shape::Shape;
+use rust_webpack_template::engn::shape::ShapeStyle;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const RED: Color = Color::rgb(255, 0, 0,);
+const VIEW: Bounds = Bounds { x: 0.0, y: 0.0, w: 100.0, h: 100.0, };
+
+/// 10x10 square at `(x, 0)`
+fn square(x: f64,) -> Command<'static,> {
+	Command::Primitive {
+		shape: Shape::Rect { x, y: 0.0, w: 10.0, h: 10.0, },
+		style: ShapeStyle::fill(RED,),
+	}
+}
+
+fn bounds_of(cmd: &Command<'_,>,) -> Option<Bounds,> {
+	match cmd {
+		Command::Primitive { shape, .. } => Some(shape.bounds(0.0,),),
+		_ => None,
+	}
+}
+
+/// layer, z and x of every command left to draw, in order
+fn drawn(queue: RenderQueue<'_,>,) -> (Vec<(Layer, i32, f64,),>, FrameStats,) {
+	let (queued, stats,) = queue.cull(&VIEW, bounds_of,);
+	let order = queued
+		.iter()
+		.map(|q| match &q.cmd {
+			Command::Primitive { shape: Shape::Rect { x, .. }, .. } => {
+				(q.layer, q.z, *x,)
+			},
+			_ => unreachable!("only squares are queued"),
+		},)
+		.collect();
+	(order, stats,)
+}
+
+#[test]
+fn draws_by_layer_then_z_then_submission() {
+	let mut queue = RenderQueue::new();
+	queue.push(Layer::World, 1, square(30.0,),);
+	queue.push(Layer::Hud, -5, square(50.0,),);
+	queue.push(Layer::World, 0, square(20.0,),);
+	queue.push(Layer::World, 1, square(40.0,),);
+	queue.push(Layer::Background, 9, square(10.0,),);
+	let (order, _,) = drawn(queue,);
+	assert_eq!(
+		order,
+		[
+			(Layer::Background, 9, 10.0,),
+			(Layer::World, 0, 20.0,),
+			(Layer::World, 1, 30.0,),
+			(Layer::World, 1, 40.0,),
+			(Layer::Hud, -5, 50.0,),
+		]
+	);
+}
+
+#[test]
+fn culls_world_commands_out_of_view_and_counts_the_frame() {
+	let mut queue = RenderQueue::new();
+	queue.push(Layer::World, 0, square(10.0,),);
+	queue.push(Layer::World, 0, square(500.0,),);
+	// touching the view from outside is still out of it
+	queue.push(Layer::Background, 0, square(-10.0,),);
+	// screen space is never culled, even when drawn off screen
+	queue.push(Layer::Hud, 0, square(500.0,),);
+	let (order, stats,) = drawn(queue,);
+	assert_eq!(order, [(Layer::World, 0, 10.0,), (Layer::Hud, 0, 500.0,),]);
+	assert_eq!(
+		stats,
+		FrameStats {
+			submitted: 4,
+			drawn:     2,
+			culled:    2,
+			batches:   0,
+			per_layer: BTreeMap::from([(Layer::World, 1,), (Layer::Hud, 1,),],),
+		}
+	);
+}
+
+#[test]
+fn sprites_flipped_into_view_are_not_culled() {
+	let root = Path::new(env!("CARGO_MANIFEST_DIR"),);
+	let sheet = Image::read(
+		root.join("static/rhb.png",),
+		Some(&root.join("static/rhb.json",),),
+	)
+	.unwrap();
+	let rndrr = Renderer::software(200, 200,).unwrap();
+	// 160 wide, mirrored around a pivot 10 past its right edge
+	let params = DrawParams {
+		flip_x: true,
+		pivot: Some((170.0, 68.0,),),
+		..Default::default()
+	};
+	let mut queue = RenderQueue::new();
+	for x in [-185, -400,] {
+		let pos = Point { x, y: 0, };
+		queue.sprite(Layer::World, 0, &sheet, "Idle (1).png", pos, params,);
+	}
+	rndrr.flush(queue,).expect("failed to flush",);
+	let stats = rndrr.frame_stats();
+	assert_eq!((stats.drawn, stats.culled,), (1, 1,));
+	let px = rndrr.snapshot().unwrap();
+	let drawn = (0..200).any(|x| (0..136).any(|y| px.pixel(x, y,)[3] != 0,),);
+	assert!(drawn, "the mirrored sprite left no pixels on screen");
+}
+
+#[test]
+fn intersection_is_the_shared_area() {
+	let a = Bounds { x: 0.0, y: 0.0, w: 100.0, h: 50.0, };
+	let b = Bounds { x: 60.0, y: -10.0, w: 100.0, h: 30.0, };
+	assert_eq!(
+		intersection(&a, &b,),
+		Some(Bounds { x: 60.0, y: 0.0, w: 40.0, h: 20.0, })
+	);
+	let far = Bounds { x: 200.0, y: 0.0, w: 10.0, h: 10.0, };
+	assert_eq!(intersection(&a, &far,), None);
+}
@@ -0,0 +1,37 @@
+use rust_webpack_template::engn::rope::Rope;
+
+#[test]
+fn slack_rope_pulls_nothing() {
+	let rope = Rope::new(100.0,);
+	assert!(!rope.is_taut((0.0, 0.0,), (60.0, 70.0,),));
+	assert_eq!(rope.solve((0.0, 0.0,), (60.0, 70.0,), 1.0, 1.0,), None);
+	assert!(rope.is_taut((0.0, 0.0,), (100.0, 0.0,),));
+}
+
+#[test]
+fn taut_rope_pulls_lighter_end_further() {
+	let rope = Rope::new(100.0,);
+	let (a, b,) = ((0.0, 0.0,), (130.0, 0.0,),);
+	// three times as heavy, so a quarter of the excess
+	let (da, db,) = rope.solve(a, b, 3.0, 1.0,).unwrap();
+	assert_eq!(da, (7.5, 0.0,));
+	assert_eq!(db, (-22.5, 0.0,));
+	let (a, b,) = ((a.0 + da.0, a.1 + da.1,), (b.0 + db.0, b.1 + db.1,),);
+	assert_eq!(b.0 - a.0, 100.0);
+}
+
+#[test]
+fn rope_hangs_under_its_slack() {
+	let rope = Rope::new(100.0,);
+	let curve = rope.curve((0.0, 0.0,), (80.0, 0.0,), 4,);
+	assert_eq!(curve.len(), 5);
+	assert_eq!(curve[0], (0.0, 0.0,));
+	assert_eq!(curve[4], (80.0, 0.0,));
+	// sqrt(3 * 80 * 20 / 8)
+	assert_eq!(curve[2], (40.0, 600.0_f64.sqrt(),));
+
+	// straight once taut, and half its length down with both ends together
+	let straight = rope.curve((0.0, 0.0,), (100.0, 0.0,), 4,);
+	assert!(straight.iter().all(|&(_, y,)| y == 0.0));
+	assert_eq!(rope.sag((5.0, 5.0,), (5.0, 5.0,),), 50.0);
+}
@@ -0,0 +1,93 @@
+use rust_webpack_template::HighScore;
+use rust_webpack_template::Save;
+use rust_webpack_template::brwsr::storage::Storage;
+use serde_json::Value;
+use serde_json::json;
+
+#[test]
+fn memory_storage_round_trips() {
+	let storage = Storage::local();
+	// no browser here, so values stay in memory
+	assert!(!storage.is_persistent());
+	assert_eq!(storage.get("k",).unwrap(), None);
+	storage.set("k", "v",).unwrap();
+	assert_eq!(storage.get("k",).unwrap().as_deref(), Some("v"));
+	storage.remove("k",).unwrap();
+	assert_eq!(storage.get("k",).unwrap(), None);
+}
+
+#[test]
+fn save_survives_storage() {
+	let storage = Storage::memory();
+	assert_eq!(Save::load(&storage,).unwrap(), Save::default());
+
+	let mut save = Save::default();
+	save.settings.volume = 0.25;
+	save.settings.bindings.left = "ArrowLeft".into();
+	save.settings.accessibility.reduce_motion = true;
+	save.store(&storage,).unwrap();
+	assert_eq!(Save::load(&storage,).unwrap(), save);
+}
+
+#[test]
+fn high_scores_stay_sorted_and_short() {
+	let mut save = Save::default();
+	let score = |score| HighScore { score, ..Default::default() };
+	for s in [30, 10, 50, 20, 40,] {
+		save.record(score(s,),);
+	}
+	assert_eq!(save.record(score(5,),), None);
+	assert_eq!(save.record(score(45,),), Some(1));
+	let scores: Vec<_,> = save.high_scores.iter().map(|h| h.score,).collect();
+	assert_eq!(scores, [50, 45, 40, 30, 20]);
+	assert_eq!(save.best(), 50);
+}
+
+#[test]
+fn missing_fields_take_defaults() {
+	let save =
+		Save::from_json(r#"{"version":1,"settings":{"volume":0.5}}"#, &[],)
+			.unwrap();
+	assert_eq!(save.settings.volume, 0.5);
+	assert_eq!(save.settings.bindings, Save::default().settings.bindings);
+	assert!(save.high_scores.is_empty());
+}
+
+#[test]
+fn unknown_versions_are_refused() {
+	assert!(Save::from_json(r#"{"version":9}"#, &[],).is_err());
+	assert!(Save::from_json(r#"{"high_scores":[]}"#, &[],).is_err());
+	assert!(Save::from_json("not json", &[],).is_err());
+}
+
+#[test]
+fn old_saves_are_migrated_in_order() {
+	// version 1 kept bare scores, version 2 renamed `sound` to `volume`
+	let migrations: &[fn(Value,) -> anyhow::Result<Value,>] = &[
+		|mut v| {
+			let scores = v["scores"].take();
+			v["high_scores"] = scores
+				.as_array()
+				.into_iter()
+				.flatten()
+				.map(
+					|s| json!({ "score": s, "distance": 0.0, "seconds": 0, "pickups": 0 }),
+				)
+				.collect();
+			Ok(v,)
+		},
+		|mut v| {
+			v["settings"]["volume"] = v["settings"]["sound"].take();
+			Ok(v,)
+		},
+	];
+	let v1 = r#"{"version":1,"scores":[70,20],"settings":{"sound":0.1}}"#;
+	let save = Save::from_json(v1, migrations,).unwrap();
+	assert_eq!(save.version, 3);
+	assert_eq!(save.best(), 70);
+	assert_eq!(save.settings.volume, 0.1);
+
+	// a version 2 save only takes the second step
+	let v2 = r#"{"version":2,"settings":{"sound":0.3}}"#;
+	assert_eq!(Save::from_json(v2, migrations,).unwrap().settings.volume, 0.3);
+}
@@ -0,0 +1,135 @@
+use rust_webpack_template::engn::KeyboardState;
+use rust_webpack_template::engn::Renderer;
+use rust_webpack_template::engn::color::Color;
+use rust_webpack_template::engn::scene::Change;
+use rust_webpack_template::engn::scene::Input;
+use rust_webpack_template::engn::scene::Scene;
+use rust_webpack_template::engn::scene::SceneStack;
+use rust_webpack_template::engn::transition::Style;
+use rust_webpack_template::engn::transition::Transition;
+
+/// renderer plus a log of what scenes did
+struct Ctx {
+	renderer: Renderer,
+	log:      Vec<String,>,
+}
+
+impl AsRef<Renderer,> for Ctx {
+	fn as_ref(&self,) -> &Renderer {
+		&self.renderer
+	}
+}
+
+/// scene which logs its calls and does what the pressed key says
+struct Logged {
+	name:    &'static str,
+	overlay: bool,
+}
+
+fn scene(name: &'static str,) -> Logged {
+	Logged { name, overlay: false, }
+}
+
+fn overlay(name: &'static str,) -> Logged {
+	Logged { name, overlay: true, }
+}
+
+impl Scene<Ctx,> for Logged {
+	fn name(&self,) -> &str {
+		self.name
+	}
+
+	fn init(&mut self, ctx: &mut Ctx,) -> anyhow::Result<(),> {
+		ctx.log.push(format!("init {}", self.name),);
+		Ok((),)
+	}
+
+	fn update(&mut self, ctx: &mut Ctx, input: &Input<'_,>,) -> Change<Ctx,> {
+		ctx.log.push(format!("update {}", self.name),);
+		if input.just_pressed("KeyP",) {
+			Change::push(overlay("menu",),)
+		} else if input.just_pressed("KeyO",) {
+			Change::Pop
+		} else if input.just_pressed("KeyR",) {
+			Change::replace(scene("next",),)
+		} else if input.just_pressed("KeyF",) {
+			Change::reset(scene("faded",),).through(Transition::new(
+				Style::Fade { color: Color::BLACK, },
+				2,
+			),)
+		} else {
+			Change::Stay
+		}
+	}
+
+	fn draw(&self, _ctx: &Ctx,) -> anyhow::Result<(),> {
+		Ok((),)
+	}
+
+	fn is_overlay(&self,) -> bool {
+		self.overlay
+	}
+}
+
+fn stack() -> (SceneStack<Ctx,>, Ctx,) {
+	let mut ctx =
+		Ctx { renderer: Renderer::software(8, 8,).unwrap(), log: vec![], };
+	let mut stack = SceneStack::new();
+	stack.apply(&mut ctx, Change::push(scene("game",),),).unwrap();
+	(stack, ctx,)
+}
+
+fn keys(pressed: &[&str],) -> KeyboardState {
+	let mut kb = KeyboardState::new();
+	for key in pressed {
+		kb.press(key,);
+	}
+	kb
+}
+
+#[test]
+fn only_the_top_scene_updates() {
+	let (mut stack, mut ctx,) = stack();
+	stack.update(&mut ctx, &keys(&["KeyP",],),).unwrap();
+	assert_eq!(stack.names(), ["game", "menu"]);
+
+	ctx.log.clear();
+	stack.update(&mut ctx, &keys(&["KeyP",],),).unwrap();
+	// a held key is not pressed again
+	assert_eq!(stack.names(), ["game", "menu"]);
+	assert_eq!(ctx.log, ["update menu"]);
+
+	stack.update(&mut ctx, &keys(&["KeyO",],),).unwrap();
+	assert_eq!(stack.names(), ["game"]);
+}
+
+#[test]
+fn replace_initializes_the_new_scene() {
+	let (mut stack, mut ctx,) = stack();
+	stack.update(&mut ctx, &keys(&["KeyR",],),).unwrap();
+	assert_eq!(stack.names(), ["next"]);
+	assert_eq!(ctx.log, ["init game", "update game", "init next"]);
+}
+
+#[test]
+fn transitioned_change_waits_for_full_cover() {
+	let (mut stack, mut ctx,) = stack();
+	stack.update(&mut ctx, &keys(&["KeyP",],),).unwrap();
+	stack.update(&mut ctx, &keys(&["KeyF",],),).unwrap();
+	assert!(stack.is_transitioning());
+	assert_eq!(stack.names(), ["game", "menu"]);
+
+	// covering: the menu is frozen
+	ctx.log.clear();
+	stack.update(&mut ctx, &keys(&[],),).unwrap();
+	assert_eq!(stack.names(), ["game", "menu"]);
+	assert!(ctx.log.is_empty());
+
+	// fully covered: everything is swapped for the new scene
+	stack.update(&mut ctx, &keys(&[],),).unwrap();
+	assert_eq!(stack.names(), ["faded"]);
+
+	stack.update(&mut ctx, &keys(&[],),).unwrap();
+	stack.update(&mut ctx, &keys(&[],),).unwrap();
+	assert!(!stack.is_transitioning());
+}
@@ -0,0 +1,53 @@
+use rust_webpack_template::Score;
+use rust_webpack_template::engn::Point;
+
+#[test]
+fn distance_and_time_add_up() {
+	let mut score = Score::new();
+	score.travel(Point { x: 10, y: 0, }, Point { x: 40, y: 40, },);
+	score.travel(Point { x: 40, y: 40, }, Point { x: 0, y: 40, },);
+	score.travel(Point { x: 0, y: 40, }, Point { x: 90, y: 0, },);
+	for _ in 0..120 {
+		score.tick();
+	}
+	assert_eq!(score.distance(), 80.0);
+	assert_eq!(score.seconds(), 2);
+	assert_eq!(score.total(), 8 + 2);
+}
+
+#[test]
+fn pacing_in_place_earns_nothing() {
+	let mut score = Score::new();
+	let (here, there,) = (Point { x: 100, y: 0, }, Point { x: 107, y: 7, },);
+	score.travel(here, there,);
+	for _ in 0..100 {
+		score.travel(there, here,);
+		score.travel(here, there,);
+	}
+	assert_eq!(score.distance(), 7.0);
+}
+
+#[test]
+fn quick_pickups_build_a_combo() {
+	let mut score = Score::new();
+	assert_eq!(score.pickup(), 50);
+	score.tick();
+	assert_eq!(score.pickup(), 100);
+	assert_eq!(score.multiplier(), 2);
+
+	// too slow, so the combo starts over
+	for _ in 0..90 {
+		score.tick();
+	}
+	assert_eq!(score.combo(), 0);
+	assert_eq!(score.pickup(), 50);
+	assert_eq!(score.pickups(), 3);
+	assert_eq!(score.total(), 200 + 1);
+}
+
+#[test]
+fn multiplier_is_capped() {
+	let mut score = Score::new();
+	let points: Vec<_,> = (0..7).map(|_| score.pickup(),).collect();
+	assert_eq!(points, [50, 100, 150, 200, 250, 250, 250]);
+}
@@ -0,0 +1,38 @@
+use rust_webpack_template::engn::camera::Bounds;
+use rust_webpack_template::engn::color::Color;
+use rust_webpack_template::engn::shape::Shape;
+use rust_webpack_template::engn::shape::ShapeStyle;
+
+#[test]
+fn bounds_grow_by_half_the_stroke() {
+	let rect = Shape::Rect { x: 10.0, y: 20.0, w: 30.0, h: 40.0, };
+	assert_eq!(
+		rect.bounds(0.0,),
+		Bounds { x: 10.0, y: 20.0, w: 30.0, h: 40.0, }
+	);
+	assert_eq!(
+		rect.bounds(4.0,),
+		Bounds { x: 8.0, y: 18.0, w: 34.0, h: 44.0, }
+	);
+}
+
+#[test]
+fn polyline_bounds_cover_every_vertex() {
+	let line = Shape::Polyline(vec![(5.0, 5.0,), (-5.0, 20.0,), (15.0, 0.0,)],);
+	assert_eq!(
+		line.bounds(0.0,),
+		Bounds { x: -5.0, y: 0.0, w: 20.0, h: 20.0, }
+	);
+	assert_eq!(
+		Shape::Polygon(vec![]).bounds(0.0,),
+		Bounds { x: 0.0, y: 0.0, w: 0.0, h: 0.0, }
+	);
+}
+
+#[test]
+fn style_builders() {
+	let style = ShapeStyle::stroke(Color::RED, 2.0,).dashed([4.0, 2.0,],);
+	assert_eq!(style.fill, None);
+	assert_eq!(style.stroke, Some(Color::RED));
+	assert_eq!(style.dash, vec![4.0, 2.0]);
+}
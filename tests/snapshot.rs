@@ -0,0 +1,135 @@
+mod common;
+
+use common::START;
+use common::game;
+use common::run;
+use serde_json::Value;
+use serde_json::json;
+
+#[test]
+fn title_has_nothing_to_snapshot() {
+	let game = game();
+	assert_eq!(game.snapshot().unwrap(), None);
+}
+
+#[test]
+fn restored_run_carries_on_identically() {
+	let mut original = game();
+	run(&mut original, &START,);
+	// kick up dust so the particles' generator is part of it
+	run(&mut original, &[(30, &["KeyF",],),],);
+	let saved = original.snapshot().unwrap().unwrap();
+	run(&mut original, &[(30, &["KeyF", "KeyS",],),],);
+
+	// restored from the title, which starts a run to put it in
+	let mut restored = game();
+	run(&mut restored, &[(45, &[],),],);
+	restored.restore(&saved,).unwrap();
+	assert_eq!(restored.scene_names(), ["Play"]);
+	assert_eq!(restored.snapshot().unwrap().unwrap(), saved);
+
+	run(&mut restored, &[(30, &["KeyF", "KeyS",],),],);
+	assert_eq!(restored.snapshot().unwrap(), original.snapshot().unwrap());
+}
+
+#[test]
+fn restoring_over_game_over_picks_the_run_back_up() {
+	let mut original = game();
+	run(&mut original, &START,);
+	let saved = original.snapshot().unwrap().unwrap();
+
+	let mut game = game();
+	run(&mut game, &START,);
+	run(&mut game, &[(120, &["KeyA",],),],);
+	assert_eq!(game.scene_names(), ["Play", "GameOver"]);
+	game.restore(&saved,).unwrap();
+	assert_eq!(game.scene_names(), ["Play"]);
+
+	// enter no longer starts over, it is just held while playing
+	run(&mut original, &[(30, &["Enter", "KeyD",],),],);
+	run(&mut game, &[(30, &["Enter", "KeyD",],),],);
+	assert_eq!(game.snapshot().unwrap(), original.snapshot().unwrap());
+}
+
+#[test]
+fn restoring_mid_fade_drops_the_change_waiting_on_it() {
+	let mut original = game();
+	run(&mut original, &START,);
+	run(&mut original, &[(20, &["KeyD",],),],);
+	let saved = original.snapshot().unwrap().unwrap();
+
+	// fading from the title into a fresh run
+	let mut game = game();
+	run(&mut game, &[(45, &[],), (1, &["Enter",],),],);
+	game.restore(&saved,).unwrap();
+	run(&mut game, &[(45, &[],),],);
+	assert_eq!(game.scene_names(), ["Play"]);
+	run(&mut original, &[(45, &[],),],);
+	assert_eq!(game.snapshot().unwrap(), original.snapshot().unwrap());
+}
+
+#[test]
+fn failed_restore_keeps_the_run() {
+	let mut game = game();
+	run(&mut game, &START,);
+	run(&mut game, &[(20, &["KeyD",],),],);
+	let before = game.snapshot().unwrap();
+	assert!(game.restore(r#"{"scene":"Play","state":{"lives":3}}"#,).is_err());
+	assert_eq!(game.snapshot().unwrap(), before);
+}
+
+#[test]
+fn tampered_snapshots_are_refused_without_panicking() {
+	let mut game = game();
+	run(&mut game, &START,);
+	run(&mut game, &[(20, &["KeyF",],),],);
+	let saved: Value =
+		serde_json::from_str(&game.snapshot().unwrap().unwrap(),).unwrap();
+	let tamper = |edit: fn(&mut Value,)| {
+		let mut json = saved.clone();
+		edit(&mut json["state"],);
+		json.to_string()
+	};
+	let tampered = [
+		tamper(|state| state["entities"]["free"] = json!([4000]),),
+		tamper(|state| state["entities"]["free"] = json!([0]),),
+		tamper(|state| state["particles"]["particles"][0]["kind"] = json!(9),),
+		tamper(|state| state["player"]["generation"] = json!(5),),
+	];
+	for json in tampered {
+		assert!(game.restore(&json,).is_err(), "{json}");
+		run(&mut game, &[(2, &["KeyF",],),],);
+	}
+}
+
+#[test]
+fn snapshots_from_before_the_dog_still_restore() {
+	let mut game = game();
+	run(&mut game, &START,);
+	let mut json: Value =
+		serde_json::from_str(&game.snapshot().unwrap().unwrap(),).unwrap();
+	let state = json["state"].as_object_mut().unwrap();
+	state.remove("dogs",);
+	state.remove("leashes",);
+	game.restore(&json.to_string(),).unwrap();
+	run(&mut game, &[(10, &["KeyD",],),],);
+	assert!(!game.entities().iter().any(|e| e.name == "Dog",));
+}
+
+#[test]
+fn paused_run_is_still_snapshotted() {
+	let mut game = game();
+	run(&mut game, &START,);
+	run(&mut game, &[(1, &["Escape",],),],);
+	assert_eq!(game.scene_names(), ["Play", "Pause"]);
+	assert!(game.snapshot().unwrap().is_some());
+}
+
+#[test]
+fn broken_snapshots_are_refused() {
+	let mut game = game();
+	assert!(game.restore("{}",).is_err());
+	assert!(game.restore(r#"{"scene":"Title","state":null}"#,).is_err());
+	run(&mut game, &START,);
+	assert!(game.restore(r#"{"scene":"Play","state":{"lives":3}}"#,).is_err());
+}
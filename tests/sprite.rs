@@ -0,0 +1,93 @@
+use rust_webpack_template::engn::DrawParams;
+use rust_webpack_template::engn::Image;
+use rust_webpack_template::engn::Point;
+use rust_webpack_template::engn::Renderer;
+use rust_webpack_template::engn::software::Pixmap;
+use serde_json::json;
+
+const RED: [u8; 4] = [255, 0, 0, 255,];
+const BLUE: [u8; 4] = [0, 0, 255, 255,];
+const CLEAR: [u8; 4] = [0, 0, 0, 0,];
+
+/// `w` x `h` pixels, `paint` picking the color of each
+fn pixmap(w: u32, h: u32, paint: impl Fn(u32, u32,) -> [u8; 4],) -> Pixmap {
+	let mut rgba = vec![];
+	for y in 0..h {
+		for x in 0..w {
+			rgba.extend(paint(x, y,),);
+		}
+	}
+	Pixmap::from_rgba(w, h, rgba,).unwrap()
+}
+
+/// sheet of a single sprite named "s" covering all of `pixmap`, cut out of
+/// a `source` sized image at `at`
+fn sheet(pixmap: Pixmap, at: (u32, u32,), source: (u32, u32,),) -> Image {
+	let (w, h,) = (pixmap.w, pixmap.h,);
+	let sheet = json!({
+		"frames": {
+			"s": {
+				"frame": { "x": 0, "y": 0, "w": w, "h": h },
+				"rotated": false,
+				"trimmed": (w, h) != source,
+				"spriteSourceSize": { "x": at.0, "y": at.1, "w": w, "h": h },
+				"sourceSize": { "w": source.0, "h": source.1 }
+			}
+		}
+	});
+	Image::from_pixmap(pixmap, Some(serde_json::from_value(sheet,).unwrap(),),)
+}
+
+/// sprite "s" of `image` drawn at `(10, 10)` on a 40x40 screen
+fn draw(image: &Image, params: DrawParams,) -> Pixmap {
+	let rndrr = Renderer::software(40, 40,).unwrap();
+	let at = Point { x: 10, y: 10, };
+	rndrr
+		.screen_space(|r| r.draw_sprite_sheet_with(image, "s", at, &params,),)
+		.unwrap();
+	rndrr.snapshot().unwrap()
+}
+
+#[test]
+fn trimmed_sprites_turn_around_the_untrimmed_pivot() {
+	// a 4x3 block at (6, 2) of a 10x10 sprite
+	let block = |x, y| (6..10).contains(&x,) && (2..5).contains(&y,);
+	let whole = sheet(
+		pixmap(10, 10, |x, y| if block(x, y,) { RED } else { CLEAR },),
+		(0, 0,),
+		(10, 10,),
+	);
+	let trimmed = sheet(pixmap(4, 3, |_, _| RED,), (6, 2,), (10, 10,),);
+	for pivot in [None, Some((0.0, 0.0,),), Some((3.0, 7.0,),),] {
+		let params = DrawParams {
+			flip_x: true,
+			flip_y: true,
+			pivot,
+			..Default::default()
+		};
+		assert_eq!(draw(&trimmed, params,), draw(&whole, params,), "{pivot:?}");
+	}
+}
+
+#[test]
+fn flipping_keeps_the_footprint_in_place() {
+	// red on the left, blue on the right
+	let image = sheet(
+		pixmap(10, 6, |x, _| if x < 5 { RED } else { BLUE },),
+		(0, 0,),
+		(10, 6,),
+	);
+	let flipped =
+		draw(&image, DrawParams { flip_x: true, ..Default::default() },);
+	for y in 0..40 {
+		for x in 0..40 {
+			let inside = (10..20).contains(&x,) && (10..16).contains(&y,);
+			let expected = match inside {
+				false => CLEAR,
+				true if x < 15 => BLUE,
+				true => RED,
+			};
+			assert_eq!(flipped.pixel(x, y,), expected, "{x}, {y}");
+		}
+	}
+}
@@ -0,0 +1,70 @@
+use rust_webpack_template::engn::Image;
+use rust_webpack_template::engn::camera::Bounds;
+use rust_webpack_template::engn::queue::Command;
+use rust_webpack_template::engn::tilemap::Grid;
+use rust_webpack_template::engn::tilemap::Tile;
+use rust_webpack_template::engn::tilemap::TileAnimation;
+use rust_webpack_template::engn::tilemap::TileLayer;
+use rust_webpack_template::engn::tilemap::Tilemap;
+use rust_webpack_template::engn::tilemap::Tileset;
+use std::path::Path;
+
+const GRID: Grid =
+	Grid { tile_w: 16, tile_h: 16, spacing: 2, margin: 1, columns: 4, };
+
+#[test]
+fn clip_skips_margin_and_spacing() {
+	assert_eq!(GRID.clip(0,), (1.0, 1.0));
+	assert_eq!(GRID.clip(5,), (19.0, 19.0));
+}
+
+#[test]
+fn gid_flags() {
+	assert_eq!(Tile::from_gid(0,), None);
+	assert_eq!(Tile::from_gid(3,), Some(Tile::new(2,)));
+	assert_eq!(
+		Tile::from_gid(0x8000_0003,),
+		Some(Tile { index: 2, flip_x: true, flip_y: false, })
+	);
+}
+
+#[test]
+fn csv_layer() {
+	let layer = TileLayer::from_csv("ground", "0,1,0\n2,2,2\n",).unwrap();
+	assert_eq!((layer.width(), layer.height()), (3, 2));
+	assert_eq!(layer.get(1, 0,), Some(Tile::new(0,)));
+	assert_eq!(layer.get(0, 0,), None);
+	assert_eq!(layer.get(5, 0,), None);
+
+	assert!(TileLayer::from_csv("ragged", "1,1\n1\n",).is_err());
+	assert!(TileLayer::from_csv("bad", "1,x\n",).is_err());
+}
+
+#[test]
+fn only_cells_in_view() {
+	let layer = TileLayer::new("ground", 100, 10,);
+	let view = Bounds { x: 40.0, y: -20.0, w: 40.0, h: 50.0, };
+	assert_eq!(layer.visible_cells(&GRID, (0.0, 0.0,), &view,), (2..6, 0..2));
+	let far = Bounds { x: -500.0, y: 0.0, w: 10.0, h: 10.0, };
+	assert_eq!(layer.visible_cells(&GRID, (0.0, 0.0,), &far,), (0..0, 0..1));
+}
+
+#[test]
+fn animated_tile_cycles() {
+	let anim =
+		TileAnimation { frames: vec![4, 5, 6], ticks_per_frame: 10, };
+	assert_eq!(anim.frame_at(0,), Some(4));
+	assert_eq!(anim.frame_at(19,), Some(5));
+	assert_eq!(anim.frame_at(30,), Some(4));
+}
+
+#[test]
+fn queued_tiles_of_a_missing_layer_print() {
+	let root = Path::new(env!("CARGO_MANIFEST_DIR"),);
+	let image = Image::read(root.join("static/rhb.png",), None,).unwrap();
+	let mut map = Tilemap::new(Tileset::new(image, GRID,),);
+	map.add_layer(TileLayer::new("ground", 2, 2,),);
+	let shown = |index| format!("{:?}", Command::Tiles { map: &map, index, });
+	assert!(shown(0).contains(r#"Some("ground")"#,), "{}", shown(0));
+	assert!(shown(7).contains("layer: None",), "{}", shown(7));
+}
@@ -0,0 +1,72 @@
+use rust_webpack_template::ANIMATION_CLIPS;
+use rust_webpack_template::DOG_CLIPS;
+use rust_webpack_template::engn::Sheet;
+use rust_webpack_template::engn::Size;
+use rust_webpack_template::engn::atlas;
+use rust_webpack_template::engn::validate::Clip;
+use rust_webpack_template::engn::validate::Problem;
+use rust_webpack_template::engn::validate::validate;
+
+fn sheet(frames: &[(&str, u16, u16,)],) -> Sheet {
+	let frames = frames
+		.iter()
+		.map(|(name, x, y,)| {
+			format!(
+				r#""{name}": {{ "frame": {{ "x": {x}, "y": {y}, "w": 10, "h": 10 }} }}"#
+			)
+		},)
+		.collect::<Vec<_,>>()
+		.join(",",);
+	serde_json::from_str(&format!(r#"{{ "frames": {{ {frames} }} }}"#),)
+		.expect("failed to build sheet",)
+}
+
+/// size of the image `static/{name}` really has
+fn image_size(name: &str,) -> Size {
+	let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"),)
+		.join("static",)
+		.join(name,);
+	let png = atlas::read_png(&path,).expect("failed to read sheet image",);
+	let side = |n| u16::try_from(n,).expect("sheet image is too large",);
+	Size { w: side(png.w,), h: side(png.h,), }
+}
+
+#[test]
+fn shipped_sheet_is_valid() {
+	let sheet: Sheet =
+		serde_json::from_str(include_str!("../static/rhb.json"),)
+			.expect("failed to parse rhb.json",);
+	let size = image_size("rhb.png",);
+	assert_eq!(validate(&sheet, size, ANIMATION_CLIPS,), []);
+}
+
+#[test]
+fn shipped_dog_sheet_is_valid() {
+	let sheet: Sheet =
+		serde_json::from_str(include_str!("../static/dog.json"),)
+			.expect("failed to parse dog.json",);
+	let size = image_size("dog.png",);
+	assert_eq!(validate(&sheet, size, DOG_CLIPS,), []);
+}
+
+#[test]
+fn reports_every_problem() {
+	let sheet = sheet(&[
+		("Walk (1).png", 0, 0,),
+		("Walk (3).png", 10, 0,),
+		("Walk (4).png", 20, 0,),
+		("Walk (6).png", 95, 0,),
+	],);
+	let clips =
+		[Clip { name: "Walk", cards: 3, }, Clip { name: "Bark", cards: 1, },];
+	let problems = validate(&sheet, Size { w: 100, h: 100, }, &clips,);
+	assert_eq!(
+		problems,
+		[
+			Problem::OutOfBounds { frame: "Walk (6).png".to_string(), },
+			Problem::MissingCards { clip: "Walk", cards: vec![2], },
+			Problem::Gap { clip: "Walk", after: 4, before: 6, },
+			Problem::MissingCards { clip: "Bark", cards: vec![1], },
+		]
+	);
+}
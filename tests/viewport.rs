@@ -0,0 +1,44 @@
+use rust_webpack_template::engn::viewport::ScalePolicy;
+use rust_webpack_template::engn::viewport::Viewport;
+use rust_webpack_template::engn::viewport::compose;
+
+#[test]
+fn native_follows_css_size_and_pixel_ratio() {
+	let fit = Viewport::native().fit(300.0, 200.0, 2.0,);
+	assert_eq!(fit.backing, (600, 400));
+	assert_eq!(fit.logical, (300.0, 200.0));
+	assert_eq!(fit.transform, [2.0, 0.0, 0.0, 2.0, 0.0, 0.0]);
+}
+
+#[test]
+fn letterbox_centers_uniform_scale() {
+	let fit = Viewport::fixed(600.0, 600.0, ScalePolicy::Letterbox,)
+		.fit(800.0, 600.0, 1.0,);
+	assert_eq!(fit.backing, (800, 600));
+	assert_eq!(fit.transform, [1.0, 0.0, 0.0, 1.0, 100.0, 0.0]);
+}
+
+#[test]
+fn stretch_fills_canvas() {
+	let fit = Viewport::fixed(600.0, 600.0, ScalePolicy::Stretch,)
+		.fit(800.0, 600.0, 1.5,);
+	assert_eq!(fit.backing, (1200, 900));
+	assert_eq!(fit.transform, [2.0, 0.0, 0.0, 1.5, 0.0, 0.0]);
+}
+
+#[test]
+fn pixel_perfect_scales_by_whole_numbers() {
+	let policy = ScalePolicy::PixelPerfect;
+	let fit = Viewport::fixed(320.0, 180.0, policy,).fit(1000.0, 600.0, 1.0,);
+	assert_eq!(fit.transform, [3.0, 0.0, 0.0, 3.0, 20.0, 30.0]);
+	// never scales below one
+	let fit = Viewport::fixed(320.0, 180.0, policy,).fit(200.0, 100.0, 1.0,);
+	assert_eq!(fit.transform[0], 1.0);
+}
+
+#[test]
+fn compose_applies_inner_first() {
+	let outer = [2.0, 0.0, 0.0, 2.0, 10.0, 0.0,];
+	let inner = [1.0, 0.0, 0.0, 1.0, 5.0, 5.0,];
+	assert_eq!(compose(outer, inner,), [2.0, 0.0, 0.0, 2.0, 20.0, 10.0]);
+}